@@ -0,0 +1,430 @@
+//! On-disk layout of the index.
+//!
+//! Events are stored under their contract, block number and event index, all
+//! big-endian, so that iterating a contract's prefix yields its events in chain
+//! order. The topic column repeats the key of every event under each of its
+//! topics.
+
+use crate::Error;
+use build3_node_runtime::{AccountId, BlockNumber, Hash};
+use codec::{Decode, Encode};
+use kvdb::{DBTransaction, KeyValueDB};
+use kvdb_rocksdb::{Database, DatabaseConfig};
+use serde::{Deserialize, Serialize};
+use std::{iter, path::Path};
+
+const COL_META: u32 = 0;
+const COL_EVENTS: u32 = 1;
+const COL_TOPICS: u32 = 2;
+const COL_CODE: u32 = 3;
+const NUM_COLUMNS: u32 = 4;
+
+const LAST_INDEXED_KEY: &[u8] = b"last_indexed";
+
+/// Most events returned in one page.
+pub const MAX_PAGE_SIZE: u32 = 1000;
+
+/// Events returned in one page unless the query asks for fewer.
+pub const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Where an event sits in the chain.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPosition {
+	pub block_number: BlockNumber,
+	/// Index of the event among all events of the block.
+	pub event_index: u32,
+}
+
+/// What a contract did.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ContractEvent {
+	/// The contract emitted an event of its own.
+	#[serde(rename_all = "camelCase")]
+	Emitted {
+		#[serde(with = "sp_core::bytes")]
+		data: Vec<u8>,
+	},
+	/// The contract was instantiated.
+	#[serde(rename_all = "camelCase")]
+	Instantiated { deployer: AccountId },
+	/// The contract was terminated.
+	#[serde(rename_all = "camelCase")]
+	Terminated { beneficiary: AccountId },
+}
+
+/// A contract event as kept in the index.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent {
+	#[serde(flatten)]
+	pub position: EventPosition,
+	pub block_hash: Hash,
+	/// `None` for events deposited outside of an extrinsic.
+	pub extrinsic_index: Option<u32>,
+	pub topics: Vec<Hash>,
+	#[serde(flatten)]
+	pub event: ContractEvent,
+}
+
+/// Upload of a contract code blob.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeUpload {
+	#[serde(flatten)]
+	pub position: EventPosition,
+	pub block_hash: Hash,
+	pub extrinsic_index: Option<u32>,
+}
+
+/// Events of a contract, filtered by topic and block range.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct EventQuery {
+	pub contract: AccountId,
+	/// Only events carrying this topic.
+	pub topic: Option<Hash>,
+	/// First block to include.
+	pub from_block: Option<BlockNumber>,
+	/// Last block to include.
+	pub to_block: Option<BlockNumber>,
+	/// Only events after this one, the `next` position of the previous page.
+	pub after: Option<EventPosition>,
+	/// Most events to return, at most [`MAX_PAGE_SIZE`].
+	pub limit: Option<u32>,
+}
+
+/// One page of events matching a query.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage {
+	pub events: Vec<IndexedEvent>,
+	/// Position to continue from, `None` on the last page.
+	pub next: Option<EventPosition>,
+}
+
+/// Contract events, code uploads and instantiations of finalized blocks.
+pub struct ContractsIndex {
+	db: Database,
+}
+
+impl ContractsIndex {
+	/// Open the index at `path`, creating it if needed.
+	pub fn open(path: &Path) -> Result<Self, Error> {
+		let path = path.to_str().ok_or_else(|| Error::InvalidPath(path.into()))?;
+		let db = Database::open(&DatabaseConfig::with_columns(NUM_COLUMNS), path)?;
+		Ok(Self { db })
+	}
+
+	/// The last block whose events have been indexed.
+	pub fn last_indexed(&self) -> Result<Option<BlockNumber>, Error> {
+		match self.db.get(COL_META, LAST_INDEXED_KEY)? {
+			Some(value) => Ok(Some(BlockNumber::decode(&mut &value[..])?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Store the contract events and code uploads of block `number`, and mark it
+	/// indexed.
+	pub fn insert_block(
+		&self,
+		number: BlockNumber,
+		events: Vec<(AccountId, IndexedEvent)>,
+		code_uploads: Vec<(Hash, CodeUpload)>,
+	) -> Result<(), Error> {
+		let mut tx = DBTransaction::new();
+		for (contract, event) in events {
+			let key = event_key(&contract, &event.position);
+			for topic in &event.topics {
+				tx.put(COL_TOPICS, &topic_key(&contract, topic, &event.position), &[]);
+			}
+			tx.put_vec(COL_EVENTS, &key, event.encode());
+		}
+		for (code_hash, upload) in code_uploads {
+			tx.put_vec(COL_CODE, code_hash.as_bytes(), upload.encode());
+		}
+		tx.put_vec(COL_META, LAST_INDEXED_KEY, number.encode());
+		self.db.write(tx)?;
+		Ok(())
+	}
+
+	/// Where and when the code with `code_hash` was uploaded.
+	pub fn code_upload(&self, code_hash: &Hash) -> Result<Option<CodeUpload>, Error> {
+		match self.db.get(COL_CODE, code_hash.as_bytes())? {
+			Some(value) => Ok(Some(CodeUpload::decode(&mut &value[..])?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Events matching `query`, oldest first.
+	pub fn events(&self, query: &EventQuery) -> Result<EventPage, Error> {
+		let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE) as usize;
+		let (column, prefix) = match &query.topic {
+			Some(topic) => (COL_TOPICS, [query.contract.as_ref(), topic.as_bytes()].concat()),
+			None => (COL_EVENTS, query.contract.as_ref().to_vec()),
+		};
+
+		// Reading starts at the block of `after` or `from_block`, whichever is
+		// later, the events of that block before the page are skipped below.
+		let start = query.after.map(|after| after.block_number).max(query.from_block);
+		let ranges: Box<dyn Iterator<Item = (BlockNumber, Vec<u8>)>> = match start {
+			Some(start) => Box::new(block_ranges(prefix, start)),
+			None => Box::new(iter::once((0, prefix))),
+		};
+
+		let mut events = Vec::new();
+		let mut next = None;
+		'ranges: for (first_block, range) in ranges {
+			if query.to_block.map_or(false, |to| first_block > to) {
+				break
+			}
+			for (key, _) in self.db.iter_with_prefix(column, &range) {
+				let position = match position_of(&key) {
+					Some(position) => position,
+					None => continue,
+				};
+				if query.after.map_or(false, |after| position <= after) ||
+					query.from_block.map_or(false, |from| position.block_number < from)
+				{
+					continue
+				}
+				if query.to_block.map_or(false, |to| position.block_number > to) {
+					break 'ranges
+				}
+				if events.len() == limit {
+					next = events.last().map(|event: &IndexedEvent| event.position);
+					break 'ranges
+				}
+
+				let value = self
+					.db
+					.get(COL_EVENTS, &event_key(&query.contract, &position))?
+					.ok_or(Error::MissingEvent(position))?;
+				events.push(IndexedEvent::decode(&mut &value[..])?);
+			}
+		}
+
+		Ok(EventPage { events, next })
+	}
+}
+
+fn event_key(contract: &AccountId, position: &EventPosition) -> Vec<u8> {
+	[contract.as_ref(), &position_suffix(position)[..]].concat()
+}
+
+fn topic_key(contract: &AccountId, topic: &Hash, position: &EventPosition) -> Vec<u8> {
+	[contract.as_ref(), topic.as_bytes(), &position_suffix(position)[..]].concat()
+}
+
+fn position_suffix(position: &EventPosition) -> [u8; 8] {
+	let mut suffix = [0u8; 8];
+	suffix[..4].copy_from_slice(&position.block_number.to_be_bytes());
+	suffix[4..].copy_from_slice(&position.event_index.to_be_bytes());
+	suffix
+}
+
+/// Key prefixes covering, in order, the keys under `prefix` of block `from` and
+/// of every later block, each with the first block it covers.
+///
+/// The database only iterates over prefixes, so the blocks after `from` are
+/// split along the bytes of their big-endian number: the rest of its run of 256
+/// blocks, then of 65536 blocks, and so on, a prefix for each run. Seeking to a
+/// page thus costs at most about a thousand empty prefixes, instead of reading
+/// every event before it.
+fn block_ranges(
+	prefix: Vec<u8>,
+	from: BlockNumber,
+) -> impl Iterator<Item = (BlockNumber, Vec<u8>)> {
+	let bytes = from.to_be_bytes();
+	let block = (from, [&prefix[..], &bytes[..]].concat());
+	let later = (0..bytes.len()).rev().flat_map(move |len| {
+		let prefix = prefix.clone();
+		(bytes[len]..=u8::MAX).skip(1).map(move |byte| {
+			let mut first = bytes;
+			first[len] = byte;
+			first[len + 1..].fill(0);
+			(BlockNumber::from_be_bytes(first), [&prefix[..], &first[..=len]].concat())
+		})
+	});
+	iter::once(block).chain(later)
+}
+
+/// Position encoded in the last eight bytes of an event or topic key.
+fn position_of(key: &[u8]) -> Option<EventPosition> {
+	let suffix = key.len().checked_sub(8).map(|start| &key[start..])?;
+	let (block_number, event_index) = suffix.split_at(4);
+	Some(EventPosition {
+		block_number: BlockNumber::from_be_bytes(block_number.try_into().ok()?),
+		event_index: u32::from_be_bytes(event_index.try_into().ok()?),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	const CONTRACT: [u8; 32] = [1; 32];
+	const OTHER: [u8; 32] = [2; 32];
+	const TOPIC: Hash = H256([7; 32]);
+
+	fn at(block_number: BlockNumber, event_index: u32) -> EventPosition {
+		EventPosition { block_number, event_index }
+	}
+
+	fn event(position: EventPosition, topics: Vec<Hash>) -> IndexedEvent {
+		IndexedEvent {
+			position,
+			block_hash: H256::repeat_byte(position.block_number as u8),
+			extrinsic_index: Some(position.event_index),
+			topics,
+			event: ContractEvent::Emitted { data: position.encode() },
+		}
+	}
+
+	fn query(contract: [u8; 32]) -> EventQuery {
+		EventQuery {
+			contract: contract.into(),
+			topic: None,
+			from_block: None,
+			to_block: None,
+			after: None,
+			limit: None,
+		}
+	}
+
+	fn positions(page: &EventPage) -> Vec<EventPosition> {
+		page.events.iter().map(|event| event.position).collect()
+	}
+
+	/// An index holding events of `CONTRACT` at `positions`, those with an odd
+	/// event index carrying `TOPIC`, and one event of `OTHER` with `TOPIC` in
+	/// each of their blocks.
+	fn index(positions: &[EventPosition]) -> (tempfile::TempDir, ContractsIndex) {
+		let dir = tempfile::tempdir().unwrap();
+		let index = ContractsIndex::open(dir.path()).unwrap();
+		for position in positions {
+			let topics = if position.event_index % 2 == 1 { vec![TOPIC] } else { vec![] };
+			let other = at(position.block_number, u32::MAX);
+			index
+				.insert_block(
+					position.block_number,
+					vec![
+						(CONTRACT.into(), event(*position, topics)),
+						(OTHER.into(), event(other, vec![TOPIC])),
+					],
+					vec![],
+				)
+				.unwrap();
+		}
+		(dir, index)
+	}
+
+	#[test]
+	fn keys_end_with_the_big_endian_position() {
+		let position = at(0x0102_0304, 0x0a0b_0c0d);
+		let suffix = [1, 2, 3, 4, 0x0a, 0x0b, 0x0c, 0x0d];
+
+		let key = event_key(&CONTRACT.into(), &position);
+		assert_eq!(key, [&[1; 32][..], &suffix].concat());
+		assert_eq!(position_of(&key), Some(position));
+
+		let key = topic_key(&CONTRACT.into(), &TOPIC, &position);
+		assert_eq!(key, [&[1; 32][..], &[7; 32], &suffix].concat());
+		assert_eq!(position_of(&key), Some(position));
+
+		assert_eq!(position_of(&suffix[1..]), None);
+	}
+
+	#[test]
+	fn keys_sort_in_chain_order() {
+		let positions = [at(0, 1), at(1, 0), at(255, 3), at(256, 0), at(256, 256), at(65_536, 2)];
+		let keys: Vec<_> = positions.iter().map(|p| event_key(&CONTRACT.into(), p)).collect();
+
+		let mut sorted = keys.clone();
+		sorted.sort();
+		assert_eq!(keys, sorted);
+	}
+
+	#[test]
+	fn block_ranges_cover_every_later_block_once() {
+		let ranges: Vec<_> = block_ranges(vec![9], 0x0100_fffe).collect();
+
+		assert_eq!(ranges[0], (0x0100_fffe, vec![9, 1, 0, 0xff, 0xfe]));
+		assert_eq!(ranges[1], (0x0100_ffff, vec![9, 1, 0, 0xff, 0xff]));
+		assert_eq!(ranges[2], (0x0101_0000, vec![9, 1, 1]));
+		assert_eq!(ranges[256], (0x01ff_0000, vec![9, 1, 0xff]));
+		assert_eq!(ranges[257], (0x0200_0000, vec![9, 2]));
+		assert_eq!(ranges.last(), Some(&(0xff00_0000, vec![9, 0xff])));
+		assert!(ranges.windows(2).all(|pair| pair[0] < pair[1]));
+		assert_eq!(ranges.len(), 1 + 1 + 255 + 254);
+	}
+
+	#[test]
+	fn pages_continue_after_the_last_event() {
+		let all = [at(1, 0), at(1, 1), at(255, 0), at(256, 1), at(70_000, 0), at(70_000, 3)];
+		let (_dir, index) = index(&all);
+
+		let mut query = EventQuery { limit: Some(4), ..query(CONTRACT) };
+		let page = index.events(&query).unwrap();
+		assert_eq!(positions(&page), &all[..4]);
+		assert_eq!(page.next, Some(at(256, 1)));
+		assert_eq!(page.events[0], event(at(1, 0), vec![]));
+
+		query.after = page.next;
+		let page = index.events(&query).unwrap();
+		assert_eq!(positions(&page), &all[4..]);
+		assert_eq!(page.next, None);
+	}
+
+	#[test]
+	fn pages_respect_the_block_range() {
+		let all = [at(1, 0), at(255, 0), at(255, 1), at(256, 1), at(70_000, 0)];
+		let (_dir, index) = index(&all);
+
+		let query = EventQuery { from_block: Some(255), to_block: Some(256), ..query(CONTRACT) };
+		assert_eq!(positions(&index.events(&query).unwrap()), &all[1..4]);
+
+		// `after` within the range moves the start further.
+		let query = EventQuery { after: Some(at(255, 0)), ..query };
+		assert_eq!(positions(&index.events(&query).unwrap()), &all[2..4]);
+
+		// `from_block` after `after` wins.
+		let query = EventQuery { after: Some(at(1, 0)), from_block: Some(256), ..query };
+		assert_eq!(positions(&index.events(&query).unwrap()), &all[3..4]);
+
+		let query = EventQuery { from_block: Some(70_001), to_block: None, ..query };
+		assert!(index.events(&query).unwrap().events.is_empty());
+	}
+
+	#[test]
+	fn topics_select_events_of_the_contract() {
+		let all = [at(1, 0), at(1, 1), at(300, 1), at(300, 2), at(80_000, 5)];
+		let (_dir, index) = index(&all);
+
+		let query = EventQuery { topic: Some(TOPIC), limit: Some(2), ..query(CONTRACT) };
+		let page = index.events(&query).unwrap();
+		assert_eq!(positions(&page), [at(1, 1), at(300, 1)]);
+
+		let query = EventQuery { after: page.next, ..query };
+		assert_eq!(positions(&index.events(&query).unwrap()), [at(80_000, 5)]);
+
+		// Topics of other contracts are not mixed in.
+		let query = EventQuery { topic: Some(TOPIC), ..query(OTHER) };
+		assert_eq!(index.events(&query).unwrap().events.len(), 3);
+	}
+
+	#[test]
+	fn page_sizes_are_capped() {
+		let all: Vec<_> = (0..MAX_PAGE_SIZE + 1).map(|i| at(i / 3, i % 3)).collect();
+		let (_dir, index) = index(&all);
+
+		let page = index.events(&EventQuery { limit: Some(u32::MAX), ..query(CONTRACT) }).unwrap();
+		assert_eq!(page.events.len(), MAX_PAGE_SIZE as usize);
+		let page = index.events(&query(CONTRACT)).unwrap();
+		assert_eq!(page.events.len(), DEFAULT_PAGE_SIZE as usize);
+	}
+}
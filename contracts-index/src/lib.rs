@@ -0,0 +1,180 @@
+//! Local index of contract activity for the Build3 node.
+//!
+//! Finding every `ContractEmitted` event of a contract through the generic RPC
+//! means scanning every block. Started with `--contracts-index`, the node instead
+//! follows finalized blocks and keeps the events, code uploads and
+//! instantiations of contracts in a database of its own, keyed by contract,
+//! topic and block. They are served through `contracts_getEvents` and
+//! `contracts_getCodeUpload`, see [`rpc`].
+
+#![warn(missing_docs)]
+
+#[allow(missing_docs)]
+pub mod db;
+pub mod rpc;
+
+use build3_node_runtime::{opaque::Block, BlockNumber, Event, Hash, Runtime};
+use codec::Decode;
+use frame_system::{EventRecord, Phase};
+use futures::StreamExt;
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::{generic::BlockId, traits::Header};
+use std::{fmt, io, path::PathBuf, sync::Arc};
+
+pub use db::{
+	CodeUpload, ContractEvent, ContractsIndex, EventPage, EventPosition, EventQuery, IndexedEvent,
+};
+
+/// Errors reading or writing the index.
+#[derive(Debug)]
+pub enum Error {
+	/// The database path is not valid UTF-8.
+	InvalidPath(PathBuf),
+	/// The database could not be read or written.
+	Io(io::Error),
+	/// A stored entry or the block's events could not be decoded.
+	Codec(codec::Error),
+	/// The chain could not be read.
+	Client(sp_blockchain::Error),
+	/// A topic entry points at an event that is not stored.
+	MissingEvent(EventPosition),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::InvalidPath(path) =>
+				write!(f, "Invalid contracts index path `{}`", path.display()),
+			Error::Io(e) => write!(f, "Contracts index database error: {}", e),
+			Error::Codec(e) => write!(f, "Contracts index decoding error: {}", e),
+			Error::Client(e) => write!(f, "Contracts index chain error: {}", e),
+			Error::MissingEvent(position) => write!(
+				f,
+				"Contracts index is missing event {} of block {}",
+				position.event_index, position.block_number
+			),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+impl From<codec::Error> for Error {
+	fn from(e: codec::Error) -> Self {
+		Error::Codec(e)
+	}
+}
+
+impl From<sp_blockchain::Error> for Error {
+	fn from(e: sp_blockchain::Error) -> Self {
+		Error::Client(e)
+	}
+}
+
+/// Index every finalized block, first catching up with the chain and then
+/// following finality notifications. Ends when the notification stream does.
+pub async fn run<BE, C>(client: Arc<C>, index: Arc<ContractsIndex>)
+where
+	BE: Backend<Block>,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C: Send + Sync + 'static,
+{
+	let mut finality_notifications = client.finality_notification_stream();
+
+	if let Err(e) = index_up_to(&*client, &index, client.info().finalized_number) {
+		log::error!(target: "contracts-index", "Indexing failed: {}", e);
+		return
+	}
+
+	while let Some(notification) = finality_notifications.next().await {
+		if let Err(e) = index_up_to(&*client, &index, *notification.header.number()) {
+			log::error!(target: "contracts-index", "Indexing failed: {}", e);
+			return
+		}
+	}
+}
+
+/// Index the blocks after the last indexed one, up to and including `target`.
+fn index_up_to<BE, C>(client: &C, index: &ContractsIndex, target: BlockNumber) -> Result<(), Error>
+where
+	BE: Backend<Block>,
+	C: StorageProvider<Block, BE> + HeaderBackend<Block>,
+{
+	let mut number = index.last_indexed()?.map_or(0, |last| last + 1);
+	while number <= target {
+		let hash = client
+			.hash(number)?
+			.ok_or_else(|| sp_blockchain::Error::UnknownBlock(number.to_string()))?;
+		index_block(client, index, number, hash)?;
+		number += 1;
+	}
+	Ok(())
+}
+
+/// Index the contract events of one block.
+fn index_block<BE, C>(
+	client: &C,
+	index: &ContractsIndex,
+	number: BlockNumber,
+	hash: Hash,
+) -> Result<(), Error>
+where
+	BE: Backend<Block>,
+	C: StorageProvider<Block, BE>,
+{
+	let key = StorageKey(frame_system::Events::<Runtime>::hashed_key().to_vec());
+	let records = match client.storage(&BlockId::Hash(hash), &key)? {
+		Some(data) => Vec::<EventRecord<Event, Hash>>::decode(&mut &data.0[..])?,
+		None => Vec::new(),
+	};
+
+	let mut events = Vec::new();
+	let mut code_uploads = Vec::new();
+	for (event_index, record) in records.into_iter().enumerate() {
+		let position = EventPosition { block_number: number, event_index: event_index as u32 };
+		let extrinsic_index = match record.phase {
+			Phase::ApplyExtrinsic(index) => Some(index),
+			_ => None,
+		};
+		let (contract, event) = match record.event {
+			Event::Contracts(pallet_contracts::Event::<Runtime>::ContractEmitted {
+				contract,
+				data,
+			}) => (contract, ContractEvent::Emitted { data }),
+			Event::Contracts(pallet_contracts::Event::<Runtime>::Instantiated {
+				deployer,
+				contract,
+			}) => (contract, ContractEvent::Instantiated { deployer }),
+			Event::Contracts(pallet_contracts::Event::<Runtime>::Terminated {
+				contract,
+				beneficiary,
+			}) => (contract, ContractEvent::Terminated { beneficiary }),
+			Event::Contracts(pallet_contracts::Event::<Runtime>::CodeStored { code_hash }) => {
+				code_uploads
+					.push((code_hash, CodeUpload { position, block_hash: hash, extrinsic_index }));
+				continue
+			},
+			_ => continue,
+		};
+		events.push((
+			contract,
+			IndexedEvent {
+				position,
+				block_hash: hash,
+				extrinsic_index,
+				topics: record.topics,
+				event,
+			},
+		));
+	}
+
+	index.insert_block(number, events, code_uploads)
+}
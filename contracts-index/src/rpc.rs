@@ -0,0 +1,56 @@
+//! RPC methods serving the contracts index.
+//!
+//! They extend the `contracts_*` namespace of `pallet-contracts-rpc` and are only
+//! available on nodes started with `--contracts-index`.
+
+use crate::{CodeUpload, ContractsIndex, EventPage, EventQuery};
+use build3_node_runtime::Hash;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use std::sync::Arc;
+
+const INDEX_ERROR: i64 = 1;
+
+/// Contracts index RPC methods.
+#[rpc]
+pub trait ContractsIndexApi {
+	/// Page through the indexed events of a contract, optionally only those with
+	/// a given topic or within a block range. Only finalized blocks are indexed.
+	#[rpc(name = "contracts_getEvents")]
+	fn events(&self, query: EventQuery) -> Result<EventPage>;
+
+	/// Look up where the code with the given hash was uploaded.
+	#[rpc(name = "contracts_getCodeUpload")]
+	fn code_upload(&self, code_hash: Hash) -> Result<Option<CodeUpload>>;
+}
+
+/// Serves queries from a [`ContractsIndex`].
+pub struct ContractsIndexRpc {
+	index: Arc<ContractsIndex>,
+}
+
+impl ContractsIndexRpc {
+	/// Create new `ContractsIndexRpc` reading from `index`.
+	pub fn new(index: Arc<ContractsIndex>) -> Self {
+		Self { index }
+	}
+}
+
+/// Converts an index error into an RPC error.
+fn index_error_into_rpc_err(err: crate::Error) -> Error {
+	Error {
+		code: ErrorCode::ServerError(INDEX_ERROR),
+		message: "Contracts index error".into(),
+		data: Some(err.to_string().into()),
+	}
+}
+
+impl ContractsIndexApi for ContractsIndexRpc {
+	fn events(&self, query: EventQuery) -> Result<EventPage> {
+		self.index.events(&query).map_err(index_error_into_rpc_err)
+	}
+
+	fn code_upload(&self, code_hash: Hash) -> Result<Option<CodeUpload>> {
+		self.index.code_upload(&code_hash).map_err(index_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,71 @@
+//! Runtime API definition for the governance RPC.
+//!
+//! Open motions of every collective instance of the runtime, such as the
+//! council and the state boards, together with their votes and deadlines.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// An open motion of a collective.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CollectiveProposal<AccountId, BlockNumber, Hash> {
+	/// Hash of the proposed call.
+	pub hash: Hash,
+	/// Proposal index within the collective.
+	pub index: u32,
+	/// Name of the pallet of the proposed call.
+	pub pallet: Vec<u8>,
+	/// Name of the proposed call.
+	pub method: Vec<u8>,
+	/// The proposed call, SCALE encoded.
+	pub call: Vec<u8>,
+	/// Number of approvals required.
+	pub threshold: u32,
+	pub ayes: Vec<AccountId>,
+	pub nays: Vec<AccountId>,
+	/// Block after which the motion may be closed.
+	pub end: BlockNumber,
+	/// Blocks left until `end`, zero once the motion may be closed.
+	pub blocks_remaining: BlockNumber,
+}
+
+/// A collective with its open motions.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CollectiveState<AccountId, BlockNumber, Hash> {
+	pub members: Vec<AccountId>,
+	/// Member whose vote counts for members who abstain.
+	pub prime: Option<AccountId>,
+	/// Blocks a motion stays open for.
+	pub motion_duration: BlockNumber,
+	pub proposals: Vec<CollectiveProposal<AccountId, BlockNumber, Hash>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the motions of the runtime's collectives.
+	pub trait GovernanceApi<AccountId, BlockNumber, Hash, CollectiveId> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+		CollectiveId: Codec,
+	{
+		/// Every collective of the runtime.
+		fn collectives() -> Vec<CollectiveId>;
+
+		/// Members, prime and open motions of `collective`.
+		fn collective(collective: CollectiveId) -> CollectiveState<AccountId, BlockNumber, Hash>;
+
+		/// Motions of `collective` closed in this block, with whether each was
+		/// approved.
+		fn closed_proposals(collective: CollectiveId) -> Vec<(Hash, bool)>;
+	}
+}
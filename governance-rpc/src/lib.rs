@@ -0,0 +1,327 @@
+//! Node-specific RPC methods to follow the motions of the runtime's collectives.
+//!
+//! The `governance_*` namespace lists the open motions of the council or of a
+//! board, with the proposed call decoded, the votes cast so far, the threshold,
+//! the blocks left to vote and the collective's prime member. Board member
+//! portals can subscribe to a collective to learn of new motions and closures
+//! as they happen, e.g. to send voting reminders.
+
+use codec::{Codec, Decode};
+use futures::{future, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_client_api::BlockchainEvents;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
+};
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+
+pub use build3_governance_rpc_runtime_api::{
+	CollectiveProposal, CollectiveState, GovernanceApi as GovernanceRuntimeApi,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Most blocks searched for the closures of motions when the best block moves
+/// by more than one block.
+const MAX_CATCH_UP_BLOCKS: usize = 256;
+
+/// An open motion, with its call decoded.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposalDetails<AccountId, BlockNumber, Hash> {
+	pub hash: Hash,
+	pub index: u32,
+	pub pallet: String,
+	pub method: String,
+	/// The proposed call with its arguments, `None` if this node cannot decode
+	/// it, e.g. after a runtime upgrade.
+	pub call: Option<String>,
+	pub threshold: u32,
+	pub ayes: Vec<AccountId>,
+	pub nays: Vec<AccountId>,
+	pub end: BlockNumber,
+	pub blocks_remaining: BlockNumber,
+}
+
+/// A collective with its open motions.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectiveOverview<AccountId, BlockNumber, Hash> {
+	pub members: Vec<AccountId>,
+	pub prime: Option<AccountId>,
+	pub motion_duration: BlockNumber,
+	pub proposals: Vec<ProposalDetails<AccountId, BlockNumber, Hash>>,
+}
+
+/// Notification sent to subscribers following a collective.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ProposalNotification<BlockHash, AccountId, BlockNumber, Hash> {
+	/// A motion was opened, or was open when the subscription started.
+	#[serde(rename_all = "camelCase")]
+	Proposed { block: BlockHash, proposal: ProposalDetails<AccountId, BlockNumber, Hash> },
+	/// A motion announced to the subscriber was closed, in `block`.
+	///
+	/// `approved` is `None` when the motion is gone but its closure was not
+	/// found: it happened more than `MAX_CATCH_UP_BLOCKS` blocks before the
+	/// best block it was noticed at, or on a fork the best chain left. `block`
+	/// is then that best block.
+	#[serde(rename_all = "camelCase")]
+	Closed { block: BlockHash, hash: Hash, approved: Option<bool> },
+}
+
+/// Governance RPC methods.
+#[rpc]
+pub trait GovernanceApi<BlockHash, AccountId, BlockNumber, Hash, CollectiveId> {
+	/// RPC metadata
+	type Metadata;
+
+	/// List the collectives of the runtime.
+	#[rpc(name = "governance_getCollectives")]
+	fn collectives(&self, at: Option<BlockHash>) -> Result<Vec<CollectiveId>>;
+
+	/// Show the members, prime and open motions of a collective.
+	#[rpc(name = "governance_getProposals")]
+	fn proposals(
+		&self,
+		collective: CollectiveId,
+		at: Option<BlockHash>,
+	) -> Result<CollectiveOverview<AccountId, BlockNumber, Hash>>;
+
+	/// Follow a collective, receiving its open motions and then every motion
+	/// opened or closed on the best chain.
+	#[pubsub(
+		subscription = "governance_proposals",
+		subscribe,
+		name = "governance_subscribeProposals"
+	)]
+	fn subscribe_proposals(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ProposalNotification<BlockHash, AccountId, BlockNumber, Hash>>,
+		collective: CollectiveId,
+	);
+
+	/// Stop following a collective.
+	#[pubsub(
+		subscription = "governance_proposals",
+		unsubscribe,
+		name = "governance_unsubscribeProposals"
+	)]
+	fn unsubscribe_proposals(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
+/// Provides RPC methods to follow collective motions.
+///
+/// Proposed calls are decoded as `Call`, the runtime's call type.
+pub struct Governance<C, B, Call> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	_marker: PhantomData<(B, Call)>,
+}
+
+impl<C, B, Call> Governance<C, B, Call> {
+	/// Create new `Governance` with the given reference to the client.
+	pub fn new(client: Arc<C>, manager: SubscriptionManager) -> Self {
+		Self { client, manager, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+fn details<Call: Decode + Debug, AccountId, BlockNumber, Hash>(
+	proposal: CollectiveProposal<AccountId, BlockNumber, Hash>,
+) -> ProposalDetails<AccountId, BlockNumber, Hash> {
+	ProposalDetails {
+		hash: proposal.hash,
+		index: proposal.index,
+		pallet: String::from_utf8_lossy(&proposal.pallet).into_owned(),
+		method: String::from_utf8_lossy(&proposal.method).into_owned(),
+		call: Call::decode(&mut &proposal.call[..]).ok().map(|call| format!("{:?}", call)),
+		threshold: proposal.threshold,
+		ayes: proposal.ayes,
+		nays: proposal.nays,
+		end: proposal.end,
+		blocks_remaining: proposal.blocks_remaining,
+	}
+}
+
+/// `best` and its ancestors after block `last`, oldest first, at most
+/// `MAX_CATCH_UP_BLOCKS` of them.
+fn blocks_since<Block: BlockT>(
+	client: &impl HeaderBackend<Block>,
+	best: Block::Hash,
+	last: NumberFor<Block>,
+) -> Vec<Block::Hash> {
+	let mut blocks = vec![best];
+	while blocks.len() < MAX_CATCH_UP_BLOCKS {
+		match client.header(BlockId::hash(blocks[blocks.len() - 1])) {
+			Ok(Some(header)) if *header.number() > last + One::one() =>
+				blocks.push(*header.parent_hash()),
+			_ => break,
+		}
+	}
+	blocks.reverse();
+	blocks
+}
+
+impl<C, Block, Call, AccountId, BlockNumber, Hash, CollectiveId>
+	GovernanceApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Hash, CollectiveId>
+	for Governance<C, Block, Call>
+where
+	Block: BlockT,
+	Call: Decode + Debug + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C: Send + Sync + 'static,
+	C::Api: GovernanceRuntimeApi<Block, AccountId, BlockNumber, Hash, CollectiveId>,
+	AccountId: Codec + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+	Hash: Codec + Clone + PartialEq + Send + Sync + 'static,
+	CollectiveId: Codec + Clone + Send + Sync + 'static,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn collectives(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<CollectiveId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.collectives(&at).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn proposals(
+		&self,
+		collective: CollectiveId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CollectiveOverview<AccountId, BlockNumber, Hash>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let state = api.collective(&at, collective).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(CollectiveOverview {
+			members: state.members,
+			prime: state.prime,
+			motion_duration: state.motion_duration,
+			proposals: state.proposals.into_iter().map(details::<Call, _, _, _>).collect(),
+		})
+	}
+
+	fn subscribe_proposals(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<
+			ProposalNotification<<Block as BlockT>::Hash, AccountId, BlockNumber, Hash>,
+		>,
+		collective: CollectiveId,
+	) {
+		let client = self.client.clone();
+		// Hashes of the motions open at the last block looked at, and its number.
+		let mut open: Vec<Hash> = Vec::new();
+		let mut last: Option<NumberFor<Block>> = None;
+		let mut changes_at = move |block: <Block as BlockT>::Hash| {
+			let api = client.runtime_api();
+			let state = match api.collective(&BlockId::hash(block), collective.clone()) {
+				Ok(state) => state,
+				Err(e) => {
+					log::warn!(target: "rpc", "Governance proposals lookup failed: {:?}", e);
+					return Vec::new()
+				},
+			};
+			// Closures up to the first block looked at happened before the
+			// subscription started. After it, the best block may move by several
+			// blocks at once, each of which may close motions.
+			let blocks = match last {
+				Some(last) => blocks_since(&*client, block, last),
+				None => Vec::new(),
+			};
+			let closed: Vec<_> = blocks
+				.into_iter()
+				.flat_map(|at| {
+					api.closed_proposals(&BlockId::hash(at), collective.clone())
+						.unwrap_or_else(|e| {
+							log::warn!(target: "rpc", "Governance closures lookup failed: {:?}", e);
+							Vec::new()
+						})
+						.into_iter()
+						.map(move |(hash, approved)| (at, hash, approved))
+				})
+				.collect();
+
+			let now_open: Vec<Hash> = state.proposals.iter().map(|p| p.hash.clone()).collect();
+			let mut notifications = Vec::new();
+			// Motions opened and closed between two blocks looked at were never
+			// announced, and their closure is not either.
+			for (at, hash, approved) in &closed {
+				if open.contains(hash) && !now_open.contains(hash) {
+					notifications.push(ProposalNotification::Closed {
+						block: *at,
+						hash: hash.clone(),
+						approved: Some(*approved),
+					});
+				}
+			}
+			for hash in open.iter().filter(|hash| !now_open.contains(hash)) {
+				if !closed.iter().any(|(_, closed, _)| closed == hash) {
+					notifications.push(ProposalNotification::Closed {
+						block,
+						hash: hash.clone(),
+						approved: None,
+					});
+				}
+			}
+			for proposal in state.proposals.into_iter().filter(|p| !open.contains(&p.hash)) {
+				notifications.push(ProposalNotification::Proposed {
+					block,
+					proposal: details::<Call, _, _, _>(proposal),
+				});
+			}
+
+			open = now_open;
+			last = client.number(block).ok().flatten();
+			notifications
+		};
+
+		// Start from the motions open at the current best block.
+		let initial = changes_at(self.client.info().best_hash);
+		let changes = self
+			.client
+			.import_notification_stream()
+			.filter(|notification| future::ready(notification.is_new_best))
+			.flat_map(move |notification| futures::stream::iter(changes_at(notification.hash)));
+
+		self.manager.add(subscriber, |sink| {
+			futures::stream::iter(initial)
+				.chain(changes)
+				.map(|notification| Ok::<_, ()>(Ok(notification)))
+				.forward(sink.sink_map_err(
+					|e| log::warn!(target: "rpc", "Error sending governance notification: {:?}", e),
+				))
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_proposals(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
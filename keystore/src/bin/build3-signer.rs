@@ -0,0 +1,51 @@
+//! Reference signer daemon for the Build3 remote keystore.
+
+use build3_remote_keystore::server;
+use clap::Parser;
+use sc_keystore::LocalKeystore;
+use sp_core::crypto::SecretString;
+use std::{fs, os::unix::net::UnixListener, path::PathBuf, sync::Arc};
+
+#[derive(Debug, Parser)]
+#[clap(name = "build3-signer", about = "Serve a keystore to a Build3 node over a Unix socket.")]
+struct Opts {
+	/// Path of the Unix socket to listen on.
+	#[clap(long, parse(from_os_str))]
+	socket: PathBuf,
+
+	/// Directory of the file keystore holding the keys.
+	#[clap(long, parse(from_os_str))]
+	keystore_path: PathBuf,
+
+	/// File containing the keystore password.
+	#[clap(long, parse(from_os_str))]
+	password_filename: Option<PathBuf>,
+}
+
+fn main() -> Result<(), String> {
+	let opts = Opts::parse();
+
+	let password = opts
+		.password_filename
+		.map(|path| {
+			fs::read_to_string(&path)
+				.map(|password| SecretString::new(password.trim_end().into()))
+				.map_err(|e| format!("Reading {}: {}", path.display(), e))
+		})
+		.transpose()?;
+
+	let keystore = LocalKeystore::open(&opts.keystore_path, password)
+		.map_err(|e| format!("Opening keystore {}: {}", opts.keystore_path.display(), e))?;
+
+	// A socket left behind by a previous run would make `bind` fail.
+	if opts.socket.exists() {
+		fs::remove_file(&opts.socket)
+			.map_err(|e| format!("Removing stale {}: {}", opts.socket.display(), e))?;
+	}
+	let listener = UnixListener::bind(&opts.socket)
+		.map_err(|e| format!("Binding {}: {}", opts.socket.display(), e))?;
+
+	eprintln!("build3-signer listening on {}", opts.socket.display());
+
+	server::serve(listener, Arc::new(keystore)).map_err(|e| e.to_string())
+}
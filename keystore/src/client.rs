@@ -0,0 +1,487 @@
+//! Keystore which forwards every operation to a remote signer.
+
+use crate::protocol::*;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_core::{
+	crypto::{CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+	sr25519::vrf::{VRFSignature, VRFTranscriptData},
+	ByteArray,
+};
+use sp_keystore::{CryptoStore, Error as TraitError, SyncCryptoStore};
+use std::{
+	fmt,
+	io::{self, BufRead, BufReader, Write},
+	os::unix::net::UnixStream,
+	path::PathBuf,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+/// Errors talking to the remote signer.
+#[derive(Debug)]
+pub enum Error {
+	/// The keystore URL is not a `unix://` socket path.
+	InvalidUrl(String),
+	/// The socket could not be reached or was closed.
+	Io(io::Error),
+	/// The signer sent something that is not a valid response.
+	Json(serde_json::Error),
+	/// The signer answered with an error.
+	Rpc(RpcError),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::InvalidUrl(url) =>
+				write!(f, "Invalid remote keystore url `{}`, expected `unix://<path>`", url),
+			Error::Io(e) => write!(f, "Remote keystore connection failed: {}", e),
+			Error::Json(e) => write!(f, "Invalid remote keystore response: {}", e),
+			Error::Rpc(e) => write!(f, "Remote keystore error {}: {}", e.code, e.message),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+impl From<serde_json::Error> for Error {
+	fn from(e: serde_json::Error) -> Self {
+		Error::Json(e)
+	}
+}
+
+impl From<Error> for TraitError {
+	fn from(e: Error) -> Self {
+		match e {
+			Error::Io(_) => TraitError::Unavailable,
+			e => TraitError::Other(e.to_string()),
+		}
+	}
+}
+
+/// How long the signer may take to accept a request or to answer it before the
+/// request fails.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A keystore holding no keys itself, asking a remote signer over a Unix socket
+/// instead. See [`crate::protocol`] for the messages exchanged.
+///
+/// Clones share the same connection.
+#[derive(Clone)]
+pub struct RemoteKeystore {
+	inner: Arc<Inner>,
+}
+
+struct Inner {
+	path: PathBuf,
+	connection: Mutex<Option<BufReader<UnixStream>>>,
+	next_id: AtomicU64,
+}
+
+/// Why an exchange with the signer failed.
+enum Failure {
+	/// Nothing of the request reached the signer, so it may be sent again.
+	NotSent(Error),
+	/// The signer may have received the request, and may even have acted on it.
+	Sent(Error),
+}
+
+impl Failure {
+	fn write(written: usize, e: io::Error) -> Self {
+		if written == 0 {
+			Failure::NotSent(e.into())
+		} else {
+			Failure::Sent(e.into())
+		}
+	}
+}
+
+impl RemoteKeystore {
+	/// Connect to the signer listening at `url`, given as `unix://<path>`.
+	pub fn open(url: &str) -> Result<Self, Error> {
+		let path = url
+			.strip_prefix("unix://")
+			.filter(|path| !path.is_empty())
+			.ok_or_else(|| Error::InvalidUrl(url.into()))?;
+
+		let keystore = RemoteKeystore {
+			inner: Arc::new(Inner {
+				path: PathBuf::from(path),
+				connection: Mutex::new(None),
+				next_id: AtomicU64::new(0),
+			}),
+		};
+		// Fail early instead of on the first signing request.
+		*keystore.inner.connection.lock() = Some(keystore.connect()?);
+
+		Ok(keystore)
+	}
+
+	fn connect(&self) -> Result<BufReader<UnixStream>, Error> {
+		let stream = UnixStream::connect(&self.inner.path)?;
+		stream.set_read_timeout(Some(TIMEOUT))?;
+		stream.set_write_timeout(Some(TIMEOUT))?;
+
+		Ok(BufReader::new(stream))
+	}
+
+	/// Send one request and wait for its response.
+	///
+	/// A broken connection is reopened once before giving up, so that a restarted
+	/// signer does not take the node down with it. The request is only sent again
+	/// if none of it was written the first time: generating or inserting a key
+	/// twice is not harmless.
+	fn call<P: Serialize, R: DeserializeOwned>(&self, method: &str, params: P) -> Result<R, Error> {
+		let request = Request {
+			jsonrpc: JSONRPC_VERSION.into(),
+			id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+			method: method.into(),
+			params: serde_json::to_value(params)?,
+		};
+		let mut line = serde_json::to_string(&request)?;
+		line.push('\n');
+
+		let mut connection = self.inner.connection.lock();
+		let result = match self.exchange(&mut connection, &line) {
+			Err(Failure::NotSent(e)) => {
+				log::debug!(target: "remote-keystore", "Reconnecting after: {}", e);
+				*connection = None;
+				self.exchange(&mut connection, &line)
+			},
+			result => result,
+		};
+		let response = result.map_err(|failure| {
+			// A request that failed half way leaves the connection out of step with
+			// the signer, a late answer would be taken for the next request's.
+			*connection = None;
+			match failure {
+				Failure::NotSent(e) | Failure::Sent(e) => e,
+			}
+		})?;
+
+		if response.id != Some(request.id) {
+			*connection = None;
+			return Err(Error::Rpc(RpcError {
+				code: INVALID_REQUEST,
+				message: format!("Response id {:?} does not match {}", response.id, request.id),
+			}))
+		}
+
+		match (response.result, response.error) {
+			(_, Some(error)) => Err(Error::Rpc(error)),
+			(result, None) => Ok(serde_json::from_value(result.unwrap_or_default())?),
+		}
+	}
+
+	fn exchange(
+		&self,
+		connection: &mut Option<BufReader<UnixStream>>,
+		line: &str,
+	) -> Result<Response, Failure> {
+		let stream = match connection {
+			Some(stream) => stream,
+			None => connection.insert(self.connect().map_err(Failure::NotSent)?),
+		};
+
+		let mut written = 0;
+		while written < line.len() {
+			match stream.get_mut().write(&line.as_bytes()[written..]) {
+				Ok(0) => return Err(Failure::write(written, io::ErrorKind::WriteZero.into())),
+				Ok(n) => written += n,
+				Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+				Err(e) => return Err(Failure::write(written, e)),
+			}
+		}
+
+		let mut answer = String::new();
+		match stream.read_line(&mut answer) {
+			Ok(0) => return Err(Failure::Sent(Error::Io(io::ErrorKind::UnexpectedEof.into()))),
+			Ok(_) => {},
+			Err(e) => return Err(Failure::Sent(e.into())),
+		}
+
+		serde_json::from_str(&answer).map_err(|e| Failure::Sent(e.into()))
+	}
+
+	fn public_keys<T: ByteArray>(&self, key_type: KeyTypeId, crypto: Crypto) -> Vec<T> {
+		self.call::<_, Vec<sp_core::Bytes>>(
+			PUBLIC_KEYS,
+			PublicKeysParams { key_type: KeyType(key_type), crypto },
+		)
+		.map_err(|e| log::warn!(target: "remote-keystore", "Listing public keys failed: {}", e))
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|public| T::from_slice(&public).ok())
+		.collect()
+	}
+
+	fn generate_new<T: ByteArray>(
+		&self,
+		key_type: KeyTypeId,
+		crypto: Crypto,
+		seed: Option<&str>,
+	) -> Result<T, TraitError> {
+		let public: sp_core::Bytes = self.call(
+			GENERATE_NEW,
+			GenerateNewParams { key_type: KeyType(key_type), crypto, seed: seed.map(Into::into) },
+		)?;
+
+		T::from_slice(&public).map_err(|_| TraitError::ValidationError("Invalid public key".into()))
+	}
+}
+
+/// Run `f`, which talks to the signer, off the async executor.
+///
+/// Outside of a Tokio runtime there is no blocking pool to hand it to, and it
+/// runs in place.
+async fn unblock<R, F>(f: F) -> R
+where
+	F: FnOnce() -> R + Send + 'static,
+	R: Send + 'static,
+{
+	match tokio::runtime::Handle::try_current() {
+		Ok(handle) => handle
+			.spawn_blocking(f)
+			.await
+			.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())),
+		Err(_) => f(),
+	}
+}
+
+#[async_trait]
+impl CryptoStore for RemoteKeystore {
+	async fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		let keystore = self.clone();
+		unblock(move || SyncCryptoStore::sr25519_public_keys(&keystore, id)).await
+	}
+
+	async fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(String::from));
+		unblock(move || SyncCryptoStore::sr25519_generate_new(&keystore, id, seed.as_deref())).await
+	}
+
+	async fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		let keystore = self.clone();
+		unblock(move || SyncCryptoStore::ed25519_public_keys(&keystore, id)).await
+	}
+
+	async fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(String::from));
+		unblock(move || SyncCryptoStore::ed25519_generate_new(&keystore, id, seed.as_deref())).await
+	}
+
+	async fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		let keystore = self.clone();
+		unblock(move || SyncCryptoStore::ecdsa_public_keys(&keystore, id)).await
+	}
+
+	async fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, TraitError> {
+		let (keystore, seed) = (self.clone(), seed.map(String::from));
+		unblock(move || SyncCryptoStore::ecdsa_generate_new(&keystore, id, seed.as_deref())).await
+	}
+
+	async fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		let (keystore, suri, public) = (self.clone(), suri.to_owned(), public.to_vec());
+		unblock(move || SyncCryptoStore::insert_unknown(&keystore, id, &suri, &public)).await
+	}
+
+	async fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let keystore = self.clone();
+		unblock(move || SyncCryptoStore::supported_keys(&keystore, id, keys)).await
+	}
+
+	async fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let keystore = self.clone();
+		unblock(move || SyncCryptoStore::keys(&keystore, id)).await
+	}
+
+	async fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let (keystore, public_keys) = (self.clone(), public_keys.to_vec());
+		unblock(move || SyncCryptoStore::has_keys(&keystore, &public_keys)).await
+	}
+
+	async fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, TraitError> {
+		let (keystore, key, msg) = (self.clone(), key.clone(), msg.to_vec());
+		unblock(move || SyncCryptoStore::sign_with(&keystore, id, &key, &msg)).await
+	}
+
+	async fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, TraitError> {
+		// Refused without asking the signer, nothing to move off the executor.
+		SyncCryptoStore::sr25519_vrf_sign(self, key_type, public, transcript_data)
+	}
+
+	async fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		let (keystore, public, msg) = (self.clone(), *public, *msg);
+		unblock(move || SyncCryptoStore::ecdsa_sign_prehashed(&keystore, id, &public, &msg)).await
+	}
+}
+
+impl SyncCryptoStore for RemoteKeystore {
+	fn sr25519_public_keys(&self, id: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(id, Crypto::Sr25519)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, TraitError> {
+		self.generate_new(id, Crypto::Sr25519, seed)
+	}
+
+	fn ed25519_public_keys(&self, id: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(id, Crypto::Ed25519)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, TraitError> {
+		self.generate_new(id, Crypto::Ed25519, seed)
+	}
+
+	fn ecdsa_public_keys(&self, id: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(id, Crypto::Ecdsa)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		id: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, TraitError> {
+		self.generate_new(id, Crypto::Ecdsa, seed)
+	}
+
+	fn insert_unknown(&self, id: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.call::<_, ()>(
+			INSERT_UNKNOWN,
+			InsertUnknownParams { key_type: KeyType(id), suri: suri.into(), public: public.into() },
+		)
+		.map_err(|e| log::warn!(target: "remote-keystore", "Inserting key failed: {}", e))
+	}
+
+	fn supported_keys(
+		&self,
+		id: KeyTypeId,
+		keys: Vec<CryptoTypePublicPair>,
+	) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let keys = keys.iter().filter_map(PublicPair::from_pair).collect();
+		let supported: Vec<PublicPair> =
+			self.call(SUPPORTED_KEYS, SupportedKeysParams { key_type: KeyType(id), keys })?;
+
+		Ok(supported.into_iter().map(PublicPair::into_pair).collect())
+	}
+
+	fn keys(&self, id: KeyTypeId) -> Result<Vec<CryptoTypePublicPair>, TraitError> {
+		let keys: Vec<PublicPair> = self.call(KEYS, KeysParams { key_type: KeyType(id) })?;
+
+		Ok(keys.into_iter().map(PublicPair::into_pair).collect())
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		let keys = public_keys
+			.iter()
+			.map(|(public, id)| TypedPublic {
+				key_type: KeyType(*id),
+				public: public.clone().into(),
+			})
+			.collect();
+
+		self.call(HAS_KEYS, HasKeysParams { keys })
+			.map_err(|e| log::warn!(target: "remote-keystore", "Checking keys failed: {}", e))
+			.unwrap_or(false)
+	}
+
+	fn sign_with(
+		&self,
+		id: KeyTypeId,
+		key: &CryptoTypePublicPair,
+		msg: &[u8],
+	) -> Result<Option<Vec<u8>>, TraitError> {
+		let key = PublicPair::from_pair(key).ok_or(TraitError::KeyNotSupported(id))?;
+		let signature: Option<sp_core::Bytes> = self.call(
+			SIGN_WITH,
+			SignWithParams { key_type: KeyType(id), key, message: msg.to_vec().into() },
+		)?;
+
+		Ok(signature.map(|signature| signature.0))
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_transcript_data: VRFTranscriptData,
+	) -> Result<Option<VRFSignature>, TraitError> {
+		// Aura and GRANDPA never ask for VRF signatures, and transcripts cannot be
+		// rebuilt on the other side of the socket.
+		Err(TraitError::Other("VRF signing is not supported by the remote keystore".into()))
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		id: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, TraitError> {
+		let signature: Option<sp_core::Bytes> = self.call(
+			ECDSA_SIGN_PREHASHED,
+			EcdsaSignPrehashedParams {
+				key_type: KeyType(id),
+				public: public.to_raw_vec().into(),
+				message: msg.to_vec().into(),
+			},
+		)?;
+
+		signature
+			.map(|signature| {
+				ecdsa::Signature::try_from(&signature[..])
+					.map_err(|_| TraitError::ValidationError("Invalid signature".into()))
+			})
+			.transpose()
+	}
+}
@@ -0,0 +1,18 @@
+//! Remote keystore for the Build3 node.
+//!
+//! Validators can keep their session keys out of the node process by running
+//! a signer next to it and starting the node with
+//! `--keystore-uri unix:///path/to/signer.sock`. The node then asks the signer
+//! for public keys and signatures over the protocol described in [`protocol`].
+//! `build3-signer` is a reference signer backed by a regular file keystore.
+
+#![warn(missing_docs)]
+
+mod client;
+#[allow(missing_docs)]
+pub mod protocol;
+pub mod server;
+#[cfg(test)]
+mod tests;
+
+pub use client::{Error, RemoteKeystore};
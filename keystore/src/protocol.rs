@@ -0,0 +1,205 @@
+//! Wire format spoken between the node and a remote signer.
+//!
+//! Every message is a single line of JSON terminated by `\n`. Requests and
+//! responses follow JSON-RPC 2.0; a connection may carry any number of them,
+//! one at a time. Keys, messages and signatures are `0x` prefixed hex strings,
+//! key types are their four character identifier (e.g. `aura`, `gran`).
+//!
+//! | method                        | params                                 | result                 |
+//! |-------------------------------|----------------------------------------|------------------------|
+//! | `keystore_publicKeys`         | `keyType`, `crypto`                    | `[public]`             |
+//! | `keystore_generateNew`        | `keyType`, `crypto`, `seed?`           | `public`               |
+//! | `keystore_insertUnknown`      | `keyType`, `suri`, `public`            | `null`                 |
+//! | `keystore_keys`               | `keyType`                              | `[{crypto, public}]`   |
+//! | `keystore_supportedKeys`      | `keyType`, `keys: [{crypto, public}]`  | `[{crypto, public}]`   |
+//! | `keystore_hasKeys`            | `keys: [{keyType, public}]`            | `bool`                 |
+//! | `keystore_signWith`           | `keyType`, `key: {crypto, public}`, `message` | `signature \| null` |
+//! | `keystore_ecdsaSignPrehashed` | `keyType`, `public`, `message`         | `signature \| null`    |
+//!
+//! `crypto` is one of `sr25519`, `ed25519` or `ecdsa`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sp_core::{
+	crypto::{CryptoTypeId, CryptoTypePublicPair, KeyTypeId},
+	ecdsa, ed25519, sr25519, Bytes,
+};
+use std::convert::TryFrom;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PUBLIC_KEYS: &str = "keystore_publicKeys";
+pub const GENERATE_NEW: &str = "keystore_generateNew";
+pub const INSERT_UNKNOWN: &str = "keystore_insertUnknown";
+pub const KEYS: &str = "keystore_keys";
+pub const SUPPORTED_KEYS: &str = "keystore_supportedKeys";
+pub const HAS_KEYS: &str = "keystore_hasKeys";
+pub const SIGN_WITH: &str = "keystore_signWith";
+pub const ECDSA_SIGN_PREHASHED: &str = "keystore_ecdsaSignPrehashed";
+
+/// JSON-RPC error code for a request the signer could not parse.
+pub const INVALID_REQUEST: i64 = -32600;
+/// JSON-RPC error code for an unknown method.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for malformed method parameters.
+pub const INVALID_PARAMS: i64 = -32602;
+/// Error code for a keystore operation which failed on the signer.
+pub const KEYSTORE_ERROR: i64 = 1;
+
+/// A request sent to the signer.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Request {
+	pub jsonrpc: String,
+	pub id: u64,
+	pub method: String,
+	#[serde(default)]
+	pub params: Value,
+}
+
+/// The signer's answer to a [`Request`] with the same `id`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+	pub jsonrpc: String,
+	pub id: Option<u64>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub result: Option<Value>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<RpcError>,
+}
+
+/// Error object of a failed [`Response`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RpcError {
+	pub code: i64,
+	pub message: String,
+}
+
+/// Signature schemes understood by the signer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Crypto {
+	Sr25519,
+	Ed25519,
+	Ecdsa,
+}
+
+impl Crypto {
+	pub fn id(self) -> CryptoTypeId {
+		match self {
+			Crypto::Sr25519 => sr25519::CRYPTO_ID,
+			Crypto::Ed25519 => ed25519::CRYPTO_ID,
+			Crypto::Ecdsa => ecdsa::CRYPTO_ID,
+		}
+	}
+
+	pub fn from_id(id: CryptoTypeId) -> Option<Self> {
+		[Crypto::Sr25519, Crypto::Ed25519, Crypto::Ecdsa]
+			.into_iter()
+			.find(|c| c.id() == id)
+	}
+}
+
+/// A public key tagged with its signature scheme.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicPair {
+	pub crypto: Crypto,
+	pub public: Bytes,
+}
+
+impl PublicPair {
+	/// Convert from the keystore representation, `None` for unknown schemes.
+	pub fn from_pair(pair: &CryptoTypePublicPair) -> Option<Self> {
+		Crypto::from_id(pair.0).map(|crypto| PublicPair { crypto, public: pair.1.clone().into() })
+	}
+
+	pub fn into_pair(self) -> CryptoTypePublicPair {
+		CryptoTypePublicPair(self.crypto.id(), self.public.0)
+	}
+}
+
+/// A key type, serialized as its four character identifier.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeyType(pub KeyTypeId);
+
+impl TryFrom<String> for KeyType {
+	type Error = String;
+
+	fn try_from(s: String) -> Result<Self, Self::Error> {
+		KeyTypeId::try_from(s.as_str())
+			.map(KeyType)
+			.map_err(|_| format!("Invalid key type `{}`", s))
+	}
+}
+
+impl From<KeyType> for String {
+	fn from(key_type: KeyType) -> String {
+		String::from_utf8_lossy(&key_type.0 .0).into_owned()
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeysParams {
+	pub key_type: KeyType,
+	pub crypto: Crypto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateNewParams {
+	pub key_type: KeyType,
+	pub crypto: Crypto,
+	#[serde(default)]
+	pub seed: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertUnknownParams {
+	pub key_type: KeyType,
+	pub suri: String,
+	pub public: Bytes,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeysParams {
+	pub key_type: KeyType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedKeysParams {
+	pub key_type: KeyType,
+	pub keys: Vec<PublicPair>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedPublic {
+	pub key_type: KeyType,
+	pub public: Bytes,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HasKeysParams {
+	pub keys: Vec<TypedPublic>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignWithParams {
+	pub key_type: KeyType,
+	pub key: PublicPair,
+	pub message: Bytes,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EcdsaSignPrehashedParams {
+	pub key_type: KeyType,
+	pub public: Bytes,
+	pub message: Bytes,
+}
@@ -0,0 +1,172 @@
+//! Reference signer serving a keystore over a Unix socket.
+
+use crate::protocol::*;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use sp_core::{ecdsa, ByteArray, Bytes};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use std::{
+	io::{self, BufRead, BufReader, Write},
+	os::unix::net::{UnixListener, UnixStream},
+	thread,
+};
+
+/// Accept connections forever, answering each on its own thread.
+pub fn serve(listener: UnixListener, keystore: SyncCryptoStorePtr) -> io::Result<()> {
+	for stream in listener.incoming() {
+		let stream = stream?;
+		let keystore = keystore.clone();
+
+		thread::spawn(move || {
+			if let Err(e) = handle_connection(stream, &*keystore) {
+				log::debug!(target: "build3-signer", "Connection closed: {}", e);
+			}
+		});
+	}
+
+	Ok(())
+}
+
+/// Answer the requests of one connection until it is closed.
+pub fn handle_connection(stream: UnixStream, keystore: &dyn SyncCryptoStore) -> io::Result<()> {
+	let mut writer = stream.try_clone()?;
+
+	for line in BufReader::new(stream).lines() {
+		let line = line?;
+		if line.trim().is_empty() {
+			continue
+		}
+
+		let response = match serde_json::from_str::<Request>(&line) {
+			Ok(request) => {
+				let (result, error) = match dispatch(keystore, &request.method, request.params) {
+					Ok(result) => (Some(result), None),
+					Err(error) => (None, Some(error)),
+				};
+				Response { jsonrpc: JSONRPC_VERSION.into(), id: Some(request.id), result, error }
+			},
+			Err(e) => Response {
+				jsonrpc: JSONRPC_VERSION.into(),
+				id: None,
+				result: None,
+				error: Some(RpcError { code: INVALID_REQUEST, message: e.to_string() }),
+			},
+		};
+
+		let mut answer = serde_json::to_string(&response).map_err(io::Error::from)?;
+		answer.push('\n');
+		writer.write_all(answer.as_bytes())?;
+	}
+
+	Ok(())
+}
+
+fn params<P: DeserializeOwned>(params: Value) -> Result<P, RpcError> {
+	serde_json::from_value(params)
+		.map_err(|e| RpcError { code: INVALID_PARAMS, message: e.to_string() })
+}
+
+fn result<R: Serialize>(result: R) -> Result<Value, RpcError> {
+	serde_json::to_value(result)
+		.map_err(|e| RpcError { code: KEYSTORE_ERROR, message: e.to_string() })
+}
+
+fn keystore_error(e: impl std::fmt::Display) -> RpcError {
+	RpcError { code: KEYSTORE_ERROR, message: e.to_string() }
+}
+
+/// Run a single request against the keystore.
+pub fn dispatch(
+	keystore: &dyn SyncCryptoStore,
+	method: &str,
+	raw: Value,
+) -> Result<Value, RpcError> {
+	match method {
+		PUBLIC_KEYS => {
+			let PublicKeysParams { key_type, crypto } = params(raw)?;
+			let keys: Vec<Bytes> = match crypto {
+				Crypto::Sr25519 => keystore
+					.sr25519_public_keys(key_type.0)
+					.iter()
+					.map(|k| k.to_raw_vec().into())
+					.collect(),
+				Crypto::Ed25519 => keystore
+					.ed25519_public_keys(key_type.0)
+					.iter()
+					.map(|k| k.to_raw_vec().into())
+					.collect(),
+				Crypto::Ecdsa => keystore
+					.ecdsa_public_keys(key_type.0)
+					.iter()
+					.map(|k| k.to_raw_vec().into())
+					.collect(),
+			};
+			result(keys)
+		},
+		GENERATE_NEW => {
+			let GenerateNewParams { key_type, crypto, seed } = params(raw)?;
+			let seed = seed.as_deref();
+			let public: Bytes = match crypto {
+				Crypto::Sr25519 => keystore
+					.sr25519_generate_new(key_type.0, seed)
+					.map_err(keystore_error)?
+					.to_raw_vec(),
+				Crypto::Ed25519 => keystore
+					.ed25519_generate_new(key_type.0, seed)
+					.map_err(keystore_error)?
+					.to_raw_vec(),
+				Crypto::Ecdsa => keystore
+					.ecdsa_generate_new(key_type.0, seed)
+					.map_err(keystore_error)?
+					.to_raw_vec(),
+			}
+			.into();
+			result(public)
+		},
+		INSERT_UNKNOWN => {
+			let InsertUnknownParams { key_type, suri, public } = params(raw)?;
+			keystore
+				.insert_unknown(key_type.0, &suri, &public)
+				.map_err(|()| keystore_error("Inserting key failed"))?;
+			result(())
+		},
+		KEYS => {
+			let KeysParams { key_type } = params(raw)?;
+			let keys = keystore.keys(key_type.0).map_err(keystore_error)?;
+			result(keys.iter().filter_map(PublicPair::from_pair).collect::<Vec<_>>())
+		},
+		SUPPORTED_KEYS => {
+			let SupportedKeysParams { key_type, keys } = params(raw)?;
+			let keys = keys.into_iter().map(PublicPair::into_pair).collect();
+			let supported = keystore.supported_keys(key_type.0, keys).map_err(keystore_error)?;
+			result(supported.iter().filter_map(PublicPair::from_pair).collect::<Vec<_>>())
+		},
+		HAS_KEYS => {
+			let HasKeysParams { keys } = params(raw)?;
+			let keys: Vec<_> = keys.into_iter().map(|k| (k.public.0, k.key_type.0)).collect();
+			result(keystore.has_keys(&keys))
+		},
+		SIGN_WITH => {
+			let SignWithParams { key_type, key, message } = params(raw)?;
+			let signature = keystore
+				.sign_with(key_type.0, &key.into_pair(), &message)
+				.map_err(keystore_error)?;
+			result(signature.map(Bytes))
+		},
+		ECDSA_SIGN_PREHASHED => {
+			let EcdsaSignPrehashedParams { key_type, public, message } = params(raw)?;
+			let public = ecdsa::Public::from_slice(&public)
+				.map_err(|()| keystore_error("Invalid ecdsa public key"))?;
+			let message = <[u8; 32]>::try_from(&message[..])
+				.map_err(|_| keystore_error("Prehashed message must be 32 bytes"))?;
+			let signature = keystore
+				.ecdsa_sign_prehashed(key_type.0, &public, &message)
+				.map_err(keystore_error)?;
+			result(signature.map(|s| Bytes(s.0.to_vec())))
+		},
+		method => Err(RpcError {
+			code: METHOD_NOT_FOUND,
+			message: format!("Unknown method `{}`", method),
+		}),
+	}
+}
@@ -0,0 +1,151 @@
+//! Tests of the remote keystore against the reference signer.
+
+use crate::{server, RemoteKeystore};
+use sc_keystore::LocalKeystore;
+use sp_core::{
+	crypto::{CryptoTypePublicPair, KeyTypeId},
+	sr25519, Pair,
+};
+use sp_keystore::SyncCryptoStore;
+use std::{
+	net::Shutdown,
+	os::unix::net::{UnixListener, UnixStream},
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	thread,
+};
+use tempfile::TempDir;
+
+const AURA: KeyTypeId = KeyTypeId(*b"aura");
+
+/// A `build3-signer` serving the file keystore in `keystore` on `socket`.
+///
+/// Unlike [`server::serve`] it can be stopped, closing every connection the
+/// way a signer process exiting would.
+struct Signer {
+	socket: PathBuf,
+	stopped: Arc<AtomicBool>,
+	connections: Arc<parking_lot::Mutex<Vec<UnixStream>>>,
+}
+
+impl Signer {
+	fn start(socket: &Path, keystore: &Path) -> Self {
+		let keystore = Arc::new(LocalKeystore::open(keystore, None).unwrap());
+		let listener = UnixListener::bind(socket).unwrap();
+		let signer = Signer {
+			socket: socket.into(),
+			stopped: Default::default(),
+			connections: Default::default(),
+		};
+
+		let (stopped, connections) = (signer.stopped.clone(), signer.connections.clone());
+		thread::spawn(move || {
+			for stream in listener.incoming() {
+				let stream = stream.unwrap();
+				if stopped.load(Ordering::SeqCst) {
+					break
+				}
+				connections.lock().push(stream.try_clone().unwrap());
+
+				let keystore = keystore.clone();
+				thread::spawn(move || server::handle_connection(stream, &*keystore));
+			}
+		});
+
+		signer
+	}
+
+	fn stop(self) {
+		self.stopped.store(true, Ordering::SeqCst);
+		// Wake the accepting thread up so that it drops the listener.
+		UnixStream::connect(&self.socket).unwrap();
+		std::fs::remove_file(&self.socket).unwrap();
+
+		for stream in self.connections.lock().drain(..) {
+			let _ = stream.shutdown(Shutdown::Both);
+		}
+	}
+}
+
+fn setup() -> (TempDir, PathBuf, PathBuf) {
+	let dir = tempfile::tempdir().unwrap();
+	let (socket, keystore) = (dir.path().join("signer.sock"), dir.path().join("keystore"));
+	std::fs::create_dir(&keystore).unwrap();
+
+	(dir, socket, keystore)
+}
+
+fn url(socket: &Path) -> String {
+	format!("unix://{}", socket.display())
+}
+
+fn sign(keystore: &RemoteKeystore, public: &sr25519::Public, message: &[u8]) -> Option<Vec<u8>> {
+	let key = CryptoTypePublicPair(sr25519::CRYPTO_ID, public.0.to_vec());
+	SyncCryptoStore::sign_with(keystore, AURA, &key, message).unwrap()
+}
+
+fn verifies(signature: &[u8], message: &[u8], public: &sr25519::Public) -> bool {
+	let signature = sr25519::Signature::try_from(signature).unwrap();
+	sr25519::Pair::verify(&signature, message, public)
+}
+
+#[test]
+fn keys_are_generated_and_used_on_the_signer() {
+	let (_dir, socket, path) = setup();
+	let _signer = Signer::start(&socket, &path);
+	let keystore = RemoteKeystore::open(&url(&socket)).unwrap();
+
+	let public = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, None).unwrap();
+	assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, AURA), vec![public]);
+	assert!(SyncCryptoStore::has_keys(&keystore, &[(public.0.to_vec(), AURA)]));
+	assert!(SyncCryptoStore::ed25519_public_keys(&keystore, AURA).is_empty());
+
+	let signature = sign(&keystore, &public, b"block").unwrap();
+	assert!(verifies(&signature, b"block", &public));
+
+	// Unknown keys are not an error, the signer has nothing to sign with.
+	assert_eq!(sign(&keystore, &sr25519::Public([7; 32]), b"block"), None);
+}
+
+#[test]
+fn seeded_keys_match_the_seed() {
+	let (_dir, socket, path) = setup();
+	let _signer = Signer::start(&socket, &path);
+	let keystore = RemoteKeystore::open(&url(&socket)).unwrap();
+
+	let public = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some("//Alice")).unwrap();
+	assert_eq!(public, sr25519::Pair::from_string("//Alice", None).unwrap().public());
+}
+
+#[test]
+fn requests_survive_a_signer_restart() {
+	let (_dir, socket, path) = setup();
+	let signer = Signer::start(&socket, &path);
+	let keystore = RemoteKeystore::open(&url(&socket)).unwrap();
+	let public = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, None).unwrap();
+
+	signer.stop();
+	let _signer = Signer::start(&socket, &path);
+
+	// The first request after the restart finds the old connection closed and
+	// goes out again on a new one.
+	let signature = sign(&keystore, &public, b"block").unwrap();
+	assert!(verifies(&signature, b"block", &public));
+	assert_eq!(SyncCryptoStore::sr25519_public_keys(&keystore, AURA), vec![public]);
+}
+
+#[test]
+fn unreachable_signers_fail_the_request() {
+	let (_dir, socket, path) = setup();
+	let signer = Signer::start(&socket, &path);
+	let keystore = RemoteKeystore::open(&url(&socket)).unwrap();
+	assert!(SyncCryptoStore::keys(&keystore, AURA).unwrap().is_empty());
+
+	signer.stop();
+	assert!(SyncCryptoStore::keys(&keystore, AURA).is_err());
+	assert!(RemoteKeystore::open(&url(&socket)).is_err());
+	assert!(RemoteKeystore::open("/tmp/signer.sock").is_err());
+}
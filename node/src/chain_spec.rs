@@ -1,7 +1,10 @@
 use build3_node_runtime::{
-	AccountId, AuraConfig, BalancesConfig, BoardVirginiaConfig, CouncilConfig, GenesisConfig,
-	GrandpaConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
+	AccountId, ApprovalRule, AuraConfig, Balance, BalancesConfig, BlockNumber, Board,
+	BoardVirginiaConfig, ContractsRegistryConfig, Discipline, ElectionsConfig, GenesisConfig,
+	GrandpaConfig, LicensureAction, LicensureConfig, RosterSyncConfig, Signature, SudoConfig,
+	SystemConfig, VestingConfig, DAYS, DOLLARS, WASM_BINARY,
 };
+use sc_chain_spec::ChainSpecExtension;
 use sc_service::ChainType;
 use sc_telemetry::TelemetryEndpoints;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -12,12 +15,103 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 use sc_network::config::MultiaddrWithPeerId;
 
 use hex_literal::hex;
+use serde::{Deserialize, Serialize};
 
 // The URL for the telemetry server.
 const STAGING_TELEMETRY_URL: &str = "ws://127.0.0.1:9944/";
 
+// Term of the licenses issued at genesis, two years.
+const GENESIS_LICENSE_TERM: BlockNumber = 730 * DAYS;
+
+// Where `build3-roster-feed` serves the Virginia roster in development.
+const ROSTER_FEED_URL: &[u8] = b"http://127.0.0.1:8090/virginia.csv";
+
+// Initial balance of every endowed account.
+const ENDOWMENT: Balance = 1 << 60;
+
+// Self-vote backing each council member elected at genesis, until the first
+// election.
+const GENESIS_COUNCIL_STAKE: Balance = 100 * DOLLARS;
+
+// Balance of the accounts of the authorities' Aura keys, which bond their
+// randomness beacon commitments.
+const BEACON_FUNDS: Balance = 10_000 * DOLLARS;
+
+// Launch balances stay locked for six months, then unlock block by block over
+// two years. A tenth of the endowment is liquid from genesis for fees.
+const VESTING_CLIFF: BlockNumber = 180 * DAYS;
+const VESTING_PERIOD: BlockNumber = 730 * DAYS;
+const VESTING_LIQUID: Balance = ENDOWMENT / 10;
+
+/// Node-side settings carried by the chain spec next to the genesis state.
+#[derive(Default, Clone, Serialize, Deserialize, ChainSpecExtension)]
+#[serde(rename_all = "camelCase")]
+pub struct Extensions {
+	/// GRANDPA voter settings shared by every node of the network.
+	#[serde(default)]
+	pub grandpa: GrandpaParameters,
+}
+
+impl Extensions {
+	/// Try to get the extension from the given `ChainSpec`.
+	pub fn try_get(chain_spec: &dyn sc_service::ChainSpec) -> Option<&Self> {
+		sc_chain_spec::get_extension(chain_spec.extensions())
+	}
+}
+
+/// GRANDPA timing, justification and voting rule settings.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GrandpaParameters {
+	/// Interval of gossip message propagation, in milliseconds.
+	pub gossip_duration_millis: u64,
+	/// Import a justification for every `justification_period` blocks.
+	pub justification_period: u32,
+	/// Run as a passive observer instead of a voter.
+	pub observer_enabled: bool,
+	/// Never vote on a block more than this many blocks behind the best block.
+	/// `None` removes the rule.
+	pub before_best_block_by: Option<u32>,
+	/// Only vote on the first three quarters of the unfinalized chain.
+	pub three_quarters_of_unfinalized_chain: bool,
+}
+
+impl Default for GrandpaParameters {
+	fn default() -> Self {
+		GrandpaParameters {
+			gossip_duration_millis: 333,
+			justification_period: 512,
+			observer_enabled: false,
+			before_best_block_by: Some(2),
+			three_quarters_of_unfinalized_chain: true,
+		}
+	}
+}
+
+impl GrandpaParameters {
+	/// Gossip durations accepted from a chain spec, in milliseconds.
+	const GOSSIP_DURATION_MILLIS: core::ops::RangeInclusive<u64> = 10..=10_000;
+
+	/// Reject settings the GRANDPA voter cannot run with.
+	pub fn validate(&self) -> Result<(), String> {
+		if self.justification_period == 0 {
+			return Err("Invalid chain spec: `grandpa.justificationPeriod` must be at least 1".into())
+		}
+		if !Self::GOSSIP_DURATION_MILLIS.contains(&self.gossip_duration_millis) {
+			return Err(format!(
+				"Invalid chain spec: `grandpa.gossipDurationMillis` must be between {} and {}, got {}",
+				Self::GOSSIP_DURATION_MILLIS.start(),
+				Self::GOSSIP_DURATION_MILLIS.end(),
+				self.gossip_duration_millis,
+			))
+		}
+
+		Ok(())
+	}
+}
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
+pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig, Extensions>;
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
@@ -110,6 +204,15 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					// Controller 3: 5ERJ2uNxW7qzWb8jBTkSXmo6rw3VwXSJuuqoZAR8Q3LUL1ZR
 					hex!["682c6c59747c165003e9ca3e44bda8475cb8827c313ccb8bdc1dae8cd6824953"].into(),
 				],
+				// VA Licensed PEs
+				vec![
+					// VA PE 1: 5Est8NkVq5aqsHPieNq89e24nCf6gUUKPhi7G66ytQRn5aF5
+					hex!["7c739356df4ae8218719d0e6e6927fda4feb4dd8fcdc9271afbe805b8f276555"].into(),
+					// VA PE 2: 5CqTHp7ykJLHmBFxNRFjwn3XqmvmizbqybvFnQhoeAXd3jp5
+					hex!["2221021107fbe5de0445925098dfb6e825542125408ceaf7e64ba14e19d21450"].into(),
+					// VA PE 3: 5G4ACtpvTzDB6P9tdCyN48AhH2rzg9Vc4jiWUnA6ZMqqdnmh
+					hex!["b08662d74abb9af331b6c805aab339a3bf9388b39079f8d553e4adcb3846ba07"].into(),
+				],
 				true,
 			)
 		},
@@ -127,7 +230,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Properties
 		None,
 		// Extensions
-		None,
+		Default::default(),
 	))
 }
 
@@ -207,6 +310,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				hex!["21a51b57515eefd5c0b12bf02038f7e9faed69874f578c518ec45b23e58b34f4"].into(),
 				// Initial PoA authorities
 				initial_authorities.clone(),
+				// Vesting of the stash and controller launch balances
+				launch_vesting(initial_authorities.iter().flat_map(|x| [x.0.clone(), x.1.clone()])),
 				// Pre-funded accounts
 				vec![
 					// Stash 1: 5Gmdzhhd6KavPysdrnMbywimYcUKtRLdEDQtbmJbdi7ZC5sU
@@ -257,7 +362,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Properties
 		None,
 		// Extensions
-		None,
+		Default::default(),
 	))
 }
 
@@ -269,6 +374,7 @@ fn staging_genesis(
 	wasm_binary: &[u8],
 	root_key: AccountId,
 	initial_authorities: Vec<(AccountId, AccountId, AccountId, AccountId, AuraId, GrandpaId)>,
+	vesting: Vec<(AccountId, BlockNumber, BlockNumber, Balance)>,
 	endowed_accounts: Vec<AccountId>,
 	council_accounts: Vec<AccountId>,
 	board_va_accounts: Vec<AccountId>,
@@ -281,7 +387,15 @@ fn staging_genesis(
 		},
 		balances: BalancesConfig {
 			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			balances: endowed_accounts
+				.iter()
+				.cloned()
+				.map(|k| (k, ENDOWMENT))
+				.chain(initial_authorities.iter().map(|x| {
+					let aura = AccountPublic::from(sr25519::Public::from(x.4.clone()));
+					(aura.into_account(), BEACON_FUNDS)
+				}))
+				.collect(),
 		},
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.4.clone())).collect(),
@@ -289,11 +403,12 @@ fn staging_genesis(
 		grandpa: GrandpaConfig {
 			authorities: initial_authorities.iter().map(|x| (x.5.clone(), 1)).collect(),
 		},
-		council: CouncilConfig {
-			phantom: Default::default(),
-			members: council_accounts.iter().cloned().collect(),
-		},
+		// The council is seated by the elections pallet.
+		council: Default::default(),
 		council_member_manager: Default::default(),
+		elections: ElectionsConfig {
+			members: council_accounts.iter().cloned().map(|k| (k, GENESIS_COUNCIL_STAKE)).collect(),
+		},
 		board_virginia: BoardVirginiaConfig {
 			phantom: Default::default(),
 			members: board_va_accounts.iter().cloned().collect(),
@@ -301,9 +416,22 @@ fn staging_genesis(
 		board_virginia_member_manager: Default::default(),
 		sudo: SudoConfig {
 			// Assign network admin rights.
-			key: Some(root_key),
+			key: Some(root_key.clone()),
 		},
 		transaction_payment: Default::default(),
+		licensure: LicensureConfig {
+			// License the VA PE of every authority.
+			licenses: va_licenses(initial_authorities.iter().map(|x| x.2.clone())),
+			action_rules: board_action_rules(),
+		},
+		vesting: VestingConfig { vesting },
+		indices: Default::default(),
+		contracts_registry: ContractsRegistryConfig {
+			// Only the network admin uploads contract code until the council
+			// allows others to.
+			uploaders: vec![root_key],
+		},
+		roster_sync: Default::default(),
 	}
 }
 
@@ -315,6 +443,7 @@ fn dev_genesis(
 	endowed_accounts: Vec<AccountId>,
 	council_accounts: Vec<AccountId>,
 	board_va_accounts: Vec<AccountId>,
+	va_licensee_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -324,7 +453,7 @@ fn dev_genesis(
 		},
 		balances: BalancesConfig {
 			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 60)).collect(),
+			balances: endowed_accounts.iter().cloned().map(|k| (k, ENDOWMENT)).collect(),
 		},
 		aura: AuraConfig {
 			authorities: initial_authorities.iter().map(|x| (x.0.clone())).collect(),
@@ -332,11 +461,12 @@ fn dev_genesis(
 		grandpa: GrandpaConfig {
 			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
 		},
-		council: CouncilConfig {
-			phantom: Default::default(),
-			members: council_accounts.iter().cloned().collect(),
-		},
+		// The council is seated by the elections pallet.
+		council: Default::default(),
 		council_member_manager: Default::default(),
+		elections: ElectionsConfig {
+			members: council_accounts.iter().cloned().map(|k| (k, GENESIS_COUNCIL_STAKE)).collect(),
+		},
 		board_virginia: BoardVirginiaConfig {
 			phantom: Default::default(),
 			members: board_va_accounts.iter().cloned().collect(),
@@ -344,8 +474,60 @@ fn dev_genesis(
 		board_virginia_member_manager: Default::default(),
 		sudo: SudoConfig {
 			// Assign network admin rights.
-			key: Some(root_key),
+			key: Some(root_key.clone()),
 		},
 		transaction_payment: Default::default(),
+		licensure: LicensureConfig {
+			licenses: va_licenses(va_licensee_accounts),
+			action_rules: board_action_rules(),
+		},
+		vesting: Default::default(),
+		indices: Default::default(),
+		contracts_registry: ContractsRegistryConfig { uploaders: endowed_accounts },
+		roster_sync: RosterSyncConfig {
+			// The Virginia roster served by `build3-roster-feed`, reported by the
+			// network admin.
+			feeds: vec![(
+				Board::Virginia,
+				ROSTER_FEED_URL.to_vec(),
+				get_from_seed::<sr25519::Public>("VirginiaRoster").0,
+				root_key,
+			)],
+		},
 	}
 }
+
+/// Vesting of launch balances: `(who, cliff, period, liquid)` for each account.
+/// After the cliff the locked part of the endowment unlocks linearly, the same
+/// amount every block, until the end of the period.
+fn launch_vesting(
+	accounts: impl IntoIterator<Item = AccountId>,
+) -> Vec<(AccountId, BlockNumber, BlockNumber, Balance)> {
+	accounts
+		.into_iter()
+		.map(|who| (who, VESTING_CLIFF, VESTING_PERIOD, VESTING_LIQUID))
+		.collect()
+}
+
+/// Approval rules of the boards at launch: revoking a license takes two thirds
+/// of the board, every other action a majority.
+fn board_action_rules() -> Vec<(Board, LicensureAction, ApprovalRule)> {
+	Board::all()
+		.into_iter()
+		.map(|board| (board, LicensureAction::RevokeLicense, ApprovalRule::TwoThirds))
+		.collect()
+}
+
+/// Civil PE licenses from the Virginia board, numbered in the order of `holders`.
+fn va_licenses(
+	holders: impl IntoIterator<Item = AccountId>,
+) -> Vec<(Vec<u8>, AccountId, Board, Discipline, BlockNumber)> {
+	holders
+		.into_iter()
+		.enumerate()
+		.map(|(i, holder)| {
+			let number = format!("0402{:06}", i + 1).into_bytes();
+			(number, holder, Board::Virginia, Discipline::Civil, GENESIS_LICENSE_TERM)
+		})
+		.collect()
+}
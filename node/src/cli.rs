@@ -1,4 +1,40 @@
 use sc_cli::RunCmd;
+use std::str::FromStr;
+
+/// Block sealing strategy used in place of Aura and GRANDPA for development
+/// and integration testing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only when requested through the `engine_*` RPC methods.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Sealing::Instant),
+			"manual" => Ok(Sealing::Manual),
+			s => match s.strip_prefix("interval:") {
+				Some(millis) => millis
+					.parse::<u64>()
+					.ok()
+					.filter(|millis| *millis > 0)
+					.map(Sealing::Interval)
+					.ok_or_else(|| format!("Invalid sealing interval `{}`", millis)),
+				None => Err(format!(
+					"Unknown sealing mode `{}`, expected `instant`, `manual` or `interval:<ms>`",
+					s
+				)),
+			},
+		}
+	}
+}
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -7,6 +43,18 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Replace Aura and GRANDPA with manual sealing: `instant`, `manual` or
+	/// `interval:<ms>`. Blocks can then be created and finalized on demand
+	/// through the `engine_createBlock` and `engine_finalizeBlock` RPC methods.
+	#[clap(long)]
+	pub sealing: Option<Sealing>,
+
+	/// Index the events, code uploads and instantiations of contracts in
+	/// finalized blocks, and serve them through `contracts_getEvents` and
+	/// `contracts_getCodeUpload`.
+	#[clap(long)]
+	pub contracts_index: bool,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -27,6 +75,9 @@ pub enum Subcommand {
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
+	/// Export licenses, firms and disciplinary actions of a given block.
+	ExportLicenses(crate::export::ExportLicensesCmd),
+
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
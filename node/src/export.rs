@@ -0,0 +1,373 @@
+//! `export-licenses` subcommand, writing an offline snapshot of the licensure
+//! registry which can be checked against the chain without trusting the node.
+
+use build3_node_runtime::{opaque::Block, Runtime};
+use codec::Decode;
+use frame_support::{
+	storage::StoragePrefixedMap, Blake2_128Concat, ReversibleStorageHasher, Twox64Concat,
+};
+use pallet_licensure::{
+	DisciplinaryActionOf, DisciplinaryActions, FirmId, FirmOf, Firms, LicenseNumberOf, LicenseOf,
+	Licenses,
+};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::{Backend, ProofProvider, StorageProvider};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	bytes::to_hex,
+	storage::{StorageData, StorageKey},
+};
+use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+use std::{
+	fmt::Debug,
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::PathBuf,
+	str::FromStr,
+	sync::Arc,
+};
+
+/// Output format of the export.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExportFormat {
+	/// One header line, then a comma separated line per record.
+	Csv,
+	/// A JSON object per line.
+	JsonLines,
+}
+
+impl FromStr for ExportFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"csv" => Ok(ExportFormat::Csv),
+			"jsonl" => Ok(ExportFormat::JsonLines),
+			s => Err(format!("Unknown export format `{}`, expected `csv` or `jsonl`", s)),
+		}
+	}
+}
+
+/// The `export-licenses` command used to export licenses, firms and
+/// disciplinary actions.
+#[derive(Debug, clap::Parser)]
+pub struct ExportLicensesCmd {
+	/// Block hash or number to export the registry at. Defaults to the best
+	/// finalized block.
+	#[clap(value_name = "HASH or NUMBER")]
+	pub input: Option<BlockNumberOrHash>,
+
+	/// Output file. Defaults to stdout.
+	#[clap(long, short, parse(from_os_str))]
+	pub output: Option<PathBuf>,
+
+	/// Output format: `csv` or `jsonl`.
+	#[clap(long, default_value = "jsonl")]
+	pub format: ExportFormat,
+
+	/// Attach to every record a storage proof of its entry against the state
+	/// root, instead of only the state root and block hash.
+	#[clap(long)]
+	pub with_proof: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// One exported registry entry. Fields which do not apply to a kind of record
+/// are left empty.
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+	/// `license`, `firm` or `disciplinaryAction`.
+	record: &'static str,
+	/// License number or firm id.
+	id: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	account: Option<String>,
+	board: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	discipline: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	status: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	issued_at: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	expires_at: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	name: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	engineer_in_charge: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	kind: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	order_hash: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	recorded_at: Option<u32>,
+	/// Storage key the record was read from.
+	storage_key: String,
+	block_hash: String,
+	state_root: String,
+	/// Trie nodes proving the storage entry, when requested.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	proof: Option<Vec<String>>,
+}
+
+const CSV_HEADER: &str = "record,id,account,board,discipline,status,issued_at,expires_at,name,\
+	engineer_in_charge,kind,order_hash,recorded_at,storage_key,block_hash,state_root,proof";
+
+impl Record {
+	fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+		let number = |n: Option<u32>| n.map(|n| n.to_string()).unwrap_or_default();
+		let fields = [
+			self.record.to_string(),
+			self.id.clone(),
+			self.account.clone().unwrap_or_default(),
+			self.board.clone(),
+			self.discipline.clone().unwrap_or_default(),
+			self.status.clone().unwrap_or_default(),
+			number(self.issued_at),
+			number(self.expires_at),
+			self.name.clone().unwrap_or_default(),
+			self.engineer_in_charge.clone().unwrap_or_default(),
+			self.kind.clone().unwrap_or_default(),
+			self.order_hash.clone().unwrap_or_default(),
+			number(self.recorded_at),
+			self.storage_key.clone(),
+			self.block_hash.clone(),
+			self.state_root.clone(),
+			self.proof.as_ref().map(|nodes| nodes.join(";")).unwrap_or_default(),
+		];
+		let line = fields.iter().map(String::as_str).map(csv_field).collect::<Vec<_>>().join(",");
+		writeln!(out, "{}", line)
+	}
+}
+
+/// Quotes `field` if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+	if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_string()
+	}
+}
+
+fn debug(value: impl Debug) -> String {
+	format!("{:?}", value)
+}
+
+/// Decodes the key of an entry of the map stored under `prefix`, whose keys are
+/// hashed with `H`.
+fn decode_key<H: ReversibleStorageHasher, K: Decode>(
+	key: &StorageKey,
+	prefix: &[u8],
+) -> sc_cli::Result<K> {
+	let hashed = key.0.strip_prefix(prefix).unwrap_or_default();
+	K::decode(&mut H::reverse(hashed)).map_err(|e| invalid_entry(key, e))
+}
+
+fn decode_value<V: Decode>(key: &StorageKey, value: &StorageData) -> sc_cli::Result<V> {
+	V::decode(&mut &value.0[..]).map_err(|e| invalid_entry(key, e))
+}
+
+fn invalid_entry(key: &StorageKey, e: codec::Error) -> sc_cli::Error {
+	sc_cli::Error::Input(format!("Undecodable licensure entry at {}: {}", to_hex(&key.0, false), e))
+}
+
+impl ExportLicensesCmd {
+	/// Run the export-licenses command
+	pub async fn run<BA, C>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		BA: Backend<Block>,
+		C: StorageProvider<Block, BA> + ProofProvider<Block> + HeaderBackend<Block>,
+	{
+		let block_id = match &self.input {
+			Some(input) => input.parse::<Block>()?,
+			None => BlockId::Hash(client.info().finalized_hash),
+		};
+		let header = client
+			.header(block_id)?
+			.ok_or_else(|| sc_cli::Error::Input(format!("Unknown block {}", block_id)))?;
+		let block_hash = header.hash();
+		let block_number = *header.number();
+		let at = BlockId::Hash(block_hash);
+
+		log::info!("Exporting licensure registry at #{} ({})", block_number, block_hash);
+
+		// Fields shared by every record: the block and state root they are proven
+		// against, and the proof of the entry itself when requested.
+		let base = |key: &StorageKey| -> sc_cli::Result<Record> {
+			let proof = if self.with_proof {
+				let proof = client.read_proof(&at, &mut std::iter::once(&key.0[..]))?;
+				Some(proof.into_iter_nodes().map(|node| to_hex(&node, false)).collect())
+			} else {
+				None
+			};
+			Ok(Record {
+				storage_key: to_hex(&key.0, false),
+				block_hash: debug(block_hash),
+				state_root: debug(header.state_root()),
+				proof,
+				..Default::default()
+			})
+		};
+
+		let mut records = Vec::new();
+
+		let prefix = Licenses::<Runtime>::final_prefix();
+		for (key, value) in client.storage_pairs(&at, &StorageKey(prefix.to_vec()))? {
+			let number: LicenseNumberOf<Runtime> =
+				decode_key::<Blake2_128Concat, _>(&key, &prefix)?;
+			let license: LicenseOf<Runtime> = decode_value(&key, &value)?;
+			records.push(Record {
+				record: "license",
+				id: String::from_utf8_lossy(&number).into_owned(),
+				account: Some(license.holder.to_string()),
+				board: debug(license.board),
+				discipline: Some(debug(license.discipline)),
+				status: Some(debug(license.status_at(&block_number))),
+				issued_at: Some(license.issued_at),
+				expires_at: Some(license.expires_at),
+				..base(&key)?
+			});
+		}
+
+		let prefix = Firms::<Runtime>::final_prefix();
+		for (key, value) in client.storage_pairs(&at, &StorageKey(prefix.to_vec()))? {
+			let id: FirmId = decode_key::<Twox64Concat, _>(&key, &prefix)?;
+			let firm: FirmOf<Runtime> = decode_value(&key, &value)?;
+			records.push(Record {
+				record: "firm",
+				id: id.to_string(),
+				account: Some(firm.admin.to_string()),
+				board: debug(firm.board),
+				status: Some(debug(firm.status_at(&block_number))),
+				issued_at: Some(firm.issued_at),
+				expires_at: Some(firm.expires_at),
+				name: Some(String::from_utf8_lossy(&firm.name).into_owned()),
+				engineer_in_charge: Some(firm.engineer_in_charge.to_string()),
+				..base(&key)?
+			});
+		}
+
+		let prefix = DisciplinaryActions::<Runtime>::final_prefix();
+		for (key, value) in client.storage_pairs(&at, &StorageKey(prefix.to_vec()))? {
+			let number: LicenseNumberOf<Runtime> =
+				decode_key::<Blake2_128Concat, _>(&key, &prefix)?;
+			let actions: Vec<DisciplinaryActionOf<Runtime>> = decode_value(&key, &value)?;
+			// All actions against a license live in one entry, sharing its proof.
+			let entry = base(&key)?;
+			for action in actions {
+				records.push(Record {
+					record: "disciplinaryAction",
+					id: String::from_utf8_lossy(&number).into_owned(),
+					board: debug(action.board),
+					kind: Some(debug(action.kind)),
+					order_hash: Some(debug(action.order_hash)),
+					recorded_at: Some(action.recorded_at),
+					storage_key: entry.storage_key.clone(),
+					block_hash: entry.block_hash.clone(),
+					state_root: entry.state_root.clone(),
+					proof: entry.proof.clone(),
+					..Default::default()
+				});
+			}
+		}
+
+		let mut out: BufWriter<Box<dyn Write>> = BufWriter::new(match &self.output {
+			Some(path) => Box::new(File::create(path)?),
+			None => Box::new(io::stdout()),
+		});
+		match self.format {
+			ExportFormat::Csv => {
+				writeln!(out, "{}", CSV_HEADER)?;
+				for record in &records {
+					record.write_csv(&mut out)?;
+				}
+			},
+			ExportFormat::JsonLines =>
+				for record in &records {
+					serde_json::to_writer(&mut out, record).map_err(io::Error::from)?;
+					writeln!(out)?;
+				},
+		}
+		out.flush()?;
+
+		log::info!("Exported {} licensure records", records.len());
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportLicensesCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn plain_fields_are_left_alone() {
+		assert_eq!(csv_field(""), "");
+		assert_eq!(csv_field("PE-1234"), "PE-1234");
+		assert_eq!(csv_field("Smith & Sons; Ltd."), "Smith & Sons; Ltd.");
+	}
+
+	#[test]
+	fn fields_with_separators_are_quoted() {
+		assert_eq!(csv_field("Smith, Jones"), "\"Smith, Jones\"");
+		assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+		assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+	}
+
+	#[test]
+	fn quotes_are_doubled() {
+		assert_eq!(csv_field("\"Acme\" Engineering"), "\"\"\"Acme\"\" Engineering\"");
+		assert_eq!(csv_field("\""), "\"\"\"\"");
+	}
+
+	#[test]
+	fn records_are_written_in_header_order() {
+		let record = Record {
+			record: "firm",
+			id: "7".into(),
+			board: "1".into(),
+			status: Some("Active".into()),
+			issued_at: Some(10),
+			name: Some("Smith, Jones \"& Co\"".into()),
+			storage_key: "0x01".into(),
+			block_hash: "0x02".into(),
+			state_root: "0x03".into(),
+			proof: Some(vec!["0x04".into(), "0x05".into()]),
+			..Default::default()
+		};
+		let mut out = Vec::new();
+		record.write_csv(&mut out).unwrap();
+
+		assert_eq!(
+			String::from_utf8(out).unwrap(),
+			"firm,7,,1,,Active,10,,\"Smith, Jones \"\"& Co\"\"\",,,,,0x01,0x02,0x03,0x04;0x05\n"
+		);
+		assert_eq!(CSV_HEADER.split(',').count(), 17);
+	}
+}
@@ -7,13 +7,34 @@
 
 use std::sync::Arc;
 
-use build3_node_runtime::{opaque::Block, AccountId, Balance, BlockNumber, Hash, Index};
+use build3_contracts_index::{
+	rpc::{ContractsIndexApi, ContractsIndexRpc},
+	ContractsIndex,
+};
+use build3_governance_rpc::{Governance, GovernanceApi};
+use build3_node_runtime::{
+	opaque::Block, AccountId, Balance, BlockNumber, Board, Call, Collective, Hash, Index,
+};
+use build3_reserves_rpc::{Reserves, ReservesApi};
+use futures::channel::mpsc;
+use jsonrpc_pubsub::manager::SubscriptionManager;
 use pallet_contracts_rpc::{Contracts, ContractsApi};
+use pallet_did_rpc::{Did, DidApi};
+use pallet_licensure_rpc::{Licensure, LicensureApi};
+use pallet_plan_review_rpc::{PlanReview, PlanReviewApi};
+use pallet_projects_rpc::{Projects, ProjectsApi};
+use sc_client_api::BlockchainEvents;
+use sc_consensus_manual_seal::{
+	rpc::{ManualSeal, ManualSealApi},
+	EngineCommand,
+};
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_keystore::SyncCryptoStorePtr;
 
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
@@ -23,6 +44,14 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Manual sealing command sink, set when the node runs with `--sealing`.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	/// Executor for RPC subscriptions.
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Contract events index, set when the node runs with `--contracts-index`.
+	pub contracts_index: Option<Arc<ContractsIndex>>,
+	/// Keystore holding the credential keys of boards this node issues for.
+	pub keystore: SyncCryptoStorePtr,
 }
 
 /// Instantiate all full RPC extensions.
@@ -30,10 +59,22 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> jsonrpc_core::IoHandler<sc_rpc
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
-	C: Send + Sync + 'static,
+	C: BlockchainEvents<Block> + Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_licensure_rpc::LicensureRuntimeApi<Block, AccountId, Board, BlockNumber, Hash>,
+	C::Api: pallet_did_rpc::DidRuntimeApi<Block, AccountId, BlockNumber>,
+	C::Api: pallet_projects_rpc::ProjectsRuntimeApi<Block, AccountId, Board, BlockNumber, Hash>,
+	C::Api: pallet_plan_review_rpc::PlanReviewRuntimeApi<Block, Hash>,
+	C::Api: build3_reserves_rpc::ReservesRuntimeApi<Block, AccountId, Balance>,
+	C::Api: build3_governance_rpc::GovernanceRuntimeApi<
+		Block,
+		AccountId,
+		BlockNumber,
+		Hash,
+		Collective,
+	>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
@@ -41,7 +82,15 @@ where
 	use substrate_frame_rpc_system::{FullSystem, SystemApi};
 
 	let mut io = jsonrpc_core::IoHandler::default();
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps {
+		client,
+		pool,
+		deny_unsafe,
+		command_sink,
+		subscription_executor,
+		contracts_index,
+		keystore,
+	} = deps;
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(client.clone(), pool, deny_unsafe)));
 
@@ -55,5 +104,47 @@ where
 	// Contracts RPC API extension
 	io.extend_with(ContractsApi::to_delegate(Contracts::new(client.clone())));
 
+	// Contract events index, providing `contracts_getEvents` and
+	// `contracts_getCodeUpload`.
+	if let Some(contracts_index) = contracts_index {
+		io.extend_with(ContractsIndexApi::to_delegate(ContractsIndexRpc::new(contracts_index)));
+	}
+
+	let subscriptions = SubscriptionManager::new(Arc::new(subscription_executor));
+
+	// Licensure RPC API extension, issuing credentials with the boards' keys
+	// held by this node.
+	io.extend_with(LicensureApi::to_delegate(Licensure::new(
+		client.clone(),
+		subscriptions.clone(),
+		keystore,
+		deny_unsafe,
+	)));
+
+	// DID resolution for `did:build3`, listing the licenses of each DID subject.
+	io.extend_with(DidApi::to_delegate(Did::<_, _, AccountId, Board, Hash>::new(client.clone())));
+
+	// Project registry lookups for permitting offices.
+	io.extend_with(ProjectsApi::to_delegate(Projects::new(client.clone())));
+
+	// Plan review status of projects, ahead of issuing their permits.
+	io.extend_with(PlanReviewApi::to_delegate(PlanReview::new(client.clone())));
+
+	// Reserved balances broken down by reason.
+	io.extend_with(ReservesApi::to_delegate(Reserves::<_, _, Balance>::new(client.clone())));
+
+	// Governance RPC API extension, following the motions of the council and
+	// the boards.
+	io.extend_with(GovernanceApi::to_delegate(Governance::<_, _, Call>::new(
+		client.clone(),
+		subscriptions,
+	)));
+
+	// Manual sealing API extension, providing `engine_createBlock` and
+	// `engine_finalizeBlock` when the node runs without Aura and GRANDPA.
+	if let Some(command_sink) = command_sink {
+		io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(command_sink)));
+	}
+
 	io
 }
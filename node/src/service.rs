@@ -1,15 +1,30 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use build3_node_runtime::{self, opaque::Block, RuntimeApi};
+use crate::{chain_spec::Extensions, cli::Sealing};
+use build3_contracts_index::ContractsIndex;
+use build3_node_runtime::{self, opaque::Block, RuntimeApi, DEV_SPEC_NAME, SLOT_DURATION};
+use build3_remote_keystore::RemoteKeystore;
+use futures::{channel::mpsc, prelude::*};
 use sc_client_api::{BlockBackend, ExecutorProvider};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_manual_seal::{
+	consensus::aura::AuraConsensusDataProvider, EngineCommand, ManualSealParams,
+};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
-use sc_keystore::LocalKeystore;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sc_service::{error::Error as ServiceError, ChainType, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use sc_transaction_pool_api::TransactionPool;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::sr25519::{AuthorityPair as AuraPair, AuthoritySignature as AuraSignature};
+use sp_runtime::{generic::BlockId, RuntimeString};
+use std::{
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 // Our native executor instance.
 pub struct ExecutorDispatch;
@@ -38,6 +53,7 @@ type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 
 pub fn new_partial(
 	config: &Configuration,
+	sealing: Option<Sealing>,
 ) -> Result<
 	sc_service::PartialComponents<
 		FullClient,
@@ -58,10 +74,6 @@ pub fn new_partial(
 	>,
 	ServiceError,
 > {
-	if config.keystore_remote.is_some() {
-		return Err(ServiceError::Other(format!("Remote Keystores are not supported.")));
-	}
-
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -110,9 +122,17 @@ pub fn new_partial(
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
-	let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+	// Manually sealed blocks carry no Aura seal, so they are imported straight
+	// into the client instead of going through the Aura verifier.
+	let import_queue = if sealing.is_some() {
+		sc_consensus_manual_seal::import_queue(
+			Box::new(client.clone()),
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+		)
+	} else {
+		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 
-	let import_queue =
 		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _, _>(ImportQueueParams {
 			block_import: grandpa_block_import.clone(),
 			justification_import: Some(Box::new(grandpa_block_import.clone())),
@@ -135,7 +155,8 @@ pub fn new_partial(
 			registry: config.prometheus_registry(),
 			check_for_equivocation: Default::default(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
-		})?;
+		})?
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -149,15 +170,78 @@ pub fn new_partial(
 	})
 }
 
-fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
-	// FIXME: here would the concrete keystore be built,
-	//        must return a concrete type (NOT `LocalKeystore`) that
-	//        implements `CryptoStore` and `SyncCryptoStore`
-	Err("Remote Keystore not supported.")
+/// Connect to the signer given by `--keystore-uri`, see `build3-signer`.
+fn remote_keystore(url: &String) -> Result<Arc<RemoteKeystore>, build3_remote_keystore::Error> {
+	RemoteKeystore::open(url).map(Arc::new)
+}
+
+/// First timestamp handed to manually sealed blocks.
+///
+/// Every manually sealed block advances the timestamp by exactly one slot so that
+/// the Aura slot derived from it always increases, however fast blocks are
+/// requested. Restarting a node that ran ahead of the wall clock must resume after
+/// the slot of the best block.
+fn manual_seal_start_timestamp(client: &FullClient) -> u64 {
+	let now = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.expect("Current time is always after unix epoch; qed")
+		.as_millis() as u64;
+
+	let best_slot = client
+		.header(BlockId::Hash(client.info().best_hash))
+		.ok()
+		.flatten()
+		.and_then(|header| sc_consensus_aura::find_pre_digest::<Block, AuraSignature>(&header).ok())
+		.map(u64::from)
+		.unwrap_or_default();
+
+	now.max((best_slot + 1) * SLOT_DURATION)
+}
+
+/// Refuse to run the dev runtime on a live chain, be it the native runtime of
+/// this node or the runtime on chain.
+fn ensure_production_runtime(
+	config: &Configuration,
+	client: &FullClient,
+) -> Result<(), ServiceError> {
+	if config.chain_spec.chain_type() != ChainType::Live {
+		return Ok(())
+	}
+
+	let on_chain = client.runtime_version_at(&BlockId::Hash(client.info().best_hash))?;
+	if build3_node_runtime::IS_DEV_RUNTIME ||
+		on_chain.spec_name == RuntimeString::Borrowed(DEV_SPEC_NAME)
+	{
+		return Err(ServiceError::Other(format!(
+			"Chain `{}` is live and cannot run the `{}` runtime. Build the node with \
+			 `--no-default-features` and use a chain spec with a production runtime.",
+			config.chain_spec.id(),
+			DEV_SPEC_NAME,
+		)))
+	}
+
+	Ok(())
+}
+
+/// Open the contracts index in the chain's directory, next to its database.
+fn open_contracts_index(config: &Configuration) -> Result<Arc<ContractsIndex>, ServiceError> {
+	let base_path = config
+		.base_path
+		.as_ref()
+		.ok_or_else(|| ServiceError::Other("The contracts index needs a base path".into()))?;
+	let path = base_path.config_dir(config.chain_spec.id()).join("contracts-index");
+
+	ContractsIndex::open(&path).map(Arc::new).map_err(|e| {
+		ServiceError::Other(format!("Error opening contracts index at {}: {}", path.display(), e))
+	})
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	mut config: Configuration,
+	sealing: Option<Sealing>,
+	contracts_index: bool,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -167,19 +251,33 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		select_chain,
 		transaction_pool,
 		other: (block_import, grandpa_link, mut telemetry),
-	} = new_partial(&config)?;
+	} = new_partial(&config, sealing)?;
+
+	ensure_production_runtime(&config, &client)?;
 
 	if let Some(url) = &config.keystore_remote {
 		match remote_keystore(url) {
 			Ok(k) => keystore_container.set_remote_keystore(k),
-			Err(e) => {
+			Err(e) =>
 				return Err(ServiceError::Other(format!(
 					"Error hooking up remote keystore for {}: {}",
 					url, e
-				)))
-			},
+				))),
 		};
 	}
+
+	let contracts_index = if contracts_index {
+		let index = open_contracts_index(&config)?;
+		task_manager.spawn_handle().spawn_blocking(
+			"contracts-index",
+			None,
+			build3_contracts_index::run(client.clone(), index.clone()),
+		);
+		Some(index)
+	} else {
+		None
+	};
+
 	let grandpa_protocol_name = sc_finality_grandpa::protocol_standard_name(
 		&client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
 		&config.chain_spec,
@@ -221,14 +319,36 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
+	let grandpa_parameters = Extensions::try_get(&*config.chain_spec)
+		.map(|extensions| extensions.grandpa.clone())
+		.unwrap_or_default();
+
+	// Channel used by the `engine_*` RPC methods to drive manual sealing.
+	let (command_sink, commands_stream) = match sealing {
+		Some(_) => {
+			let (sink, stream) = mpsc::channel::<EngineCommand<_>>(1024);
+			(Some(sink), Some(stream))
+		},
+		None => (None, None),
+	};
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
+		let command_sink = command_sink.clone();
+		let contracts_index = contracts_index.clone();
+		let keystore = keystore_container.sync_keystore();
 
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
+		Box::new(move |deny_unsafe, subscription_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: command_sink.clone(),
+				subscription_executor,
+				contracts_index: contracts_index.clone(),
+				keystore: keystore.clone(),
+			};
 
 			Ok(crate::rpc::create_full(deps))
 		})
@@ -247,6 +367,79 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let (Some(sealing), Some(rpc_commands)) = (sealing, commands_stream) {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		// There is no GRANDPA voter in this mode, so blocks sealed on our own
+		// schedule are finalized straight away. Blocks requested through
+		// `engine_createBlock` leave finality to `engine_finalizeBlock`.
+		let commands_stream = match sealing {
+			Sealing::Manual => rpc_commands.boxed(),
+			Sealing::Instant => stream::select(
+				rpc_commands,
+				transaction_pool.import_notification_stream().map(|_| {
+					EngineCommand::SealNewBlock {
+						create_empty: false,
+						finalize: true,
+						parent_hash: None,
+						sender: None,
+					}
+				}),
+			)
+			.boxed(),
+			Sealing::Interval(millis) => stream::select(
+				rpc_commands,
+				stream::unfold((), move |()| async move {
+					futures_timer::Delay::new(Duration::from_millis(millis)).await;
+					Some((
+						EngineCommand::SealNewBlock {
+							create_empty: true,
+							finalize: true,
+							parent_hash: None,
+							sender: None,
+						},
+						(),
+					))
+				}),
+			)
+			.boxed(),
+		};
+
+		let timestamp = Arc::new(AtomicU64::new(manual_seal_start_timestamp(&client)));
+
+		let authorship_future = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client: client.clone(),
+			pool: transaction_pool,
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client.clone()))),
+			create_inherent_data_providers: move |_, ()| {
+				let timestamp = timestamp.fetch_add(SLOT_DURATION, Ordering::SeqCst);
+
+				async move { Ok(sp_timestamp::InherentDataProvider::new(timestamp.into())) }
+			},
+		});
+
+		// the manual sealing task replaces AURA and is considered essential in
+		// the same way.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			authorship_future,
+		);
+
+		network_starter.start_network();
+		return Ok(task_manager)
+	}
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -304,11 +497,10 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		if role.is_authority() { Some(keystore_container.sync_keystore()) } else { None };
 
 	let grandpa_config = sc_finality_grandpa::Config {
-		// FIXME #1578 make this available through chainspec
-		gossip_duration: Duration::from_millis(333),
-		justification_period: 512,
+		gossip_duration: Duration::from_millis(grandpa_parameters.gossip_duration_millis),
+		justification_period: grandpa_parameters.justification_period,
 		name: Some(name),
-		observer_enabled: false,
+		observer_enabled: grandpa_parameters.observer_enabled,
 		keystore,
 		local_role: role,
 		telemetry: telemetry.as_ref().map(|x| x.handle()),
@@ -322,11 +514,19 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		// and vote data availability than the observer. The observer has not
 		// been tested extensively yet and having most nodes in a network run it
 		// could lead to finality stalls.
+		let mut voting_rule = sc_finality_grandpa::VotingRulesBuilder::new();
+		if let Some(blocks) = grandpa_parameters.before_best_block_by {
+			voting_rule = voting_rule.add(sc_finality_grandpa::BeforeBestBlockBy(blocks));
+		}
+		if grandpa_parameters.three_quarters_of_unfinalized_chain {
+			voting_rule = voting_rule.add(sc_finality_grandpa::ThreeQuartersOfTheUnfinalizedChain);
+		}
+
 		let grandpa_config = sc_finality_grandpa::GrandpaParams {
 			config: grandpa_config,
 			link: grandpa_link,
 			network,
-			voting_rule: sc_finality_grandpa::VotingRulesBuilder::default().build(),
+			voting_rule: voting_rule.build(),
 			prometheus_registry,
 			shared_voter_state: SharedVoterState::empty(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
@@ -0,0 +1,28 @@
+//! Runtime API definition for the contracts registry pallet.
+//!
+//! Lets frontends tell whether the code behind a contract has been audited
+//! before they let users interact with it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_contracts_registry::CodeAudit;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the audited code registry and the upload allowlist.
+	pub trait ContractsRegistryApi<AccountId, BlockNumber, Hash> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Audit of the code with hash `code_hash`, if it was audited.
+		fn code_audit(code_hash: Hash) -> Option<CodeAudit<AccountId, BlockNumber, Hash>>;
+
+		/// Audit of the code `contract` currently runs, if it was audited.
+		fn contract_audit(contract: AccountId) -> Option<CodeAudit<AccountId, BlockNumber, Hash>>;
+
+		/// Whether `who` may upload contract code.
+		fn is_uploader(who: AccountId) -> bool;
+	}
+}
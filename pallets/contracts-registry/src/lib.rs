@@ -0,0 +1,273 @@
+//! # Contracts Registry Pallet
+//!
+//! Governance over the contract code that runs on chain.
+//!
+//! Only allowlisted accounts may upload contract code. The runtime filters out
+//! the upload calls of the contracts pallet, and code is uploaded through this
+//! pallet instead, which checks the allowlist before handing the code over. A
+//! governance origin adds and removes uploaders.
+//!
+//! The pallet also keeps a registry of audited code, maintained by the council:
+//! for every audited code hash it records the auditor and the hash of the audit
+//! report. Frontends can check through the runtime API whether the code behind a
+//! contract was audited.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, weights::Weight, Parameter};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Contract code storage, provided by the runtime on top of its contracts
+/// pallet.
+pub trait CodeStore<AccountId, Hash> {
+	/// Balance of storage deposits.
+	type Balance: Parameter;
+
+	/// Store `code` for `who`, who pays its storage deposit up to
+	/// `storage_deposit_limit`.
+	fn upload_code(
+		who: AccountId,
+		code: Vec<u8>,
+		storage_deposit_limit: Option<Self::Balance>,
+	) -> DispatchResult;
+
+	/// Weight of uploading code of `code_len` bytes.
+	fn upload_code_weight(code_len: u32) -> Weight;
+
+	/// Hash of the code `contract` runs, if it is a contract.
+	fn code_hash(contract: &AccountId) -> Option<Hash>;
+}
+
+/// Audit of a contract code blob.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CodeAudit<AccountId, BlockNumber, Hash> {
+	pub auditor: AccountId,
+	/// Hash of the audit report, which is published off chain.
+	pub report_hash: Hash,
+	/// Block at which the audit was registered.
+	pub registered_at: BlockNumber,
+}
+
+pub type CodeAuditOf<T> = CodeAudit<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+
+type DepositBalanceOf<T> = <<T as Config>::CodeStore as CodeStore<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::Hash,
+>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Where uploaded code is stored.
+		type CodeStore: CodeStore<Self::AccountId, Self::Hash>;
+
+		/// Origin allowed to add and remove uploaders.
+		type UploaderOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to register and revoke audits.
+		type AuditOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Accounts allowed to upload contract code.
+	#[pallet::storage]
+	pub type Uploaders<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Audits by code hash.
+	#[pallet::storage]
+	#[pallet::getter(fn code_audit)]
+	pub type AuditedCode<T: Config> = StorageMap<_, Identity, T::Hash, CodeAuditOf<T>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Accounts allowed to upload contract code from genesis.
+		pub uploaders: Vec<T::AccountId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { uploaders: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for who in &self.uploaders {
+				Uploaders::<T>::insert(who, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An account may now upload contract code.
+		UploaderAdded { who: T::AccountId },
+		/// An account may no longer upload contract code.
+		UploaderRemoved { who: T::AccountId },
+		/// An audit of contract code was registered.
+		CodeAudited { code_hash: T::Hash, auditor: T::AccountId, report_hash: T::Hash },
+		/// The audit of contract code was revoked.
+		AuditRevoked { code_hash: T::Hash },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is not allowed to upload contract code.
+		NotUploader,
+		/// The account is already allowed to upload contract code.
+		AlreadyUploader,
+		/// The code has not been audited.
+		UnknownAudit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow `who` to upload contract code.
+		///
+		/// The origin must be `UploaderOrigin`.
+		#[pallet::weight(T::WeightInfo::add_uploader())]
+		pub fn add_uploader(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::UploaderOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(!Uploaders::<T>::contains_key(&who), Error::<T>::AlreadyUploader);
+
+			Uploaders::<T>::insert(&who, ());
+
+			Self::deposit_event(Event::UploaderAdded { who });
+			Ok(())
+		}
+
+		/// Stop `who` from uploading contract code. Code it already uploaded
+		/// stays.
+		///
+		/// The origin must be `UploaderOrigin`.
+		#[pallet::weight(T::WeightInfo::remove_uploader())]
+		pub fn remove_uploader(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::UploaderOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(Uploaders::<T>::contains_key(&who), Error::<T>::NotUploader);
+
+			Uploaders::<T>::remove(&who);
+
+			Self::deposit_event(Event::UploaderRemoved { who });
+			Ok(())
+		}
+
+		/// Upload contract code, to be instantiated later by its hash.
+		///
+		/// The origin must be an allowlisted uploader, who pays the storage
+		/// deposit of the code up to `storage_deposit_limit`.
+		#[pallet::weight(
+			T::CodeStore::upload_code_weight(code.len() as u32)
+				.saturating_add(T::WeightInfo::upload_code())
+		)]
+		pub fn upload_code(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<DepositBalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::is_uploader(&who), Error::<T>::NotUploader);
+
+			T::CodeStore::upload_code(who, code, storage_deposit_limit)
+		}
+
+		/// Register the audit of the code with hash `code_hash` by `auditor`,
+		/// replacing any earlier audit of the same code.
+		///
+		/// The origin must be `AuditOrigin`.
+		#[pallet::weight(T::WeightInfo::register_audit())]
+		pub fn register_audit(
+			origin: OriginFor<T>,
+			code_hash: T::Hash,
+			auditor: <T::Lookup as StaticLookup>::Source,
+			report_hash: T::Hash,
+		) -> DispatchResult {
+			T::AuditOrigin::ensure_origin(origin)?;
+			let auditor = T::Lookup::lookup(auditor)?;
+
+			AuditedCode::<T>::insert(
+				code_hash,
+				CodeAudit {
+					auditor: auditor.clone(),
+					report_hash,
+					registered_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::CodeAudited { code_hash, auditor, report_hash });
+			Ok(())
+		}
+
+		/// Revoke the audit of the code with hash `code_hash`, e.g. when a flaw
+		/// is found that the audit missed.
+		///
+		/// The origin must be `AuditOrigin`.
+		#[pallet::weight(T::WeightInfo::revoke_audit())]
+		pub fn revoke_audit(origin: OriginFor<T>, code_hash: T::Hash) -> DispatchResult {
+			T::AuditOrigin::ensure_origin(origin)?;
+			ensure!(AuditedCode::<T>::contains_key(code_hash), Error::<T>::UnknownAudit);
+
+			AuditedCode::<T>::remove(code_hash);
+
+			Self::deposit_event(Event::AuditRevoked { code_hash });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether `who` may upload contract code.
+	pub fn is_uploader(who: &T::AccountId) -> bool {
+		Uploaders::<T>::contains_key(who)
+	}
+
+	/// Audit of the code `contract` currently runs, if it was audited.
+	pub fn contract_audit(contract: &T::AccountId) -> Option<CodeAuditOf<T>> {
+		T::CodeStore::code_hash(contract).and_then(Self::code_audit)
+	}
+}
@@ -0,0 +1,119 @@
+//! Test runtime of the contracts registry pallet.
+
+use crate as pallet_contracts_registry;
+use frame_support::{
+	dispatch::DispatchResult,
+	ord_parameter_types,
+	traits::{ConstU32, ConstU64, Everything, GenesisBuild},
+	weights::Weight,
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+};
+use std::cell::RefCell;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		ContractsRegistry: pallet_contracts_registry::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+thread_local! {
+	/// Code blobs uploaded so far, with their uploader.
+	pub static UPLOADED: RefCell<Vec<(u64, Vec<u8>)>> = RefCell::new(Vec::new());
+}
+
+/// Account of a contract running `CONTRACT_CODE`.
+pub const CONTRACT: u64 = 100;
+pub const CONTRACT_CODE: &[u8] = b"contract code";
+
+/// Keeps the uploaded code in [`UPLOADED`]. [`CONTRACT`] is the only contract.
+pub struct CodeStore;
+impl pallet_contracts_registry::CodeStore<u64, H256> for CodeStore {
+	type Balance = u64;
+
+	fn upload_code(who: u64, code: Vec<u8>, _: Option<u64>) -> DispatchResult {
+		UPLOADED.with(|uploaded| uploaded.borrow_mut().push((who, code)));
+		Ok(())
+	}
+
+	fn upload_code_weight(_: u32) -> Weight {
+		0
+	}
+
+	fn code_hash(contract: &u64) -> Option<H256> {
+		(*contract == CONTRACT).then(|| BlakeTwo256::hash(CONTRACT_CODE))
+	}
+}
+
+ord_parameter_types! {
+	/// Account standing in for the council.
+	pub const Council: u64 = 10;
+}
+
+impl pallet_contracts_registry::Config for Test {
+	type Event = Event;
+	type CodeStore = CodeStore;
+	type UploaderOrigin = EnsureRoot<u64>;
+	type AuditOrigin = EnsureSignedBy<Council, u64>;
+	type WeightInfo = ();
+}
+
+/// Allowlisted at genesis.
+pub const UPLOADER: u64 = 1;
+/// Not allowlisted.
+pub const OUTSIDER: u64 = 2;
+pub const AUDITOR: u64 = 3;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_contracts_registry::GenesisConfig::<Test> { uploaders: vec![UPLOADER] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Code uploaded so far, with its uploader.
+pub fn uploaded() -> Vec<(u64, Vec<u8>)> {
+	UPLOADED.with(|uploaded| uploaded.borrow().clone())
+}
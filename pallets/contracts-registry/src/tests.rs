@@ -0,0 +1,117 @@
+//! Tests of the contracts registry pallet.
+
+use crate::{mock::*, CodeAudit, Error};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const REPORT: H256 = H256([0xaa; 32]);
+
+fn code_hash() -> H256 {
+	BlakeTwo256::hash(CONTRACT_CODE)
+}
+
+#[test]
+fn only_uploaders_upload_code() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractsRegistry::upload_code(Origin::signed(OUTSIDER), b"code".to_vec(), None),
+			Error::<Test>::NotUploader
+		);
+		assert_noop!(
+			ContractsRegistry::upload_code(Origin::root(), b"code".to_vec(), None),
+			DispatchError::BadOrigin
+		);
+		assert!(uploaded().is_empty());
+
+		assert_ok!(ContractsRegistry::upload_code(
+			Origin::signed(UPLOADER),
+			b"code".to_vec(),
+			None
+		));
+		assert_eq!(uploaded(), vec![(UPLOADER, b"code".to_vec())]);
+	});
+}
+
+#[test]
+fn governance_manages_the_uploaders() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractsRegistry::add_uploader(Origin::signed(UPLOADER), OUTSIDER),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ContractsRegistry::add_uploader(Origin::root(), UPLOADER),
+			Error::<Test>::AlreadyUploader
+		);
+		assert_ok!(ContractsRegistry::add_uploader(Origin::root(), OUTSIDER));
+		assert!(ContractsRegistry::is_uploader(&OUTSIDER));
+		assert_ok!(ContractsRegistry::upload_code(
+			Origin::signed(OUTSIDER),
+			b"code".to_vec(),
+			None
+		));
+
+		assert_ok!(ContractsRegistry::remove_uploader(Origin::root(), OUTSIDER));
+		assert_noop!(
+			ContractsRegistry::remove_uploader(Origin::root(), OUTSIDER),
+			Error::<Test>::NotUploader
+		);
+		assert_noop!(
+			ContractsRegistry::upload_code(Origin::signed(OUTSIDER), b"more".to_vec(), None),
+			Error::<Test>::NotUploader
+		);
+		assert_eq!(uploaded().len(), 1);
+	});
+}
+
+#[test]
+fn audits_are_reported_for_the_code_and_its_contracts() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ContractsRegistry::contract_audit(&CONTRACT), None);
+		assert_noop!(
+			ContractsRegistry::register_audit(
+				Origin::signed(UPLOADER),
+				code_hash(),
+				AUDITOR,
+				REPORT
+			),
+			DispatchError::BadOrigin
+		);
+
+		System::set_block_number(5);
+		assert_ok!(ContractsRegistry::register_audit(
+			Origin::signed(Council::get()),
+			code_hash(),
+			AUDITOR,
+			REPORT
+		));
+		let audit = CodeAudit { auditor: AUDITOR, report_hash: REPORT, registered_at: 5 };
+		assert_eq!(ContractsRegistry::code_audit(code_hash()), Some(audit.clone()));
+		assert_eq!(ContractsRegistry::contract_audit(&CONTRACT), Some(audit));
+
+		// Accounts which are not contracts run no audited code.
+		assert_eq!(ContractsRegistry::contract_audit(&UPLOADER), None);
+		assert_eq!(ContractsRegistry::code_audit(H256::zero()), None);
+	});
+}
+
+#[test]
+fn revoked_audits_are_no_longer_reported() {
+	new_test_ext().execute_with(|| {
+		let council = || Origin::signed(Council::get());
+		assert_noop!(
+			ContractsRegistry::revoke_audit(council(), code_hash()),
+			Error::<Test>::UnknownAudit
+		);
+		assert_ok!(ContractsRegistry::register_audit(council(), code_hash(), AUDITOR, REPORT));
+
+		assert_noop!(
+			ContractsRegistry::revoke_audit(Origin::root(), code_hash()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ContractsRegistry::revoke_audit(council(), code_hash()));
+		assert_eq!(ContractsRegistry::code_audit(code_hash()), None);
+		assert_eq!(ContractsRegistry::contract_audit(&CONTRACT), None);
+	});
+}
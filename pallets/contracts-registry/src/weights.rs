@@ -0,0 +1,79 @@
+//! Weights for pallet_contracts_registry.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own. The weight of storing uploaded code
+//! comes from the contracts pallet and is added on top.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_contracts_registry.
+pub trait WeightInfo {
+	fn add_uploader() -> Weight;
+	fn remove_uploader() -> Weight;
+	fn upload_code() -> Weight;
+	fn register_audit() -> Weight;
+	fn revoke_audit() -> Weight;
+}
+
+/// Weights for pallet_contracts_registry using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: ContractsRegistry Uploaders (r:1 w:1)
+	fn add_uploader() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ContractsRegistry Uploaders (r:1 w:1)
+	fn remove_uploader() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ContractsRegistry Uploaders (r:1 w:0)
+	fn upload_code() -> Weight {
+		(5_000_000 as Weight).saturating_add(T::DbWeight::get().reads(1 as Weight))
+	}
+	// Storage: ContractsRegistry AuditedCode (r:0 w:1)
+	fn register_audit() -> Weight {
+		(16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: ContractsRegistry AuditedCode (r:1 w:1)
+	fn revoke_audit() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn add_uploader() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn remove_uploader() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn upload_code() -> Weight {
+		(5_000_000 as Weight).saturating_add(RocksDbWeight::get().reads(1 as Weight))
+	}
+	fn register_audit() -> Weight {
+		(16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_audit() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
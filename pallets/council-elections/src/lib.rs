@@ -0,0 +1,107 @@
+//! # Council Elections Pallet
+//!
+//! Council seats are filled by `pallet_elections_phragmen`, which lets any
+//! account vote. This pallet restricts voting to the electorate, the licensed
+//! engineers in the runtime: the runtime filters out the phragmen pallet's own
+//! `vote` call, and voters call [`Pallet::vote`] instead, which checks the
+//! electorate before placing the vote. Candidacy, renouncing, runners-up and the
+//! rotation of the council at the end of each term are left to the phragmen
+//! pallet.
+//!
+//! A vote stays in place if the voter later loses their license, until they
+//! change or remove it, or anybody removes it through
+//! [`Pallet::remove_voter_if_unlicensed`]. The removal returns the voter's bond
+//! and is free, so that lapsed and revoked licensees do not keep backing
+//! candidates through the next election.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+use frame_support::{
+	traits::{Contains, Currency},
+	weights::Pays,
+};
+use pallet_elections_phragmen::WeightInfo;
+use sp_runtime::traits::StaticLookup;
+use sp_std::prelude::*;
+
+type BalanceOf<T> = <<T as pallet_elections_phragmen::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_elections_phragmen::Config {
+		/// Accounts allowed to vote in council elections.
+		type Electorate: Contains<Self::AccountId>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is not part of the electorate.
+		NotInElectorate,
+		/// The voter is still part of the electorate.
+		StillInElectorate,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Vote for up to `MAXIMUM_VOTE` of the candidates and members in
+		/// `votes`, backing them with `value`, as `pallet_elections_phragmen::vote`
+		/// does.
+		///
+		/// The origin must be part of the electorate.
+		#[pallet::weight(
+			<T as pallet_elections_phragmen::Config>::WeightInfo::vote_more(votes.len() as u32)
+				.max(<T as pallet_elections_phragmen::Config>::WeightInfo::vote_less(
+					votes.len() as u32,
+				))
+				.max(<T as pallet_elections_phragmen::Config>::WeightInfo::vote_equal(
+					votes.len() as u32,
+				))
+		)]
+		pub fn vote(
+			origin: OriginFor<T>,
+			votes: Vec<T::AccountId>,
+			#[pallet::compact] value: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(T::Electorate::contains(&who), Error::<T>::NotInElectorate);
+
+			pallet_elections_phragmen::Pallet::<T>::vote(
+				frame_system::RawOrigin::Signed(who).into(),
+				votes,
+				value,
+			)
+		}
+
+		/// Remove the vote of `voter`, who is no longer part of the electorate,
+		/// returning its bond, as `pallet_elections_phragmen::remove_voter` does.
+		///
+		/// Anybody may call this, without paying fees when the vote is removed.
+		#[pallet::weight(<T as pallet_elections_phragmen::Config>::WeightInfo::remove_voter())]
+		pub fn remove_voter_if_unlicensed(
+			origin: OriginFor<T>,
+			voter: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let voter = T::Lookup::lookup(voter)?;
+			ensure!(!T::Electorate::contains(&voter), Error::<T>::StillInElectorate);
+
+			pallet_elections_phragmen::Pallet::<T>::remove_voter(
+				frame_system::RawOrigin::Signed(voter).into(),
+			)?;
+			Ok(Pays::No.into())
+		}
+	}
+}
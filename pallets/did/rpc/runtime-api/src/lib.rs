@@ -0,0 +1,22 @@
+//! Runtime API definition for the DID pallet.
+//!
+//! What resolvers need to build the DID document of an account.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_did::{Delegate, DidDocument, DidKey, DidPublicKey, KeyPurpose, Service};
+
+sp_api::decl_runtime_apis! {
+	/// Read access to DID documents.
+	pub trait DidApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Everything recorded for the DID of `account`, without lapsed
+		/// delegations.
+		fn document(account: AccountId) -> DidDocument<AccountId, BlockNumber, Vec<u8>>;
+	}
+}
@@ -0,0 +1,263 @@
+//! DID resolution for the `did:build3` method.
+//!
+//! `did_resolve` implements the W3C DID resolution interface: given a DID, it
+//! returns the DID document, the resolution metadata and the document metadata.
+//! The document is built from the account itself, the DID pallet's record of
+//! the account and the licenses the account holds, which are listed as the
+//! license credentials of the DID subject.
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_licensure_rpc::{credential, did, LicensureRuntimeApi};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::crypto::Ss58Codec;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use pallet_did_rpc_runtime_api::{
+	DidApi as DidRuntimeApi, DidDocument, DidPublicKey, KeyPurpose,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Result of resolving a DID.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionResult<BlockNumber> {
+	pub did_resolution_metadata: ResolutionMetadata,
+	/// The DID document, `None` if the DID could not be resolved.
+	pub did_document: Option<Value>,
+	pub did_document_metadata: DocumentMetadata<BlockNumber>,
+}
+
+/// Metadata of the resolution itself.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionMetadata {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub content_type: Option<String>,
+	/// `invalidDid` if the DID is not a `did:build3` DID.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+/// Metadata of the DID document.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentMetadata<BlockNumber> {
+	pub deactivated: bool,
+	/// Block of the last change to the document, if it was ever changed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub updated_at_block: Option<BlockNumber>,
+}
+
+/// DID resolution RPC methods.
+#[rpc]
+pub trait DidApi<BlockHash, BlockNumber> {
+	/// Resolve a `did:build3` DID into its DID document.
+	#[rpc(name = "did_resolve")]
+	fn resolve(&self, did: String, at: Option<BlockHash>) -> Result<ResolutionResult<BlockNumber>>;
+}
+
+/// Provides RPC methods to resolve DIDs.
+pub struct Did<C, B, AccountId, BoardId, Hash> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, AccountId, BoardId, Hash)>,
+}
+
+impl<C, B, AccountId, BoardId, Hash> Did<C, B, AccountId, BoardId, Hash> {
+	/// Create new `Did` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+fn to_json(value: impl Serialize) -> Result<Value> {
+	serde_json::to_value(value).map_err(runtime_error_into_rpc_err)
+}
+
+fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+/// Verification method of `key` in the document of `did_url`.
+fn verification_method(did_url: &str, id: u32, key: &DidPublicKey) -> (String, Value) {
+	let (method_type, public) = match key {
+		DidPublicKey::Sr25519(public) => ("Sr25519VerificationKey2020", &public[..]),
+		DidPublicKey::Ed25519(public) => ("Ed25519VerificationKey2018", &public[..]),
+		DidPublicKey::Ecdsa(public) => ("EcdsaSecp256k1VerificationKey2019", &public[..]),
+	};
+	let method_id = format!("{}#key-{}", did_url, id);
+	let method = json!({
+		"id": method_id,
+		"type": method_type,
+		"controller": did_url,
+		"publicKeyHex": hex(public),
+	});
+	(method_id, method)
+}
+
+impl<C, Block, AccountId, BoardId, BlockNumber, Hash> DidApi<<Block as BlockT>::Hash, BlockNumber>
+	for Did<C, Block, AccountId, BoardId, Hash>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: DidRuntimeApi<Block, AccountId, BlockNumber>,
+	C::Api: LicensureRuntimeApi<Block, AccountId, BoardId, BlockNumber, Hash>,
+	AccountId: Codec + Ss58Codec + Clone + Send + Sync + 'static,
+	BoardId: Codec + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn resolve(
+		&self,
+		did: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ResolutionResult<BlockNumber>> {
+		let account: AccountId = match did::account_of(&did) {
+			Some(account) => account,
+			None =>
+				return Ok(ResolutionResult {
+					did_resolution_metadata: ResolutionMetadata {
+						content_type: None,
+						error: Some("invalidDid".into()),
+					},
+					did_document: None,
+					did_document_metadata: DocumentMetadata {
+						deactivated: false,
+						updated_at_block: None,
+					},
+				}),
+		};
+		// Resolve the canonical form of the DID, whatever address format it
+		// was given in.
+		let did = did::did_of(&account);
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let record = api.document(&at, account.clone()).map_err(runtime_error_into_rpc_err)?;
+		let document_metadata = DocumentMetadata {
+			deactivated: record.deactivated,
+			updated_at_block: record.updated_at,
+		};
+		if record.deactivated {
+			return Ok(ResolutionResult {
+				did_resolution_metadata: ResolutionMetadata {
+					content_type: Some("application/did+ld+json".into()),
+					error: None,
+				},
+				did_document: Some(json!({
+					"@context": ["https://www.w3.org/ns/did/v1"],
+					"id": did,
+				})),
+				did_document_metadata: document_metadata,
+			})
+		}
+
+		// The account itself always authenticates the DID, unless control was
+		// handed to another account.
+		let account_method = format!("{}#account", did);
+		let mut methods = vec![json!({
+			"id": account_method,
+			"type": "Build3Account",
+			"controller": did,
+			"blockchainAccountId": account.to_ss58check(),
+		})];
+		let mut authentication = vec![];
+		let mut assertion_method = vec![];
+		let mut key_agreement = vec![];
+		let mut capability_invocation = vec![];
+		let controller = match &record.controller {
+			Some(controller) => did::did_of(controller),
+			None => {
+				authentication.push(account_method.clone());
+				capability_invocation.push(account_method);
+				did.clone()
+			},
+		};
+
+		for key in &record.keys {
+			let (id, method) = verification_method(&did, key.id, &key.key);
+			methods.push(method);
+			match key.purpose {
+				KeyPurpose::Authentication => authentication.push(id),
+				KeyPurpose::AssertionMethod => assertion_method.push(id),
+				KeyPurpose::KeyAgreement => key_agreement.push(id),
+				KeyPurpose::CapabilityInvocation => capability_invocation.push(id),
+			}
+		}
+
+		let capability_delegation: Vec<_> = record
+			.delegates
+			.iter()
+			.map(|delegate| format!("{}#account", did::did_of(&delegate.account)))
+			.collect();
+
+		let services: Vec<_> = record
+			.services
+			.iter()
+			.map(|service| {
+				json!({
+					"id": format!("{}#{}", did, String::from_utf8_lossy(&service.id)),
+					"type": String::from_utf8_lossy(&service.service_type),
+					"serviceEndpoint": String::from_utf8_lossy(&service.endpoint),
+				})
+			})
+			.collect();
+
+		let licenses = api.licenses_of(&at, account).map_err(runtime_error_into_rpc_err)?;
+		let license_credentials = licenses
+			.into_iter()
+			.map(|(number, license)| {
+				let number = String::from_utf8_lossy(&number).into_owned();
+				Ok(json!({
+					"id": credential::license_id(&number),
+					"type": credential::CREDENTIAL_TYPE,
+					"license": {
+						"number": number,
+						"board": to_json(&license.board)?,
+						"discipline": to_json(&license.discipline)?,
+						"status": to_json(&license.status)?,
+						"issuedAt": to_json(&license.issued_at)?,
+						"expiresAt": to_json(&license.expires_at)?,
+					},
+				}))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		Ok(ResolutionResult {
+			did_resolution_metadata: ResolutionMetadata {
+				content_type: Some("application/did+ld+json".into()),
+				error: None,
+			},
+			did_document: Some(json!({
+				"@context": ["https://www.w3.org/ns/did/v1"],
+				"id": did,
+				"controller": controller,
+				"verificationMethod": methods,
+				"authentication": authentication,
+				"assertionMethod": assertion_method,
+				"keyAgreement": key_agreement,
+				"capabilityInvocation": capability_invocation,
+				"capabilityDelegation": capability_delegation,
+				"service": services,
+				"licenseCredentials": license_credentials,
+			})),
+			did_document_metadata: document_metadata,
+		})
+	}
+}
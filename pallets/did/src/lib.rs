@@ -0,0 +1,597 @@
+//! # DID Pallet
+//!
+//! DID documents for the `did:build3` method, in which every account is a DID:
+//! `did:build3:<ss58 address>`. An account's DID resolves without any
+//! registration, to a document whose only verification method is the account
+//! itself. This pallet records what the document holds beyond that:
+//!
+//! - verification keys, added for one purpose each and rotated or revoked at will, so that
+//!   credentials need not be signed with the account key;
+//! - service endpoints, such as a firm's permit portal;
+//! - delegates, accounts the DID delegates its capabilities to, optionally until a given block.
+//!
+//! A DID is controlled by its own account unless it names another controller,
+//! typically a multisig account, so that changes to the document need the
+//! approvals of the multisig. Deactivating a DID clears its document for good.
+//!
+//! The controller pays for what the document holds: `DidDeposit` once anything
+//! is recorded for the DID, and `ItemDeposit` for each key, service endpoint and
+//! delegate. Deposits are returned as entries are removed, all at once when the
+//! DID is deactivated, and pass to the new controller when control is handed
+//! over.
+//!
+//! Resolution into W3C DID documents is left to `pallet-did-rpc`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Currency, NamedReservableCurrency},
+	BoundedVec,
+};
+use sp_runtime::{
+	traits::{Saturating, StaticLookup, Zero},
+	DispatchError,
+};
+use sp_std::prelude::*;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type ReserveIdentifierOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+	<T as frame_system::Config>::AccountId,
+>>::ReserveIdentifier;
+pub type TextOf<T> = BoundedVec<u8, <T as Config>::MaxServiceFieldLength>;
+pub type DidKeyOf<T> = DidKey<<T as frame_system::Config>::BlockNumber>;
+pub type ServiceOf<T> = Service<TextOf<T>>;
+pub type DelegateOf<T> =
+	Delegate<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+pub type DidDocumentOf<T> = DidDocument<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	Vec<u8>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency DID deposits are held in.
+		type Currency: NamedReservableCurrency<Self::AccountId>;
+
+		/// Identifier of the named reserve DID deposits are held under.
+		#[pallet::constant]
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// Deposit reserved from the controller of a DID once anything is
+		/// recorded for it.
+		#[pallet::constant]
+		type DidDeposit: Get<BalanceOf<Self>>;
+
+		/// Deposit reserved from the controller of a DID for each of its keys,
+		/// service endpoints and delegates.
+		#[pallet::constant]
+		type ItemDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum number of verification keys of a DID.
+		#[pallet::constant]
+		type MaxKeys: Get<u32>;
+
+		/// Maximum number of service endpoints of a DID.
+		#[pallet::constant]
+		type MaxServices: Get<u32>;
+
+		/// Maximum number of delegates of a DID.
+		#[pallet::constant]
+		type MaxDelegates: Get<u32>;
+
+		/// Maximum length of a service identifier, type or endpoint.
+		#[pallet::constant]
+		type MaxServiceFieldLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Controller of each DID which is not controlled by its own account.
+	#[pallet::storage]
+	#[pallet::getter(fn controller)]
+	pub type Controllers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Verification keys of each DID.
+	#[pallet::storage]
+	#[pallet::getter(fn keys)]
+	pub type Keys<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<DidKeyOf<T>, T::MaxKeys>,
+		ValueQuery,
+	>;
+
+	/// Service endpoints of each DID.
+	#[pallet::storage]
+	#[pallet::getter(fn services)]
+	pub type Services<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ServiceOf<T>, T::MaxServices>,
+		ValueQuery,
+	>;
+
+	/// Delegates of each DID.
+	#[pallet::storage]
+	#[pallet::getter(fn delegates)]
+	pub type Delegates<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<DelegateOf<T>, T::MaxDelegates>,
+		ValueQuery,
+	>;
+
+	/// Bookkeeping of each DID whose document was ever changed.
+	#[pallet::storage]
+	#[pallet::getter(fn did_info)]
+	pub type Dids<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DidInfo<T::BlockNumber>, OptionQuery>;
+
+	/// Deposit held from the controller of each DID.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit)]
+	pub type Deposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The controller of a DID changed, `None` meaning the DID's own account.
+		ControllerSet { did: T::AccountId, controller: Option<T::AccountId> },
+		/// A verification key was added to a DID.
+		KeyAdded { did: T::AccountId, key: KeyId, purpose: KeyPurpose },
+		/// A verification key of a DID was replaced by a new one.
+		KeyRotated { did: T::AccountId, old: KeyId, new: KeyId },
+		/// A verification key of a DID was revoked.
+		KeyRevoked { did: T::AccountId, key: KeyId },
+		/// A service endpoint was added to a DID.
+		ServiceAdded { did: T::AccountId, id: TextOf<T> },
+		/// A service endpoint of a DID was removed.
+		ServiceRemoved { did: T::AccountId, id: TextOf<T> },
+		/// A DID delegated its capabilities to an account.
+		DelegateAdded {
+			did: T::AccountId,
+			delegate: T::AccountId,
+			expires_at: Option<T::BlockNumber>,
+		},
+		/// A DID withdrew its delegation to an account.
+		DelegateRemoved { did: T::AccountId, delegate: T::AccountId },
+		/// A DID was deactivated.
+		Deactivated { did: T::AccountId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin does not control the DID.
+		NotController,
+		/// The DID has been deactivated.
+		DidDeactivated,
+		/// The DID already has `MaxKeys` keys.
+		TooManyKeys,
+		/// The DID has no key with this identifier.
+		UnknownKey,
+		/// The DID already has this key.
+		DuplicateKey,
+		/// The DID already has `MaxServices` service endpoints.
+		TooManyServices,
+		/// A service identifier, type or endpoint is longer than
+		/// `MaxServiceFieldLength`.
+		ServiceFieldTooLong,
+		/// The DID already has a service with this identifier.
+		ServiceExists,
+		/// The DID has no service with this identifier.
+		UnknownService,
+		/// The DID already has `MaxDelegates` delegates.
+		TooManyDelegates,
+		/// The account is already a delegate of the DID.
+		DelegateExists,
+		/// The account is not a delegate of the DID.
+		UnknownDelegate,
+		/// Expiry must lie in the future.
+		InvalidExpiry,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Hand control of `did` to `controller`, or back to the DID's own
+		/// account if `None`. The deposit of the DID is reserved from the new
+		/// controller and returned to the old one.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::set_controller())]
+		pub fn set_controller(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			controller: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+			let controller =
+				controller.map(T::Lookup::lookup).transpose()?.filter(|account| *account != did);
+
+			let old = Self::controller_of(&did);
+			let new = controller.clone().unwrap_or_else(|| did.clone());
+			let deposit = Deposits::<T>::get(&did);
+			let added = Self::did_deposit(&did);
+			if new == old {
+				T::Currency::reserve_named(&T::ReserveId::get(), &new, added)?;
+			} else {
+				T::Currency::reserve_named(
+					&T::ReserveId::get(),
+					&new,
+					deposit.saturating_add(added),
+				)?;
+				T::Currency::unreserve_named(&T::ReserveId::get(), &old, deposit);
+			}
+			Deposits::<T>::insert(&did, deposit.saturating_add(added));
+			Controllers::<T>::set(&did, controller.clone());
+			Self::touch(&did, None);
+
+			Self::deposit_event(Event::ControllerSet { did, controller });
+			Ok(())
+		}
+
+		/// Add `key` to `did` for `purpose`, reserving `ItemDeposit`.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::add_key())]
+		pub fn add_key(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			key: DidPublicKey,
+			purpose: KeyPurpose,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+
+			let id = Self::next_key(&did);
+			let added_at = frame_system::Pallet::<T>::block_number();
+
+			Keys::<T>::try_mutate(&did, |keys| -> DispatchResult {
+				ensure!(!keys.iter().any(|k| k.key == key), Error::<T>::DuplicateKey);
+				keys.try_push(DidKey { id, key, purpose, added_at })
+					.map_err(|()| Error::<T>::TooManyKeys)?;
+				Self::hold(&did, 1)
+			})?;
+			Self::touch(&did, Some(id));
+
+			Self::deposit_event(Event::KeyAdded { did, key: id, purpose });
+			Ok(())
+		}
+
+		/// Replace key `old` of `did` with `key`, for the same purpose. The new
+		/// key gets a new identifier.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::rotate_key())]
+		pub fn rotate_key(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			old: KeyId,
+			key: DidPublicKey,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+
+			let new = Self::next_key(&did);
+			let added_at = frame_system::Pallet::<T>::block_number();
+
+			Keys::<T>::try_mutate(&did, |keys| -> DispatchResult {
+				ensure!(!keys.iter().any(|k| k.key == key), Error::<T>::DuplicateKey);
+				let entry = keys.iter_mut().find(|k| k.id == old).ok_or(Error::<T>::UnknownKey)?;
+				*entry = DidKey { id: new, key, purpose: entry.purpose, added_at };
+				Ok(())
+			})?;
+			Self::touch(&did, Some(new));
+
+			Self::deposit_event(Event::KeyRotated { did, old, new });
+			Ok(())
+		}
+
+		/// Revoke key `key` of `did`, returning its deposit.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::revoke_key())]
+		pub fn revoke_key(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			key: KeyId,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+
+			Keys::<T>::try_mutate(&did, |keys| -> DispatchResult {
+				let index = keys.iter().position(|k| k.id == key).ok_or(Error::<T>::UnknownKey)?;
+				keys.remove(index);
+				Ok(())
+			})?;
+			Self::release(&did, 1);
+			Self::touch(&did, None);
+
+			Self::deposit_event(Event::KeyRevoked { did, key });
+			Ok(())
+		}
+
+		/// Add service endpoint `id` of type `service_type` at `endpoint` to
+		/// `did`, reserving `ItemDeposit`.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::add_service())]
+		pub fn add_service(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			id: Vec<u8>,
+			service_type: Vec<u8>,
+			endpoint: Vec<u8>,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+			let id = Self::bounded_text(id)?;
+			let service = Service {
+				id: id.clone(),
+				service_type: Self::bounded_text(service_type)?,
+				endpoint: Self::bounded_text(endpoint)?,
+			};
+
+			Services::<T>::try_mutate(&did, |services| -> DispatchResult {
+				ensure!(!services.iter().any(|s| s.id == id), Error::<T>::ServiceExists);
+				services.try_push(service).map_err(|()| Error::<T>::TooManyServices)?;
+				Self::hold(&did, 1)
+			})?;
+			Self::touch(&did, None);
+
+			Self::deposit_event(Event::ServiceAdded { did, id });
+			Ok(())
+		}
+
+		/// Remove service endpoint `id` from `did`, returning its deposit.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::remove_service())]
+		pub fn remove_service(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			id: Vec<u8>,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+			let id = Self::bounded_text(id)?;
+
+			Services::<T>::try_mutate(&did, |services| -> DispatchResult {
+				let index =
+					services.iter().position(|s| s.id == id).ok_or(Error::<T>::UnknownService)?;
+				services.remove(index);
+				Ok(())
+			})?;
+			Self::release(&did, 1);
+			Self::touch(&did, None);
+
+			Self::deposit_event(Event::ServiceRemoved { did, id });
+			Ok(())
+		}
+
+		/// Delegate the capabilities of `did` to `delegate`, until `expires_at`
+		/// if given, reserving `ItemDeposit`. The deposits of lapsed delegations
+		/// are returned.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::add_delegate())]
+		pub fn add_delegate(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(expires_at.map_or(true, |at| at > now), Error::<T>::InvalidExpiry);
+
+			Delegates::<T>::try_mutate(&did, |delegates| -> DispatchResult {
+				// Lapsed delegations make room for new ones.
+				let listed = delegates.len();
+				delegates.retain(|d| d.expires_at.map_or(true, |at| at > now));
+				let lapsed = (listed - delegates.len()) as u32;
+				ensure!(
+					!delegates.iter().any(|d| d.account == delegate),
+					Error::<T>::DelegateExists
+				);
+				delegates
+					.try_push(Delegate { account: delegate.clone(), expires_at })
+					.map_err(|()| Error::<T>::TooManyDelegates)?;
+				Self::hold(&did, 1)?;
+				Self::release(&did, lapsed);
+				Ok(())
+			})?;
+			Self::touch(&did, None);
+
+			Self::deposit_event(Event::DelegateAdded { did, delegate, expires_at });
+			Ok(())
+		}
+
+		/// Withdraw the delegation of `did` to `delegate`, returning its deposit.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::remove_delegate())]
+		pub fn remove_delegate(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			Delegates::<T>::try_mutate(&did, |delegates| -> DispatchResult {
+				let index = delegates
+					.iter()
+					.position(|d| d.account == delegate)
+					.ok_or(Error::<T>::UnknownDelegate)?;
+				delegates.remove(index);
+				Ok(())
+			})?;
+			Self::release(&did, 1);
+			Self::touch(&did, None);
+
+			Self::deposit_event(Event::DelegateRemoved { did, delegate });
+			Ok(())
+		}
+
+		/// Deactivate `did` for good, clearing its document and returning its
+		/// deposits.
+		///
+		/// The origin must control the DID.
+		#[pallet::weight(T::WeightInfo::deactivate())]
+		pub fn deactivate(
+			origin: OriginFor<T>,
+			did: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let did = Self::ensure_controller(origin, did)?;
+
+			let deposit = Deposits::<T>::take(&did);
+			T::Currency::unreserve_named(&T::ReserveId::get(), &Self::controller_of(&did), deposit);
+			Controllers::<T>::remove(&did);
+			Keys::<T>::remove(&did);
+			Services::<T>::remove(&did);
+			Delegates::<T>::remove(&did);
+			Self::touch(&did, None);
+			Dids::<T>::mutate(&did, |info| {
+				if let Some(info) = info {
+					info.deactivated = true;
+				}
+			});
+
+			Self::deposit_event(Event::Deactivated { did });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The DID `did` stands for, if `origin` controls it and it is active.
+	fn ensure_controller(
+		origin: T::Origin,
+		did: <T::Lookup as StaticLookup>::Source,
+	) -> Result<T::AccountId, DispatchError> {
+		let who = frame_system::ensure_signed(origin)?;
+		let did = T::Lookup::lookup(did)?;
+		ensure!(
+			!Dids::<T>::get(&did).map_or(false, |info| info.deactivated),
+			Error::<T>::DidDeactivated
+		);
+		ensure!(Self::controller_of(&did) == who, Error::<T>::NotController);
+		Ok(did)
+	}
+
+	/// Account controlling `did`.
+	pub fn controller_of(did: &T::AccountId) -> T::AccountId {
+		Controllers::<T>::get(did).unwrap_or_else(|| did.clone())
+	}
+
+	fn bounded_text(text: Vec<u8>) -> Result<TextOf<T>, Error<T>> {
+		text.try_into().map_err(|_| Error::<T>::ServiceFieldTooLong)
+	}
+
+	/// `DidDeposit` if nothing was recorded for `did` yet, zero otherwise.
+	fn did_deposit(did: &T::AccountId) -> BalanceOf<T> {
+		if Dids::<T>::contains_key(did) {
+			Zero::zero()
+		} else {
+			T::DidDeposit::get()
+		}
+	}
+
+	/// Reserve the deposit of `items` more entries of `did` from its controller,
+	/// along with the deposit of the DID itself if nothing was recorded for it
+	/// yet.
+	fn hold(did: &T::AccountId, items: u32) -> DispatchResult {
+		let amount = T::ItemDeposit::get()
+			.saturating_mul(items.into())
+			.saturating_add(Self::did_deposit(did));
+		T::Currency::reserve_named(&T::ReserveId::get(), &Self::controller_of(did), amount)?;
+		Deposits::<T>::mutate(did, |deposit| *deposit = deposit.saturating_add(amount));
+		Ok(())
+	}
+
+	/// Return the deposit of `items` entries removed from `did` to its
+	/// controller.
+	fn release(did: &T::AccountId, items: u32) {
+		let amount = Deposits::<T>::mutate(did, |deposit| {
+			let amount = T::ItemDeposit::get().saturating_mul(items.into()).min(*deposit);
+			*deposit = deposit.saturating_sub(amount);
+			amount
+		});
+		T::Currency::unreserve_named(&T::ReserveId::get(), &Self::controller_of(did), amount);
+	}
+
+	/// Identifier the next key added to `did` gets.
+	fn next_key(did: &T::AccountId) -> KeyId {
+		Dids::<T>::get(did).map_or(0, |info| info.next_key)
+	}
+
+	/// Record a change to the document of `did` at the current block, in which
+	/// key `added` was added.
+	fn touch(did: &T::AccountId, added: Option<KeyId>) {
+		let now = frame_system::Pallet::<T>::block_number();
+		Dids::<T>::mutate(did, |info| {
+			let info =
+				info.get_or_insert(DidInfo { next_key: 0, updated_at: now, deactivated: false });
+			info.updated_at = now;
+			if let Some(added) = added {
+				info.next_key = added.saturating_add(1);
+			}
+		});
+	}
+
+	/// Everything recorded for `did`, leaving out lapsed delegations.
+	pub fn document(did: T::AccountId) -> DidDocumentOf<T> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let info = Dids::<T>::get(&did);
+
+		DidDocument {
+			controller: Controllers::<T>::get(&did),
+			keys: Keys::<T>::get(&did).into_inner(),
+			services: Services::<T>::get(&did)
+				.into_iter()
+				.map(|s| Service {
+					id: s.id.into_inner(),
+					service_type: s.service_type.into_inner(),
+					endpoint: s.endpoint.into_inner(),
+				})
+				.collect(),
+			delegates: Delegates::<T>::get(&did)
+				.into_iter()
+				.filter(|d| d.expires_at.map_or(true, |at| at > now))
+				.collect(),
+			updated_at: info.as_ref().map(|info| info.updated_at),
+			deactivated: info.map_or(false, |info| info.deactivated),
+		}
+	}
+}
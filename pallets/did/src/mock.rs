@@ -0,0 +1,110 @@
+//! Test runtime of the DID pallet.
+
+use crate as pallet_did;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64, Everything, GenesisBuild},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Did: pallet_did::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const DidReserveId: [u8; 8] = *b"b3diddep";
+}
+
+/// Deposit of a DID with anything recorded for it.
+pub const DID_DEPOSIT: u64 = 10;
+/// Deposit of each key, service endpoint and delegate.
+pub const ITEM_DEPOSIT: u64 = 3;
+pub const MAX_KEYS: u32 = 2;
+
+impl pallet_did::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type ReserveId = DidReserveId;
+	type DidDeposit = ConstU64<DID_DEPOSIT>;
+	type ItemDeposit = ConstU64<ITEM_DEPOSIT>;
+	type MaxKeys = ConstU32<MAX_KEYS>;
+	type MaxServices = ConstU32<2>;
+	type MaxDelegates = ConstU32<2>;
+	type MaxServiceFieldLength = ConstU32<32>;
+	type WeightInfo = ();
+}
+
+/// The DID under test, controlled by its own account at first.
+pub const DID: u64 = 1;
+/// A multisig account taking control of the DID.
+pub const MULTISIG: u64 = 2;
+pub const DELEGATE: u64 = 3;
+/// Cannot afford any deposit.
+pub const POOR: u64 = 4;
+pub const BALANCE: u64 = 100;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(DID, BALANCE), (MULTISIG, BALANCE), (DELEGATE, BALANCE), (POOR, 1)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,175 @@
+//! Tests of the DID pallet.
+
+use crate::{mock::*, DidPublicKey, Error, KeyPurpose};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult};
+
+const KEY: DidPublicKey = DidPublicKey::Sr25519([1; 32]);
+const ROTATED: DidPublicKey = DidPublicKey::Ed25519([2; 32]);
+
+fn add_key(who: u64, key: DidPublicKey) -> DispatchResult {
+	Did::add_key(Origin::signed(who), DID, key, KeyPurpose::AssertionMethod)
+}
+
+fn add_service(who: u64, id: &[u8]) -> DispatchResult {
+	Did::add_service(
+		Origin::signed(who),
+		DID,
+		id.to_vec(),
+		b"PermitPortal".to_vec(),
+		b"https://permits.example".to_vec(),
+	)
+}
+
+fn reserved(who: u64) -> u64 {
+	Balances::reserved_balance(who)
+}
+
+#[test]
+fn keys_are_added_rotated_and_revoked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(add_key(DID, KEY));
+		assert_noop!(add_key(DID, KEY), Error::<Test>::DuplicateKey);
+		assert_eq!(reserved(DID), DID_DEPOSIT + ITEM_DEPOSIT);
+
+		System::set_block_number(2);
+		assert_noop!(
+			Did::rotate_key(Origin::signed(DID), DID, 7, ROTATED),
+			Error::<Test>::UnknownKey
+		);
+		assert_ok!(Did::rotate_key(Origin::signed(DID), DID, 0, ROTATED));
+		let keys = Did::keys(DID);
+		assert_eq!(keys.len(), 1);
+		assert_eq!((keys[0].id, &keys[0].key), (1, &ROTATED));
+		assert_eq!((keys[0].purpose, keys[0].added_at), (KeyPurpose::AssertionMethod, 2));
+		// Rotation replaces the key, its deposit carries over.
+		assert_eq!(reserved(DID), DID_DEPOSIT + ITEM_DEPOSIT);
+
+		// Identifiers of rotated and revoked keys are not reused.
+		assert_ok!(add_key(DID, KEY));
+		assert_eq!(Did::keys(DID)[1].id, 2);
+		assert_noop!(add_key(DID, DidPublicKey::Sr25519([3; 32])), Error::<Test>::TooManyKeys);
+		assert_eq!(reserved(DID), DID_DEPOSIT + MAX_KEYS as u64 * ITEM_DEPOSIT);
+
+		assert_ok!(Did::revoke_key(Origin::signed(DID), DID, 1));
+		assert_noop!(Did::revoke_key(Origin::signed(DID), DID, 1), Error::<Test>::UnknownKey);
+		assert_eq!(reserved(DID), DID_DEPOSIT + ITEM_DEPOSIT);
+		assert_eq!(Did::did_info(DID).unwrap().next_key, 3);
+	});
+}
+
+#[test]
+fn entries_need_their_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Did::add_key(Origin::signed(POOR), POOR, KEY, KeyPurpose::Authentication),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert!(Did::did_info(POOR).is_none());
+
+		assert_ok!(add_service(DID, b"portal"));
+		assert_noop!(add_service(DID, b"portal"), Error::<Test>::ServiceExists);
+		assert_ok!(Did::add_delegate(Origin::signed(DID), DID, DELEGATE, None));
+		assert_eq!(reserved(DID), DID_DEPOSIT + 2 * ITEM_DEPOSIT);
+
+		assert_ok!(Did::remove_service(Origin::signed(DID), DID, b"portal".to_vec()));
+		assert_ok!(Did::remove_delegate(Origin::signed(DID), DID, DELEGATE));
+		assert_eq!(reserved(DID), DID_DEPOSIT);
+		assert_eq!(Did::deposit(DID), DID_DEPOSIT);
+	});
+}
+
+#[test]
+fn lapsed_delegations_return_their_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Did::add_delegate(Origin::signed(DID), DID, DELEGATE, Some(1)),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_ok!(Did::add_delegate(Origin::signed(DID), DID, DELEGATE, Some(5)));
+		assert_ok!(Did::add_delegate(Origin::signed(DID), DID, MULTISIG, None));
+		assert_noop!(
+			Did::add_delegate(Origin::signed(DID), DID, POOR, None),
+			Error::<Test>::TooManyDelegates
+		);
+
+		System::set_block_number(5);
+		assert_eq!(Did::document(DID).delegates.len(), 1);
+		assert_ok!(Did::add_delegate(Origin::signed(DID), DID, POOR, None));
+		assert_eq!(Did::delegates(DID).len(), 2);
+		assert_eq!(reserved(DID), DID_DEPOSIT + 2 * ITEM_DEPOSIT);
+	});
+}
+
+#[test]
+fn controllers_take_over_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(add_key(DID, KEY));
+		assert_noop!(
+			Did::set_controller(Origin::signed(MULTISIG), DID, Some(MULTISIG)),
+			Error::<Test>::NotController
+		);
+		assert_noop!(
+			Did::set_controller(Origin::signed(DID), DID, Some(POOR)),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(Did::set_controller(Origin::signed(DID), DID, Some(MULTISIG)));
+		assert_eq!(Did::controller_of(&DID), MULTISIG);
+		assert_eq!(reserved(DID), 0);
+		assert_eq!(reserved(MULTISIG), DID_DEPOSIT + ITEM_DEPOSIT);
+
+		// Only the new controller changes the document now, at its own expense.
+		assert_noop!(add_service(DID, b"portal"), Error::<Test>::NotController);
+		assert_ok!(add_service(MULTISIG, b"portal"));
+		assert_eq!(reserved(MULTISIG), DID_DEPOSIT + 2 * ITEM_DEPOSIT);
+
+		assert_ok!(Did::set_controller(Origin::signed(MULTISIG), DID, None));
+		assert_eq!(Did::controller(DID), None);
+		assert_eq!(reserved(MULTISIG), 0);
+		assert_eq!(reserved(DID), DID_DEPOSIT + 2 * ITEM_DEPOSIT);
+	});
+}
+
+#[test]
+fn handing_over_a_new_did_reserves_its_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Did::set_controller(Origin::signed(DID), DID, Some(MULTISIG)));
+		assert_eq!(reserved(MULTISIG), DID_DEPOSIT);
+		assert_eq!(reserved(DID), 0);
+
+		// Naming the DID itself hands control back.
+		assert_ok!(Did::set_controller(Origin::signed(MULTISIG), DID, Some(DID)));
+		assert_eq!(Did::controller(DID), None);
+		assert_eq!(reserved(DID), DID_DEPOSIT);
+	});
+}
+
+#[test]
+fn deactivation_clears_the_document_and_returns_the_deposits() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(add_key(DID, KEY));
+		assert_ok!(add_service(DID, b"portal"));
+		assert_ok!(Did::add_delegate(Origin::signed(DID), DID, DELEGATE, None));
+		assert_ok!(Did::set_controller(Origin::signed(DID), DID, Some(MULTISIG)));
+
+		assert_noop!(Did::deactivate(Origin::signed(DID), DID), Error::<Test>::NotController);
+		assert_ok!(Did::deactivate(Origin::signed(MULTISIG), DID));
+		assert_eq!(reserved(MULTISIG), 0);
+		assert_eq!(Balances::free_balance(MULTISIG), BALANCE);
+		assert_eq!(Did::deposit(DID), 0);
+
+		let document = Did::document(DID);
+		assert!(document.deactivated);
+		assert_eq!(document.controller, None);
+		assert!(document.keys.is_empty() && document.services.is_empty());
+		assert!(document.delegates.is_empty());
+
+		// Deactivation is for good, whoever controlled the DID.
+		assert_noop!(add_key(DID, KEY), Error::<Test>::DidDeactivated);
+		assert_noop!(add_key(MULTISIG, KEY), Error::<Test>::DidDeactivated);
+		assert_noop!(
+			Did::set_controller(Origin::signed(DID), DID, None),
+			Error::<Test>::DidDeactivated
+		);
+	});
+}
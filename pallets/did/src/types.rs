@@ -0,0 +1,84 @@
+//! Records kept by the DID pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Identifier of a verification key within its DID document, referenced as the
+/// fragment `#key-<id>`.
+pub type KeyId = u32;
+
+/// Public part of a verification key.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum DidPublicKey {
+	Sr25519([u8; 32]),
+	Ed25519([u8; 32]),
+	/// Compressed secp256k1 public key.
+	Ecdsa([u8; 33]),
+}
+
+/// Verification relationship a key is added for.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KeyPurpose {
+	/// Proving control of the DID, e.g. when logging in.
+	Authentication,
+	/// Signing claims such as credentials.
+	AssertionMethod,
+	/// Encrypting messages to the DID subject.
+	KeyAgreement,
+	/// Invoking capabilities granted to the DID.
+	CapabilityInvocation,
+}
+
+/// A verification key of a DID.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DidKey<BlockNumber> {
+	pub id: KeyId,
+	pub key: DidPublicKey,
+	pub purpose: KeyPurpose,
+	pub added_at: BlockNumber,
+}
+
+/// A service endpoint of a DID, e.g. a firm's permit portal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Service<Text> {
+	/// Fragment identifying the service within the document.
+	pub id: Text,
+	pub service_type: Text,
+	/// URL of the service.
+	pub endpoint: Text,
+}
+
+/// An account the DID delegates its capabilities to, e.g. a firm's staff.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Delegate<AccountId, BlockNumber> {
+	pub account: AccountId,
+	/// Block the delegation lapses at, if any.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// Bookkeeping of a DID document which has been changed at least once.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DidInfo<BlockNumber> {
+	/// Identifier given to the next key added.
+	pub next_key: KeyId,
+	/// Block of the last change to the document.
+	pub updated_at: BlockNumber,
+	/// A deactivated DID keeps resolving, to an empty document.
+	pub deactivated: bool,
+}
+
+/// Everything recorded for a DID, as returned to resolvers.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DidDocument<AccountId, BlockNumber, Text> {
+	/// Account controlling the DID, if not the DID's own account.
+	pub controller: Option<AccountId>,
+	pub keys: Vec<DidKey<BlockNumber>>,
+	pub services: Vec<Service<Text>>,
+	/// Delegates whose delegation has not lapsed.
+	pub delegates: Vec<Delegate<AccountId, BlockNumber>>,
+	/// Block of the last change, `None` if the document was never changed.
+	pub updated_at: Option<BlockNumber>,
+	pub deactivated: bool,
+}
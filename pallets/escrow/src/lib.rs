@@ -0,0 +1,395 @@
+//! # Escrow Pallet
+//!
+//! Milestone payments for engineering services, held in escrow until the work
+//! is delivered.
+//!
+//! A client funds a milestone for a licensed engineer, reserving the amount
+//! under the pallet's reserve identifier. The engineer delivers by sealing the
+//! deliverable under the license named in the milestone, after the milestone
+//! was funded, and the funds go to them once the client approves it. A
+//! document is delivered for one milestone only. The engineer may also hand the funds back
+//! to the client at any time.
+//!
+//! Either party escalates a stalled milestone to its arbiter, the board of the
+//! engineer's license or the arbitrator panel: the client once the deliverable
+//! is overdue, or when it disputes the deliverable, and the engineer once the
+//! client left a deliverable unapproved for `ApprovalPeriod`. The arbiter
+//! settles the dispute by splitting the funds between them; a board does so
+//! with the approval it requires for `ResolveEscrowDispute`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{BalanceStatus, Currency, NamedReservableCurrency},
+};
+use pallet_licensure::{LicenseNumberOf, Licenses, LicensureAction};
+use sp_std::prelude::*;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type ReserveIdentifierOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+	<T as frame_system::Config>::AccountId,
+>>::ReserveIdentifier;
+pub type MilestoneOf<T> = Milestone<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+	LicenseNumberOf<T>,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_licensure::{LicenseStatus, Seals};
+	use sp_runtime::traits::{Saturating, StaticLookup, Zero};
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: pallet_licensure::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency milestones are paid in.
+		type Currency: NamedReservableCurrency<Self::AccountId>;
+
+		/// Identifier the funds of milestones are reserved under.
+		#[pallet::constant]
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// Origin of the arbitrator panel settling disputes.
+		type ArbitratorOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Blocks the client has to approve a deliverable before the engineer
+		/// may escalate.
+		#[pallet::constant]
+		type ApprovalPeriod: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Identifier given to the next funded milestone.
+	#[pallet::storage]
+	pub type NextMilestoneId<T: Config> = StorageValue<_, MilestoneId, ValueQuery>;
+
+	/// Milestones funded and not settled yet.
+	#[pallet::storage]
+	#[pallet::getter(fn milestone)]
+	pub type Milestones<T: Config> =
+		StorageMap<_, Twox64Concat, MilestoneId, MilestoneOf<T>, OptionQuery>;
+
+	/// Milestone each document was delivered for, kept once it is settled so
+	/// that the document is not delivered again.
+	#[pallet::storage]
+	#[pallet::getter(fn delivered_for)]
+	pub type Deliverables<T: Config> = StorageMap<_, Identity, T::Hash, MilestoneId, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A client funded a milestone.
+		MilestoneFunded {
+			milestone: MilestoneId,
+			client: T::AccountId,
+			engineer: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The engineer delivered a sealed document.
+		MilestoneDelivered { milestone: MilestoneId, deliverable: T::Hash },
+		/// The client approved the deliverable and the funds went to the
+		/// engineer.
+		MilestoneReleased { milestone: MilestoneId, amount: BalanceOf<T> },
+		/// The engineer handed the funds back to the client.
+		MilestoneRefunded { milestone: MilestoneId, amount: BalanceOf<T> },
+		/// A party escalated the milestone to its arbiter.
+		MilestoneDisputed { milestone: MilestoneId, by: T::AccountId },
+		/// The arbiter split the funds between the parties.
+		DisputeResolved {
+			milestone: MilestoneId,
+			to_engineer: BalanceOf<T>,
+			to_client: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Milestones need a positive amount.
+		ZeroAmount,
+		/// The deadline is not in the future.
+		DeadlinePassed,
+		/// No license with this number exists.
+		UnknownLicense,
+		/// The engineer does not hold the license.
+		NotLicenseHolder,
+		/// The license is not in good standing.
+		LicenseNotActive,
+		/// No unsettled milestone with this identifier exists.
+		UnknownMilestone,
+		/// Only the client of the milestone may do this.
+		NotClient,
+		/// Only the engineer of the milestone may do this.
+		NotEngineer,
+		/// Only the client or the engineer of the milestone may do this.
+		NotParty,
+		/// The milestone is not waiting for its deliverable.
+		NotFunded,
+		/// The milestone has no deliverable waiting for approval.
+		NotDelivered,
+		/// The milestone is not disputed.
+		NotDisputed,
+		/// The milestone is already disputed.
+		AlreadyDisputed,
+		/// The document has not been sealed.
+		NotSealed,
+		/// The document was not sealed by the engineer under the milestone's
+		/// license.
+		SealMismatch,
+		/// The document was sealed before the milestone was funded.
+		SealedBeforeFunding,
+		/// The document was delivered for a milestone already.
+		AlreadyDelivered,
+		/// The milestone cannot be escalated yet.
+		TooEarly,
+		/// The origin is not the arbiter of the milestone.
+		NotArbiter,
+		/// The engineer's share exceeds the escrowed amount.
+		ExceedsAmount,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Fund a milestone of `amount` for `engineer`, to be delivered under
+		/// license `number` by block `deadline`. `terms` is the hash of the
+		/// agreed scope, and `arbiter` settles disputes.
+		///
+		/// The amount is reserved from the origin, the client.
+		#[pallet::weight(<T as Config>::WeightInfo::fund())]
+		pub fn fund(
+			origin: OriginFor<T>,
+			engineer: <T::Lookup as StaticLookup>::Source,
+			number: Vec<u8>,
+			#[pallet::compact] amount: BalanceOf<T>,
+			terms: T::Hash,
+			deadline: T::BlockNumber,
+			arbiter: Arbiter,
+		) -> DispatchResult {
+			let client = ensure_signed(origin)?;
+			let engineer = T::Lookup::lookup(engineer)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(deadline > now, Error::<T>::DeadlinePassed);
+			let number: LicenseNumberOf<T> =
+				number.try_into().map_err(|_| Error::<T>::UnknownLicense)?;
+			let license = Licenses::<T>::get(&number).ok_or(Error::<T>::UnknownLicense)?;
+			ensure!(license.holder == engineer, Error::<T>::NotLicenseHolder);
+			ensure!(license.status_at(&now) == LicenseStatus::Active, Error::<T>::LicenseNotActive);
+
+			T::Currency::reserve_named(&T::ReserveId::get(), &client, amount)?;
+			let milestone = NextMilestoneId::<T>::mutate(|id| {
+				let milestone = *id;
+				*id = id.saturating_add(1);
+				milestone
+			});
+			Milestones::<T>::insert(
+				milestone,
+				Milestone {
+					client: client.clone(),
+					engineer: engineer.clone(),
+					license: number,
+					amount,
+					terms,
+					deadline,
+					funded_at: now,
+					arbiter,
+					status: MilestoneStatus::Funded,
+					deliverable: None,
+					delivered_at: None,
+				},
+			);
+
+			Self::deposit_event(Event::MilestoneFunded { milestone, client, engineer, amount });
+			Ok(())
+		}
+
+		/// Deliver `document`, sealed under the license of `milestone` since it
+		/// was funded, for the client's approval. The document may not have
+		/// been delivered for another milestone.
+		///
+		/// The origin must be the engineer of the milestone.
+		#[pallet::weight(<T as Config>::WeightInfo::deliver())]
+		pub fn deliver(
+			origin: OriginFor<T>,
+			milestone: MilestoneId,
+			document: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Milestones::<T>::get(milestone).ok_or(Error::<T>::UnknownMilestone)?;
+			ensure!(info.engineer == who, Error::<T>::NotEngineer);
+			ensure!(info.status == MilestoneStatus::Funded, Error::<T>::NotFunded);
+			let seal = Seals::<T>::get(document).ok_or(Error::<T>::NotSealed)?;
+			ensure!(seal.signer == who && seal.license == info.license, Error::<T>::SealMismatch);
+			ensure!(seal.sealed_at >= info.funded_at, Error::<T>::SealedBeforeFunding);
+			ensure!(!Deliverables::<T>::contains_key(document), Error::<T>::AlreadyDelivered);
+
+			Deliverables::<T>::insert(document, milestone);
+			info.status = MilestoneStatus::Delivered;
+			info.deliverable = Some(document);
+			info.delivered_at = Some(frame_system::Pallet::<T>::block_number());
+			Milestones::<T>::insert(milestone, info);
+
+			Self::deposit_event(Event::MilestoneDelivered { milestone, deliverable: document });
+			Ok(())
+		}
+
+		/// Approve the deliverable of `milestone`, releasing the funds to the
+		/// engineer.
+		///
+		/// The origin must be the client of the milestone.
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		pub fn approve(origin: OriginFor<T>, milestone: MilestoneId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = Milestones::<T>::get(milestone).ok_or(Error::<T>::UnknownMilestone)?;
+			ensure!(info.client == who, Error::<T>::NotClient);
+			ensure!(info.status == MilestoneStatus::Delivered, Error::<T>::NotDelivered);
+
+			Self::pay(&info, info.amount)?;
+			Milestones::<T>::remove(milestone);
+
+			Self::deposit_event(Event::MilestoneReleased { milestone, amount: info.amount });
+			Ok(())
+		}
+
+		/// Hand the funds of `milestone` back to the client, at any stage.
+		///
+		/// The origin must be the engineer of the milestone.
+		#[pallet::weight(<T as Config>::WeightInfo::refund())]
+		pub fn refund(origin: OriginFor<T>, milestone: MilestoneId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = Milestones::<T>::get(milestone).ok_or(Error::<T>::UnknownMilestone)?;
+			ensure!(info.engineer == who, Error::<T>::NotEngineer);
+
+			T::Currency::unreserve_named(&T::ReserveId::get(), &info.client, info.amount);
+			Milestones::<T>::remove(milestone);
+
+			Self::deposit_event(Event::MilestoneRefunded { milestone, amount: info.amount });
+			Ok(())
+		}
+
+		/// Escalate `milestone` to its arbiter.
+		///
+		/// The client may escalate once the deliverable is overdue, or to
+		/// dispute a deliverable. The engineer may escalate once a deliverable
+		/// went unapproved for `ApprovalPeriod`.
+		#[pallet::weight(<T as Config>::WeightInfo::dispute())]
+		pub fn dispute(origin: OriginFor<T>, milestone: MilestoneId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Milestones::<T>::get(milestone).ok_or(Error::<T>::UnknownMilestone)?;
+			ensure!(info.status != MilestoneStatus::Disputed, Error::<T>::AlreadyDisputed);
+			let now = frame_system::Pallet::<T>::block_number();
+			if who == info.client {
+				if info.status == MilestoneStatus::Funded {
+					ensure!(now >= info.deadline, Error::<T>::TooEarly);
+				}
+			} else if who == info.engineer {
+				ensure!(info.status == MilestoneStatus::Delivered, Error::<T>::NotDelivered);
+				let delivered_at = info.delivered_at.unwrap_or(now);
+				ensure!(
+					now >= delivered_at.saturating_add(T::ApprovalPeriod::get()),
+					Error::<T>::TooEarly
+				);
+			} else {
+				return Err(Error::<T>::NotParty.into())
+			}
+
+			info.status = MilestoneStatus::Disputed;
+			Milestones::<T>::insert(milestone, info);
+
+			Self::deposit_event(Event::MilestoneDisputed { milestone, by: who });
+			Ok(())
+		}
+
+		/// Settle the dispute over `milestone`, paying `to_engineer` of the
+		/// funds to the engineer and the rest back to the client.
+		///
+		/// The origin must be the arbiter of the milestone: the board of the
+		/// engineer's license, with the approval it requires for
+		/// `ResolveEscrowDispute`, or the arbitrator panel.
+		#[pallet::weight(<T as Config>::WeightInfo::resolve())]
+		pub fn resolve(
+			origin: OriginFor<T>,
+			milestone: MilestoneId,
+			#[pallet::compact] to_engineer: BalanceOf<T>,
+		) -> DispatchResult {
+			let info = Milestones::<T>::get(milestone).ok_or(Error::<T>::UnknownMilestone)?;
+			Self::ensure_arbiter(origin, &info)?;
+			ensure!(info.status == MilestoneStatus::Disputed, Error::<T>::NotDisputed);
+			ensure!(to_engineer <= info.amount, Error::<T>::ExceedsAmount);
+
+			let to_client = info.amount.saturating_sub(to_engineer);
+			if !to_engineer.is_zero() {
+				Self::pay(&info, to_engineer)?;
+			}
+			T::Currency::unreserve_named(&T::ReserveId::get(), &info.client, to_client);
+			Milestones::<T>::remove(milestone);
+
+			Self::deposit_event(Event::DisputeResolved { milestone, to_engineer, to_client });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure `origin` is the arbiter of `milestone`.
+	fn ensure_arbiter(origin: T::Origin, milestone: &MilestoneOf<T>) -> DispatchResult {
+		match milestone.arbiter {
+			Arbiter::Board => {
+				let license =
+					Licenses::<T>::get(&milestone.license).ok_or(Error::<T>::UnknownLicense)?;
+				let board = pallet_licensure::Pallet::<T>::ensure_board(
+					origin,
+					LicensureAction::ResolveEscrowDispute,
+				)?;
+				ensure!(board == license.board, Error::<T>::NotArbiter);
+			},
+			Arbiter::Panel => {
+				T::ArbitratorOrigin::ensure_origin(origin)?;
+			},
+		}
+		Ok(())
+	}
+
+	/// Pay `amount` of the funds of `milestone`, reserved from the client, to
+	/// the engineer.
+	fn pay(milestone: &MilestoneOf<T>, amount: BalanceOf<T>) -> DispatchResult {
+		// Whatever part of the funds has been slashed from the client since
+		// they were reserved is lost to the engineer.
+		T::Currency::repatriate_reserved_named(
+			&T::ReserveId::get(),
+			&milestone.client,
+			&milestone.engineer,
+			amount,
+			BalanceStatus::Free,
+		)?;
+		Ok(())
+	}
+}
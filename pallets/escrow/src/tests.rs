@@ -0,0 +1,183 @@
+//! Tests of the escrow pallet.
+
+use crate::{mock::*, Arbiter, Error, MilestoneStatus, NextMilestoneId};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+use pallet_licensure::{ApprovalRule, LicensureAction};
+use sp_core::H256;
+
+const TERMS: H256 = H256([0xee; 32]);
+
+fn fund(arbiter: Arbiter) -> u32 {
+	let milestone = NextMilestoneId::<Test>::get();
+	assert_ok!(Escrow::fund(
+		Origin::signed(CLIENT),
+		ENGINEER,
+		b"PE-1".to_vec(),
+		100,
+		TERMS,
+		100,
+		arbiter,
+	));
+	milestone
+}
+
+fn seal(document: H256) {
+	assert_ok!(Licensure::affix_seal(Origin::signed(ENGINEER), b"PE-1".to_vec(), document));
+}
+
+fn delivered(arbiter: Arbiter) -> u32 {
+	let milestone = fund(arbiter);
+	let document = H256::repeat_byte(milestone as u8 + 1);
+	seal(document);
+	assert_ok!(Escrow::deliver(Origin::signed(ENGINEER), milestone, document));
+	milestone
+}
+
+fn disputed(arbiter: Arbiter) -> u32 {
+	let milestone = delivered(arbiter);
+	assert_ok!(Escrow::dispute(Origin::signed(CLIENT), milestone));
+	milestone
+}
+
+#[test]
+fn funds_are_released_on_approval() {
+	new_test_ext().execute_with(|| {
+		let milestone = delivered(Arbiter::Board);
+		assert_eq!(Balances::reserved_balance(CLIENT), 100);
+		assert_eq!(Escrow::milestone(milestone).unwrap().status, MilestoneStatus::Delivered);
+
+		assert_noop!(
+			Escrow::approve(Origin::signed(ENGINEER), milestone),
+			Error::<Test>::NotClient
+		);
+		assert_ok!(Escrow::approve(Origin::signed(CLIENT), milestone));
+		assert_eq!(Balances::reserved_balance(CLIENT), 0);
+		assert_eq!(Balances::free_balance(CLIENT), 900);
+		assert_eq!(Balances::free_balance(ENGINEER), 110);
+		assert!(Escrow::milestone(milestone).is_none());
+	});
+}
+
+#[test]
+fn deliverables_must_be_sealed_under_the_license() {
+	new_test_ext().execute_with(|| {
+		let milestone = fund(Arbiter::Board);
+		let document = H256::repeat_byte(1);
+
+		assert_noop!(
+			Escrow::deliver(Origin::signed(ENGINEER), milestone, document),
+			Error::<Test>::NotSealed
+		);
+		assert_noop!(
+			Escrow::deliver(Origin::signed(CLIENT), milestone, document),
+			Error::<Test>::NotEngineer
+		);
+	});
+}
+
+#[test]
+fn deliverables_sealed_before_funding_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let document = H256::repeat_byte(1);
+		seal(document);
+		System::set_block_number(2);
+		let milestone = fund(Arbiter::Board);
+
+		assert_noop!(
+			Escrow::deliver(Origin::signed(ENGINEER), milestone, document),
+			Error::<Test>::SealedBeforeFunding
+		);
+	});
+}
+
+#[test]
+fn deliverables_are_not_reused_across_milestones() {
+	new_test_ext().execute_with(|| {
+		let first = fund(Arbiter::Board);
+		let second = fund(Arbiter::Board);
+		let document = H256::repeat_byte(1);
+		seal(document);
+
+		assert_ok!(Escrow::deliver(Origin::signed(ENGINEER), first, document));
+		assert_eq!(Escrow::delivered_for(document), Some(first));
+		assert_noop!(
+			Escrow::deliver(Origin::signed(ENGINEER), second, document),
+			Error::<Test>::AlreadyDelivered
+		);
+
+		// Settling the first milestone does not free the document.
+		assert_ok!(Escrow::approve(Origin::signed(CLIENT), first));
+		assert_noop!(
+			Escrow::deliver(Origin::signed(ENGINEER), second, document),
+			Error::<Test>::AlreadyDelivered
+		);
+	});
+}
+
+#[test]
+fn engineers_escalate_only_after_the_approval_period() {
+	new_test_ext().execute_with(|| {
+		let milestone = delivered(Arbiter::Board);
+
+		System::set_block_number(APPROVAL_PERIOD);
+		assert_noop!(Escrow::dispute(Origin::signed(ENGINEER), milestone), Error::<Test>::TooEarly);
+		System::set_block_number(1 + APPROVAL_PERIOD);
+		assert_ok!(Escrow::dispute(Origin::signed(ENGINEER), milestone));
+		assert_eq!(Escrow::milestone(milestone).unwrap().status, MilestoneStatus::Disputed);
+	});
+}
+
+#[test]
+fn boards_resolve_with_the_approval_their_rule_requires() {
+	new_test_ext().execute_with(|| {
+		let milestone = disputed(Arbiter::Board);
+
+		assert_noop!(
+			Escrow::resolve(motion(1, 2), milestone, 60),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_noop!(
+			Escrow::resolve(executive_director(1), milestone, 60),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_noop!(Escrow::resolve(motion(2, MEMBERS), milestone, 60), Error::<Test>::NotArbiter);
+		assert_noop!(Escrow::resolve(Origin::root(), milestone, 60), DispatchError::BadOrigin);
+
+		assert_ok!(Escrow::resolve(motion(1, 3), milestone, 60));
+		assert_eq!(Balances::free_balance(ENGINEER), 70);
+		assert_eq!(Balances::free_balance(CLIENT), 940);
+		assert_eq!(Balances::reserved_balance(CLIENT), 0);
+	});
+}
+
+#[test]
+fn boards_may_leave_disputes_to_their_executive_director() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Licensure::set_action_rule(
+			Origin::root(),
+			1,
+			LicensureAction::ResolveEscrowDispute,
+			ApprovalRule::ExecutiveDirector,
+		));
+		let milestone = disputed(Arbiter::Board);
+
+		assert_noop!(
+			Escrow::resolve(motion(1, MEMBERS), milestone, 0),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_ok!(Escrow::resolve(executive_director(1), milestone, 0));
+		assert_eq!(Balances::free_balance(CLIENT), 1_000);
+	});
+}
+
+#[test]
+fn the_panel_resolves_its_disputes() {
+	new_test_ext().execute_with(|| {
+		let milestone = disputed(Arbiter::Panel);
+
+		assert_noop!(Escrow::resolve(motion(1, MEMBERS), milestone, 100), DispatchError::BadOrigin);
+		assert_noop!(Escrow::resolve(Origin::root(), milestone, 101), Error::<Test>::ExceedsAmount);
+		assert_ok!(Escrow::resolve(Origin::root(), milestone, 100));
+		assert_eq!(Balances::free_balance(ENGINEER), 110);
+	});
+}
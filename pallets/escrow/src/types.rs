@@ -0,0 +1,50 @@
+//! Records kept by the escrow pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Identifier of an escrowed milestone.
+pub type MilestoneId = u32;
+
+/// Who settles the disputes of a milestone.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Arbiter {
+	/// The board which granted the engineer's license.
+	Board,
+	/// The runtime's arbitrator panel.
+	Panel,
+}
+
+/// Where an escrowed milestone stands.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MilestoneStatus {
+	/// Funded, waiting for the deliverable.
+	Funded,
+	/// Deliverable sealed, waiting for the client's approval.
+	Delivered,
+	/// Waiting for the arbiter to settle it.
+	Disputed,
+}
+
+/// A milestone of engineering services, funded by the client in escrow.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Milestone<AccountId, Balance, BlockNumber, Hash, LicenseNumber> {
+	pub client: AccountId,
+	pub engineer: AccountId,
+	/// License the engineer must seal the deliverable under.
+	pub license: LicenseNumber,
+	/// Amount reserved from the client.
+	pub amount: Balance,
+	/// Hash of the agreed scope of the milestone, kept off chain.
+	pub terms: Hash,
+	/// Block by which the deliverable is due.
+	pub deadline: BlockNumber,
+	/// Block the milestone was funded in. Deliverables must be sealed since.
+	pub funded_at: BlockNumber,
+	pub arbiter: Arbiter,
+	pub status: MilestoneStatus,
+	/// Sealed deliverable, once delivered.
+	pub deliverable: Option<Hash>,
+	pub delivered_at: Option<BlockNumber>,
+}
@@ -0,0 +1,66 @@
+//! Runtime API definition for the licensure pallet.
+//!
+//! Everything a permitting portal needs to know about a license or a board,
+//! without decoding raw storage.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+pub use pallet_licensure::{
+	DisciplinaryAction, DisciplinaryKind, Discipline, License, LicenseStatus, SealVerification,
+};
+
+/// An open motion in a board's collective.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BoardMotion<AccountId, BlockNumber, Hash> {
+	/// Hash of the proposed call.
+	pub hash: Hash,
+	/// Proposal index within the collective.
+	pub index: u32,
+	/// Number of approvals required.
+	pub threshold: u32,
+	pub ayes: Vec<AccountId>,
+	pub nays: Vec<AccountId>,
+	/// Block after which the motion may be closed.
+	pub end: BlockNumber,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to licenses, seals, disciplinary records and the boards
+	/// governing them.
+	pub trait LicensureApi<AccountId, BoardId, BlockNumber, Hash> where
+		AccountId: Codec,
+		BoardId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// License `number`, with its status as of this block.
+		fn license(number: Vec<u8>) -> Option<License<AccountId, BoardId, BlockNumber>>;
+
+		/// Licenses held by `account`, keyed by license number.
+		fn licenses_of(account: AccountId) -> Vec<(Vec<u8>, License<AccountId, BoardId, BlockNumber>)>;
+
+		/// Current members of `board`.
+		fn board_members(board: BoardId) -> Vec<AccountId>;
+
+		/// Motions pending in `board`'s collective.
+		fn board_motions(board: BoardId) -> Vec<BoardMotion<AccountId, BlockNumber, Hash>>;
+
+		/// Who sealed the document with hash `document`, if anyone.
+		fn verify_seal(document: Hash) -> Option<SealVerification<AccountId, BoardId, BlockNumber>>;
+
+		/// Disciplinary history of license `number`, oldest first.
+		fn disciplinary_history(number: Vec<u8>) -> Vec<DisciplinaryAction<BoardId, BlockNumber, Hash>>;
+
+		/// Key `board` currently signs verifiable credentials with, if any.
+		fn credential_key(board: BoardId) -> Option<[u8; 32]>;
+	}
+}
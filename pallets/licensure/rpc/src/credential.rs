@@ -0,0 +1,270 @@
+//! W3C verifiable credentials for licenses.
+//!
+//! A license credential states that the holder's DID holds a license from a
+//! board, as recorded on chain at the block the credential is anchored to. It
+//! is issued by the DID of the board's credential key and signed with that key:
+//! the `Build3Sr25519Signature` proof is an sr25519 signature over the
+//! canonical JSON of the credential, including its proof but without
+//! `proofValue`. Canonical JSON has no whitespace and sorts object keys.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use sp_core::{
+	bytes::{from_hex, to_hex},
+	sr25519, Pair,
+};
+use std::time::SystemTime;
+
+/// Key type boards' credential keys are stored under in the node keystore.
+pub const CREDENTIAL_KEY_TYPE: sp_core::crypto::KeyTypeId = sp_core::crypto::KeyTypeId(*b"cred");
+
+/// Type of the proofs of license credentials.
+pub const PROOF_TYPE: &str = "Build3Sr25519Signature";
+
+/// Credential type of license credentials, next to `VerifiableCredential`.
+pub const CREDENTIAL_TYPE: &str = "EngineeringLicenseCredential";
+
+/// Type of the `credentialStatus` of license credentials: the status of the
+/// license on chain.
+pub const STATUS_TYPE: &str = "Build3LicenseStatus";
+
+/// The license a credential is about, as recorded on chain.
+pub struct LicenseClaim {
+	pub number: String,
+	pub holder_did: String,
+	pub board: Value,
+	pub discipline: Value,
+	pub issued_at: Value,
+	pub expires_at: Value,
+}
+
+/// Block of the chain a credential is anchored to.
+pub struct Anchor {
+	pub block_hash: Value,
+	pub block_number: Value,
+}
+
+/// Identifier of license `number`, e.g. the `credentialStatus` of its
+/// credentials.
+pub fn license_id(number: &str) -> String {
+	format!("urn:build3:license:{}", number)
+}
+
+/// License credential issued by `issuer_did`, without its `proofValue`.
+pub fn unsigned(issuer_did: &str, claim: LicenseClaim, anchor: Anchor) -> Value {
+	let issued = rfc3339(SystemTime::now());
+	let license_id = license_id(&claim.number);
+
+	json!({
+		"@context": ["https://www.w3.org/2018/credentials/v1"],
+		"id": license_id,
+		"type": ["VerifiableCredential", CREDENTIAL_TYPE],
+		"issuer": issuer_did,
+		"issuanceDate": issued,
+		"credentialSubject": {
+			"id": claim.holder_did,
+			"license": {
+				"number": claim.number,
+				"board": claim.board,
+				"discipline": claim.discipline,
+				"issuedAt": claim.issued_at,
+				"expiresAt": claim.expires_at,
+			},
+		},
+		"credentialStatus": {
+			"id": license_id,
+			"type": STATUS_TYPE,
+			"blockHash": anchor.block_hash,
+			"blockNumber": anchor.block_number,
+		},
+		"proof": {
+			"type": PROOF_TYPE,
+			"created": issued,
+			"verificationMethod": crate::did::credential_key_method(issuer_did),
+			"proofPurpose": "assertionMethod",
+		},
+	})
+}
+
+/// Bytes the proof of `credential` signs: its canonical JSON without
+/// `proofValue`.
+pub fn signing_payload(credential: &Value) -> Vec<u8> {
+	let mut credential = credential.clone();
+	if let Some(proof) = credential.get_mut("proof").and_then(Value::as_object_mut) {
+		proof.remove("proofValue");
+	}
+	let mut payload = Vec::new();
+	canonical(&credential, &mut payload);
+	payload
+}
+
+/// Set the `proofValue` of `credential` to `signature`.
+pub fn set_proof_value(credential: &mut Value, signature: &[u8]) {
+	credential["proof"]["proofValue"] = Value::String(to_hex(signature, false));
+}
+
+/// The signature in the `proofValue` of `credential`, if it holds one.
+pub fn proof_value(credential: &Value) -> Option<sr25519::Signature> {
+	credential["proof"]["proofValue"]
+		.as_str()
+		.and_then(|proof| proof.strip_prefix("0x"))
+		.and_then(|hex| from_hex(hex).ok())
+		.and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+		.map(sr25519::Signature::from_raw)
+}
+
+/// Whether `signature` is the proof of `credential` by `key`, which must be
+/// the issuer of the credential.
+pub fn signed_by(
+	credential: &Value,
+	signature: &sr25519::Signature,
+	key: &sr25519::Public,
+) -> bool {
+	let did = crate::did::did_of(key);
+	credential["issuer"].as_str() == Some(did.as_str()) &&
+		credential["proof"]["verificationMethod"].as_str() ==
+			Some(crate::did::credential_key_method(&did).as_str()) &&
+		sr25519::Pair::verify(signature, signing_payload(credential), key)
+}
+
+/// Write `value` as JSON without whitespace, with object keys sorted.
+fn canonical(value: &Value, out: &mut Vec<u8>) {
+	match value {
+		Value::Array(items) => {
+			out.push(b'[');
+			for (i, item) in items.iter().enumerate() {
+				if i > 0 {
+					out.push(b',');
+				}
+				canonical(item, out);
+			}
+			out.push(b']');
+		},
+		Value::Object(fields) => {
+			let mut fields: Vec<_> = fields.iter().collect();
+			fields.sort_by(|a, b| a.0.cmp(b.0));
+			out.push(b'{');
+			for (i, (key, field)) in fields.into_iter().enumerate() {
+				if i > 0 {
+					out.push(b',');
+				}
+				canonical(&Value::String(key.clone()), out);
+				out.push(b':');
+				canonical(field, out);
+			}
+			out.push(b'}');
+		},
+		scalar => out.extend_from_slice(scalar.to_string().as_bytes()),
+	}
+}
+
+/// `time` as an RFC 3339 UTC date, to the second.
+fn rfc3339(time: SystemTime) -> String {
+	DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::time::Duration;
+
+	fn canonical_json(value: Value) -> String {
+		let mut out = Vec::new();
+		canonical(&value, &mut out);
+		String::from_utf8(out).unwrap()
+	}
+
+	fn at(secs: u64) -> String {
+		rfc3339(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+	}
+
+	fn signed(pair: &sr25519::Pair) -> Value {
+		let claim = LicenseClaim {
+			number: "PE-1".into(),
+			holder_did: "did:build3:holder".into(),
+			board: json!("Virginia"),
+			discipline: json!("Civil"),
+			issued_at: json!(1),
+			expires_at: json!(100),
+		};
+		let anchor = Anchor { block_hash: json!("0x01"), block_number: json!(7) };
+		let mut credential = unsigned(&crate::did::did_of(&pair.public()), claim, anchor);
+		let signature = pair.sign(&signing_payload(&credential));
+		set_proof_value(&mut credential, &signature.0);
+		credential
+	}
+
+	fn verifies(credential: &Value, key: &sr25519::Public) -> bool {
+		proof_value(credential).map_or(false, |signature| signed_by(credential, &signature, key))
+	}
+
+	#[test]
+	fn canonical_json_sorts_keys_bytewise() {
+		assert_eq!(
+			canonical_json(json!({
+				"b": 1,
+				"a": { "d": [true, null, 2.5], "c": -3 },
+				"B": [],
+				"@context": {},
+				"é": u64::MAX,
+			})),
+			r#"{"@context":{},"B":[],"a":{"c":-3,"d":[true,null,2.5]},"b":1,"é":18446744073709551615}"#
+		);
+	}
+
+	#[test]
+	fn canonical_json_escapes_strings() {
+		assert_eq!(
+			canonical_json(json!({ "quote\"key": "line\nbreak\ttab\\ \u{1} é" })),
+			r#"{"quote\"key":"line\nbreak\ttab\\ \u0001 é"}"#
+		);
+	}
+
+	#[test]
+	fn the_signing_payload_leaves_only_the_proof_value_out() {
+		let credential = json!({
+			"issuer": "did:build3:board",
+			"proof": { "type": PROOF_TYPE, "proofValue": "0x00" },
+		});
+		assert_eq!(
+			String::from_utf8(signing_payload(&credential)).unwrap(),
+			r#"{"issuer":"did:build3:board","proof":{"type":"Build3Sr25519Signature"}}"#
+		);
+	}
+
+	#[test]
+	fn dates_are_rfc3339_to_the_second() {
+		assert_eq!(at(0), "1970-01-01T00:00:00Z");
+		assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+		assert_eq!(at(1_709_251_199), "2024-02-29T23:59:59Z");
+		assert_eq!(at(4_107_542_400), "2100-03-01T00:00:00Z");
+	}
+
+	#[test]
+	fn signed_credentials_verify_against_the_issuer_key() {
+		let issuer = sr25519::Pair::from_seed(&[1; 32]);
+		let other = sr25519::Pair::from_seed(&[2; 32]);
+		let credential = signed(&issuer);
+
+		assert!(verifies(&credential, &issuer.public()));
+		assert!(!verifies(&credential, &other.public()));
+
+		// Serializing and parsing back keeps the proof valid.
+		let parsed: Value = serde_json::from_str(&credential.to_string()).unwrap();
+		assert!(verifies(&parsed, &issuer.public()));
+
+		let mut tampered = credential.clone();
+		tampered["credentialSubject"]["license"]["discipline"] = json!("Structural");
+		assert!(!verifies(&tampered, &issuer.public()));
+
+		// A valid signature does not make another key's DID the issuer.
+		let mut reissued = signed(&other);
+		let signature = proof_value(&credential).unwrap();
+		set_proof_value(&mut reissued, &signature.0);
+		assert!(!verifies(&reissued, &issuer.public()));
+
+		let mut truncated = credential;
+		truncated["proof"]["proofValue"] = json!("0x1234");
+		assert!(proof_value(&truncated).is_none());
+	}
+}
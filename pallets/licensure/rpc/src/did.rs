@@ -0,0 +1,31 @@
+//! The `did:build3` DID method.
+//!
+//! Every Build3 account is a DID: `did:build3:<ss58>`, where `<ss58>` is the
+//! SS58 address of the account. The DID is controlled by whoever controls the
+//! account, a key pair or a multisig, and is never registered separately.
+//! Boards sign credentials as the DID of their credential key, with the
+//! verification method `<did>#credential-key`.
+
+use sp_core::crypto::Ss58Codec;
+
+/// Prefix shared by every `did:build3` DID.
+pub const DID_PREFIX: &str = "did:build3:";
+
+/// Fragment of the verification method boards sign credentials with.
+pub const CREDENTIAL_KEY_FRAGMENT: &str = "credential-key";
+
+/// DID of `account`.
+pub fn did_of<AccountId: Ss58Codec>(account: &AccountId) -> String {
+	format!("{}{}", DID_PREFIX, account.to_ss58check())
+}
+
+/// Account a `did:build3` DID stands for, if `did` is one.
+pub fn account_of<AccountId: Ss58Codec>(did: &str) -> Option<AccountId> {
+	did.strip_prefix(DID_PREFIX)
+		.and_then(|address| AccountId::from_ss58check(address).ok())
+}
+
+/// Verification method of the credential key with DID `did`.
+pub fn credential_key_method(did: &str) -> String {
+	format!("{}#{}", did, CREDENTIAL_KEY_FRAGMENT)
+}
@@ -0,0 +1,465 @@
+//! Node-specific RPC methods for interaction with the licensure pallet.
+//!
+//! The `licensure_*` namespace lets permitting portals look up licenses, seals
+//! and board business, and watch a license for status changes, without decoding
+//! raw storage.
+//!
+//! Nodes holding a board's credential key also issue W3C verifiable credentials
+//! for the board's licenses, see [`credential`], to holders identified by their
+//! `did:build3` DID, see [`did`]. Any node verifies them against chain state.
+
+pub mod credential;
+pub mod did;
+
+use codec::Codec;
+use futures::{future, FutureExt, SinkExt, StreamExt};
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_client_api::BlockchainEvents;
+use sc_rpc_api::DenyUnsafe;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	crypto::{CryptoTypePublicPair, Ss58Codec},
+	sr25519, ByteArray,
+};
+use sp_keystore::{SyncCryptoStore, SyncCryptoStorePtr};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, NumberFor},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use pallet_licensure_rpc_runtime_api::{
+	BoardMotion, DisciplinaryAction, License, LicenseStatus, LicensureApi as LicensureRuntimeApi,
+	SealVerification,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+const CREDENTIAL_ERROR: i64 = 2;
+
+/// A license together with its number.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseDetails<AccountId, BoardId, BlockNumber> {
+	pub number: String,
+	#[serde(flatten)]
+	pub license: License<AccountId, BoardId, BlockNumber>,
+}
+
+/// Notification sent to subscribers watching a license.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LicenseStatusUpdate<BlockHash> {
+	/// Best block at which the status was observed.
+	pub block: BlockHash,
+	pub number: String,
+	/// `None` while no license with this number exists.
+	pub status: Option<LicenseStatus>,
+}
+
+/// Outcome of checking a license credential against chain state.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialVerification {
+	/// Every check below passed and the license is in good standing.
+	pub valid: bool,
+	/// The proof is a signature by the board's current credential key.
+	pub signature_valid: bool,
+	/// The block the credential is anchored to is on the best chain.
+	pub anchored: bool,
+	/// Holder, board and discipline are those of the license on chain.
+	pub matches_license: bool,
+	/// Current status of the license, `None` if no license has this number.
+	pub status: Option<LicenseStatus>,
+}
+
+/// Licensure RPC methods.
+#[rpc]
+pub trait LicensureApi<BlockHash, AccountId, BoardId, BlockNumber, Hash> {
+	/// RPC metadata
+	type Metadata;
+
+	/// Look up a license by its number.
+	#[rpc(name = "licensure_getLicense")]
+	fn license(
+		&self,
+		number: String,
+		at: Option<BlockHash>,
+	) -> Result<Option<LicenseDetails<AccountId, BoardId, BlockNumber>>>;
+
+	/// List the licenses held by an account.
+	#[rpc(name = "licensure_getLicensesOf")]
+	fn licenses_of(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<LicenseDetails<AccountId, BoardId, BlockNumber>>>;
+
+	/// List the members of a board.
+	#[rpc(name = "licensure_getBoardMembers")]
+	fn board_members(&self, board: BoardId, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+	/// List the motions pending before a board.
+	#[rpc(name = "licensure_getBoardMotions")]
+	fn board_motions(
+		&self,
+		board: BoardId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<BoardMotion<AccountId, BlockNumber, Hash>>>;
+
+	/// Check who sealed a document, given its hash.
+	#[rpc(name = "licensure_verifySeal")]
+	fn verify_seal(
+		&self,
+		document: Hash,
+		at: Option<BlockHash>,
+	) -> Result<Option<SealVerification<AccountId, BoardId, BlockNumber>>>;
+
+	/// Fetch the disciplinary history of a license.
+	#[rpc(name = "licensure_getDisciplinaryHistory")]
+	fn disciplinary_history(
+		&self,
+		number: String,
+		at: Option<BlockHash>,
+	) -> Result<Vec<DisciplinaryAction<BoardId, BlockNumber, Hash>>>;
+
+	/// Issue a verifiable credential for license `number`, signed with its
+	/// board's credential key from this node's keystore. Unsafe.
+	#[rpc(name = "licensure_issueCredential")]
+	fn issue_credential(&self, number: String) -> Result<Value>;
+
+	/// Check a license credential against the best chain: its signature, its
+	/// anchor block and the current standing of the license.
+	#[rpc(name = "licensure_verifyCredential")]
+	fn verify_credential(&self, credential: Value) -> Result<CredentialVerification>;
+
+	/// Watch a license, receiving its current status and every change of it on
+	/// the best chain.
+	#[pubsub(
+		subscription = "licensure_licenseStatus",
+		subscribe,
+		name = "licensure_subscribeLicenseStatus"
+	)]
+	fn subscribe_license_status(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<LicenseStatusUpdate<BlockHash>>,
+		number: String,
+	);
+
+	/// Stop watching a license.
+	#[pubsub(
+		subscription = "licensure_licenseStatus",
+		unsubscribe,
+		name = "licensure_unsubscribeLicenseStatus"
+	)]
+	fn unsubscribe_license_status(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
+/// Provides RPC methods to query the licensure pallet.
+pub struct Licensure<C, B> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	keystore: SyncCryptoStorePtr,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Licensure<C, B> {
+	/// Create new `Licensure` with the given reference to the client, signing
+	/// credentials with the keys of `keystore`.
+	pub fn new(
+		client: Arc<C>,
+		manager: SubscriptionManager,
+		keystore: SyncCryptoStorePtr,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self { client, manager, keystore, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+fn credential_error(message: impl Into<String>) -> Error {
+	Error { code: ErrorCode::ServerError(CREDENTIAL_ERROR), message: message.into(), data: None }
+}
+
+fn to_json(value: impl Serialize) -> Result<Value> {
+	serde_json::to_value(value).map_err(|e| Error::invalid_params(e.to_string()))
+}
+
+/// Field `path` of `credential`, e.g. `["proof", "proofValue"]`.
+fn field<'a>(credential: &'a Value, path: &[&str]) -> Result<&'a Value> {
+	path.iter()
+		.try_fold(credential, |value, key| value.get(key))
+		.ok_or_else(|| Error::invalid_params(format!("Credential lacks {}", path.join("."))))
+}
+
+fn parse_field<T: DeserializeOwned>(credential: &Value, path: &[&str]) -> Result<T> {
+	serde_json::from_value(field(credential, path)?.clone())
+		.map_err(|e| Error::invalid_params(format!("Invalid {}: {}", path.join("."), e)))
+}
+
+fn details<AccountId, BoardId, BlockNumber>(
+	number: Vec<u8>,
+	license: License<AccountId, BoardId, BlockNumber>,
+) -> LicenseDetails<AccountId, BoardId, BlockNumber> {
+	LicenseDetails { number: String::from_utf8_lossy(&number).into_owned(), license }
+}
+
+impl<C, Block, AccountId, BoardId, BlockNumber, Hash>
+	LicensureApi<<Block as BlockT>::Hash, AccountId, BoardId, BlockNumber, Hash>
+	for Licensure<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C: Send + Sync + 'static,
+	C::Api: LicensureRuntimeApi<Block, AccountId, BoardId, BlockNumber, Hash>,
+	AccountId: Codec + Ss58Codec + PartialEq + Send + Sync + 'static,
+	BoardId: Codec + Clone + Serialize + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn license(
+		&self,
+		number: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<LicenseDetails<AccountId, BoardId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let license = api
+			.license(&at, number.clone().into_bytes())
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(license.map(|license| details(number.into_bytes(), license)))
+	}
+
+	fn licenses_of(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<LicenseDetails<AccountId, BoardId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let licenses = api.licenses_of(&at, account).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(licenses.into_iter().map(|(number, license)| details(number, license)).collect())
+	}
+
+	fn board_members(
+		&self,
+		board: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.board_members(&at, board).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn board_motions(
+		&self,
+		board: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<BoardMotion<AccountId, BlockNumber, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.board_motions(&at, board).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn verify_seal(
+		&self,
+		document: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SealVerification<AccountId, BoardId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.verify_seal(&at, document).map_err(runtime_error_into_rpc_err)
+	}
+
+	fn disciplinary_history(
+		&self,
+		number: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<DisciplinaryAction<BoardId, BlockNumber, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.disciplinary_history(&at, number.into_bytes())
+			.map_err(runtime_error_into_rpc_err)
+	}
+
+	fn issue_credential(&self, number: String) -> Result<Value> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+		let info = self.client.info();
+		let at = BlockId::hash(info.best_hash);
+
+		let license = api
+			.license(&at, number.clone().into_bytes())
+			.map_err(runtime_error_into_rpc_err)?
+			.ok_or_else(|| credential_error("Unknown license"))?;
+		let key = api
+			.credential_key(&at, license.board.clone())
+			.map_err(runtime_error_into_rpc_err)?
+			.map(sr25519::Public::from_raw)
+			.ok_or_else(|| credential_error("The board has no credential key"))?;
+		if !SyncCryptoStore::has_keys(
+			&*self.keystore,
+			&[(key.to_raw_vec(), credential::CREDENTIAL_KEY_TYPE)],
+		) {
+			return Err(credential_error("The board's credential key is not in the keystore"))
+		}
+
+		let claim = credential::LicenseClaim {
+			number,
+			holder_did: did::did_of(&license.holder),
+			board: to_json(&license.board)?,
+			discipline: to_json(&license.discipline)?,
+			issued_at: to_json(&license.issued_at)?,
+			expires_at: to_json(&license.expires_at)?,
+		};
+		let anchor = credential::Anchor {
+			block_hash: to_json(&info.best_hash)?,
+			block_number: to_json(&info.best_number)?,
+		};
+		let mut vc = credential::unsigned(&did::did_of(&key), claim, anchor);
+
+		let signature = SyncCryptoStore::sign_with(
+			&*self.keystore,
+			credential::CREDENTIAL_KEY_TYPE,
+			&CryptoTypePublicPair::from(key),
+			&credential::signing_payload(&vc),
+		)
+		.map_err(|e| credential_error(format!("Signing failed: {}", e)))?
+		.ok_or_else(|| credential_error("The board's credential key is not in the keystore"))?;
+		credential::set_proof_value(&mut vc, &signature);
+
+		Ok(vc)
+	}
+
+	fn verify_credential(&self, credential: Value) -> Result<CredentialVerification> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(self.client.info().best_hash);
+
+		let number: String = parse_field(&credential, &["credentialSubject", "license", "number"])?;
+		let signature = credential::proof_value(&credential)
+			.ok_or_else(|| Error::invalid_params("Invalid proof.proofValue"))?;
+		let block_hash: <Block as BlockT>::Hash =
+			parse_field(&credential, &["credentialStatus", "blockHash"])?;
+		let block_number: NumberFor<Block> =
+			parse_field(&credential, &["credentialStatus", "blockNumber"])?;
+
+		let license = api.license(&at, number.into_bytes()).map_err(runtime_error_into_rpc_err)?;
+		let key = match &license {
+			Some(license) => api
+				.credential_key(&at, license.board.clone())
+				.map_err(runtime_error_into_rpc_err)?,
+			None => None,
+		};
+
+		let signature_valid = key
+			.map(sr25519::Public::from_raw)
+			.map_or(false, |key| credential::signed_by(&credential, &signature, &key));
+		let anchored = self
+			.client
+			.hash(block_number)
+			.map_err(runtime_error_into_rpc_err)?
+			.map_or(false, |hash| hash == block_hash);
+		let matches_license = match &license {
+			Some(license) => {
+				let holder = Value::String(did::did_of(&license.holder));
+				let board = to_json(&license.board)?;
+				let discipline = to_json(&license.discipline)?;
+				field(&credential, &["credentialSubject", "id"])? == &holder &&
+					field(&credential, &["credentialSubject", "license", "board"])? == &board &&
+					field(&credential, &["credentialSubject", "license", "discipline"])? ==
+						&discipline
+			},
+			None => false,
+		};
+		let status = license.map(|license| license.status);
+		let valid =
+			signature_valid && anchored && matches_license && status == Some(LicenseStatus::Active);
+
+		Ok(CredentialVerification { valid, signature_valid, anchored, matches_license, status })
+	}
+
+	fn subscribe_license_status(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<LicenseStatusUpdate<<Block as BlockT>::Hash>>,
+		number: String,
+	) {
+		let client = self.client.clone();
+		let status_at = move |hash: <Block as BlockT>::Hash| {
+			client
+				.runtime_api()
+				.license(&BlockId::hash(hash), number.clone().into_bytes())
+				.map(|license| LicenseStatusUpdate {
+					block: hash,
+					number: number.clone(),
+					status: license.map(|license| license.status),
+				})
+				.map_err(|e| log::warn!(target: "rpc", "Licensure status lookup failed: {:?}", e))
+				.ok()
+		};
+
+		// Start from the current best block, then only report changes.
+		let initial = status_at(self.client.info().best_hash);
+		let mut last = initial.as_ref().map(|update| update.status);
+		let changes = self
+			.client
+			.import_notification_stream()
+			.filter(|notification| future::ready(notification.is_new_best))
+			.filter_map(move |notification| {
+				let update = status_at(notification.hash)
+					.filter(|update| last != Some(update.status))
+					.map(|update| {
+						last = Some(update.status);
+						update
+					});
+				future::ready(update)
+			});
+
+		self.manager.add(subscriber, |sink| {
+			futures::stream::iter(initial)
+				.chain(changes)
+				.map(|update| Ok::<_, ()>(Ok(update)))
+				.forward(sink.sink_map_err(
+					|e| log::warn!(target: "rpc", "Error sending licensure notification: {:?}", e),
+				))
+				.map(|_| ())
+		});
+	}
+
+	fn unsubscribe_license_status(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
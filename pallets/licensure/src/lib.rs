@@ -0,0 +1,757 @@
+//! # Licensure Pallet
+//!
+//! Professional engineering licensure as administered by state boards.
+//!
+//! A board, acting through its collective, issues licenses to engineers,
+//! renews, suspends, reinstates and revokes them, records disciplinary actions
+//! and registers engineering firms. Licensed engineers affix their seal to
+//! documents by registering the document hash under one of their licenses, so
+//! that anyone can later check who sealed a document and whether that license
+//! is still in good standing.
+//!
+//! Licenses are keyed by the number printed on the board certificate. An
+//! engineer may hold licenses from several boards at once.
+//!
+//! Each board sets the approval every kind of action requires, a majority of
+//! the board unless set otherwise: a stricter vote such as two thirds or
+//! unanimity, the board's executive director alone, or its chair together with
+//! another member. Revocations can thus require more than routine renewals.
+//!
+//! A board may also set a credential key, the sr25519 key its node signs
+//! verifiable credentials for its licenses with. Credentials are only checked
+//! against the key currently set, so replacing the key revokes every credential
+//! signed with the previous one.
+//!
+//! Each board is also the identity registrar for its licensees. A board judges
+//! an identity by vote, and only accounts whose identity the board judged
+//! `KnownGood` or `Reasonable` can be licensed by it or seal documents under its
+//! licenses. The registrar itself is provided by the runtime through
+//! [`BoardRegistrar`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Contains, EnsureOrigin},
+	BoundedVec,
+};
+use sp_runtime::DispatchError;
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Identity registrar acting for each board.
+pub trait BoardRegistrar<AccountId, BoardId> {
+	/// Record `board`'s judgement of the identity of `who`.
+	fn provide_judgement(
+		board: BoardId,
+		who: &AccountId,
+		judgement: IdentityJudgement,
+	) -> DispatchResult;
+
+	/// `board`'s current judgement of the identity of `who`, if any.
+	fn judgement(board: BoardId, who: &AccountId) -> Option<IdentityJudgement>;
+}
+
+/// Identifier of a registered firm.
+pub type FirmId = u32;
+
+pub type LicenseNumberOf<T> = BoundedVec<u8, <T as Config>::MaxLicenseNumberLength>;
+pub type LicenseOf<T> = License<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+>;
+pub type FirmOf<T> = Firm<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+	BoundedVec<u8, <T as Config>::MaxNameLength>,
+>;
+pub type DisciplinaryActionOf<T> = DisciplinaryAction<
+	<T as Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+pub type SealOf<T> = Seal<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	LicenseNumberOf<T>,
+>;
+pub type SealVerificationOf<T> = SealVerification<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{MaybeSerializeDeserialize, StaticLookup};
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Identifies a licensing board.
+		type BoardId: Parameter + Member + MaxEncodedLen + MaybeSerializeDeserialize + Copy;
+
+		/// Origin of a board acting on the licenses it governs, yielding which
+		/// board it speaks for and the approval it carries. Each action checks
+		/// the approval against the board's rule for it.
+		type BoardOrigin: EnsureOrigin<Self::Origin, Success = (Self::BoardId, BoardApproval)>;
+
+		/// Origin allowed to set the approval rules and officers of boards.
+		type SettingsOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Identity registrar of each board.
+		type Registrar: BoardRegistrar<Self::AccountId, Self::BoardId>;
+
+		/// Maximum length of a license number.
+		#[pallet::constant]
+		type MaxLicenseNumberLength: Get<u32>;
+
+		/// Maximum length of a firm name.
+		#[pallet::constant]
+		type MaxNameLength: Get<u32>;
+
+		/// Maximum number of licenses held by one account.
+		#[pallet::constant]
+		type MaxLicensesPerAccount: Get<u32>;
+
+		/// Maximum number of disciplinary actions kept per license.
+		#[pallet::constant]
+		type MaxDisciplinaryActions: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Licenses by license number.
+	#[pallet::storage]
+	#[pallet::getter(fn license)]
+	pub type Licenses<T: Config> =
+		StorageMap<_, Blake2_128Concat, LicenseNumberOf<T>, LicenseOf<T>, OptionQuery>;
+
+	/// License numbers held by an account.
+	#[pallet::storage]
+	#[pallet::getter(fn licenses_of)]
+	pub type LicensesOf<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<LicenseNumberOf<T>, T::MaxLicensesPerAccount>,
+		ValueQuery,
+	>;
+
+	/// Registered firms.
+	#[pallet::storage]
+	#[pallet::getter(fn firm)]
+	pub type Firms<T: Config> = StorageMap<_, Twox64Concat, FirmId, FirmOf<T>, OptionQuery>;
+
+	/// Identifier given to the next registered firm.
+	#[pallet::storage]
+	pub type NextFirmId<T: Config> = StorageValue<_, FirmId, ValueQuery>;
+
+	/// Disciplinary history of a license, oldest first.
+	#[pallet::storage]
+	#[pallet::getter(fn disciplinary_actions)]
+	pub type DisciplinaryActions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		LicenseNumberOf<T>,
+		BoundedVec<DisciplinaryActionOf<T>, T::MaxDisciplinaryActions>,
+		ValueQuery,
+	>;
+
+	/// Seals by hash of the sealed document.
+	#[pallet::storage]
+	#[pallet::getter(fn seal)]
+	pub type Seals<T: Config> = StorageMap<_, Identity, T::Hash, SealOf<T>, OptionQuery>;
+
+	/// Approval each board requires for each action, a majority of the board
+	/// unless set otherwise.
+	#[pallet::storage]
+	#[pallet::getter(fn action_rule)]
+	pub type ActionRules<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BoardId,
+		Twox64Concat,
+		LicensureAction,
+		ApprovalRule,
+		ValueQuery,
+	>;
+
+	/// Chair and executive director of each board.
+	#[pallet::storage]
+	#[pallet::getter(fn board_officers)]
+	pub type Officers<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BoardId, BoardOfficers<T::AccountId>, ValueQuery>;
+
+	/// Key each board signs verifiable credentials with, see
+	/// `pallet-licensure-rpc`.
+	#[pallet::storage]
+	#[pallet::getter(fn credential_key)]
+	pub type CredentialKeys<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BoardId, [u8; 32], OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Licenses in force at genesis: number, holder, board, discipline and
+		/// expiry block.
+		pub licenses: Vec<(Vec<u8>, T::AccountId, T::BoardId, Discipline, T::BlockNumber)>,
+		/// Approval rules other than a majority of the board.
+		pub action_rules: Vec<(T::BoardId, LicensureAction, ApprovalRule)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { licenses: Vec::new(), action_rules: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (number, holder, board, discipline, expires_at) in &self.licenses {
+				Pallet::<T>::do_issue_license(
+					number.clone(),
+					holder.clone(),
+					*board,
+					*discipline,
+					*expires_at,
+				)
+				.expect("Genesis licenses must be valid and unique; qed");
+			}
+			for (board, action, rule) in &self.action_rules {
+				ActionRules::<T>::insert(board, action, rule);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A board issued a license.
+		LicenseIssued { number: LicenseNumberOf<T>, holder: T::AccountId, board: T::BoardId },
+		/// A license was renewed until `expires_at`.
+		LicenseRenewed { number: LicenseNumberOf<T>, expires_at: T::BlockNumber },
+		/// A board changed the status of a license.
+		LicenseStatusChanged { number: LicenseNumberOf<T>, status: LicenseStatus },
+		/// A disciplinary action was recorded against a license.
+		DisciplinaryActionRecorded { number: LicenseNumberOf<T>, kind: DisciplinaryKind },
+		/// A board registered a firm.
+		FirmRegistered { firm: FirmId, board: T::BoardId },
+		/// A board changed the status of a firm.
+		FirmStatusChanged { firm: FirmId, status: LicenseStatus },
+		/// A licensed engineer sealed a document.
+		DocumentSealed { document: T::Hash, number: LicenseNumberOf<T>, signer: T::AccountId },
+		/// A board judged an account's identity.
+		IdentityJudged { board: T::BoardId, who: T::AccountId, judgement: IdentityJudgement },
+		/// The approval a board requires for an action changed.
+		ActionRuleSet { board: T::BoardId, action: LicensureAction, rule: ApprovalRule },
+		/// The officers of a board changed.
+		BoardOfficersSet { board: T::BoardId, officers: BoardOfficers<T::AccountId> },
+		/// A board set or removed its credential key.
+		CredentialKeySet { board: T::BoardId, key: Option<[u8; 32]> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// License number is longer than `MaxLicenseNumberLength`.
+		LicenseNumberTooLong,
+		/// Firm name is longer than `MaxNameLength`.
+		NameTooLong,
+		/// A license with this number already exists.
+		LicenseExists,
+		/// No license with this number exists.
+		UnknownLicense,
+		/// No firm with this identifier exists.
+		UnknownFirm,
+		/// The license or firm is governed by another board.
+		WrongBoard,
+		/// Expiry must lie in the future.
+		InvalidExpiry,
+		/// The account already holds `MaxLicensesPerAccount` licenses.
+		TooManyLicenses,
+		/// The license already has `MaxDisciplinaryActions` actions recorded.
+		TooManyDisciplinaryActions,
+		/// The requested status change is not allowed from the current status.
+		InvalidStatusTransition,
+		/// The license has been revoked.
+		LicenseRevoked,
+		/// The license is not in good standing.
+		LicenseNotActive,
+		/// The engineer holds no active license from this board.
+		NoActiveLicense,
+		/// Only the license holder may seal under it.
+		NotLicenseHolder,
+		/// The document has already been sealed.
+		AlreadySealed,
+		/// The board has not judged the account's identity `KnownGood` or
+		/// `Reasonable`.
+		IdentityNotVerified,
+		/// The origin does not carry the approval the board requires for the
+		/// action.
+		InsufficientApproval,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Issue license `number` to `holder`, valid until `expires_at`.
+		///
+		/// The origin must be a board, which becomes the governing board of the
+		/// license.
+		#[pallet::weight(T::WeightInfo::issue_license())]
+		pub fn issue_license(
+			origin: OriginFor<T>,
+			number: Vec<u8>,
+			holder: <T::Lookup as StaticLookup>::Source,
+			discipline: Discipline,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::IssueLicense)?;
+			let holder = T::Lookup::lookup(holder)?;
+			ensure!(Self::identity_verified(board, &holder), Error::<T>::IdentityNotVerified);
+
+			Self::do_issue_license(number, holder, board, discipline, expires_at)
+		}
+
+		/// Extend license `number` until `expires_at`, restoring an expired
+		/// license to good standing.
+		#[pallet::weight(T::WeightInfo::renew_license())]
+		pub fn renew_license(
+			origin: OriginFor<T>,
+			number: Vec<u8>,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::RenewLicense)?;
+			let number = Self::bounded_number(number)?;
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			Licenses::<T>::try_mutate(&number, |license| -> DispatchResult {
+				let license = license.as_mut().ok_or(Error::<T>::UnknownLicense)?;
+				ensure!(license.board == board, Error::<T>::WrongBoard);
+				ensure!(license.status != LicenseStatus::Revoked, Error::<T>::LicenseRevoked);
+				license.expires_at = expires_at;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::LicenseRenewed { number, expires_at });
+			Ok(())
+		}
+
+		/// Suspend an active license.
+		#[pallet::weight(T::WeightInfo::set_license_status())]
+		pub fn suspend_license(origin: OriginFor<T>, number: Vec<u8>) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::SuspendLicense)?;
+			Self::transition_license(board, number, LicenseStatus::Suspended)
+		}
+
+		/// Lift the suspension of a license.
+		#[pallet::weight(T::WeightInfo::set_license_status())]
+		pub fn reinstate_license(origin: OriginFor<T>, number: Vec<u8>) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::ReinstateLicense)?;
+			Self::transition_license(board, number, LicenseStatus::Active)
+		}
+
+		/// Revoke a license permanently.
+		#[pallet::weight(T::WeightInfo::set_license_status())]
+		pub fn revoke_license(origin: OriginFor<T>, number: Vec<u8>) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::RevokeLicense)?;
+			Self::transition_license(board, number, LicenseStatus::Revoked)
+		}
+
+		/// Record a disciplinary action against license `number`. `order_hash` is
+		/// the hash of the board order describing it.
+		///
+		/// Recording does not change the license status by itself; a suspension or
+		/// revocation is dispatched separately.
+		#[pallet::weight(T::WeightInfo::record_disciplinary_action())]
+		pub fn record_disciplinary_action(
+			origin: OriginFor<T>,
+			number: Vec<u8>,
+			kind: DisciplinaryKind,
+			order_hash: T::Hash,
+		) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::RecordDisciplinaryAction)?;
+			let number = Self::bounded_number(number)?;
+			let license = Licenses::<T>::get(&number).ok_or(Error::<T>::UnknownLicense)?;
+			ensure!(license.board == board, Error::<T>::WrongBoard);
+
+			DisciplinaryActions::<T>::try_append(
+				&number,
+				DisciplinaryAction {
+					board,
+					kind,
+					order_hash,
+					recorded_at: frame_system::Pallet::<T>::block_number(),
+				},
+			)
+			.map_err(|()| Error::<T>::TooManyDisciplinaryActions)?;
+
+			Self::deposit_event(Event::DisciplinaryActionRecorded { number, kind });
+			Ok(())
+		}
+
+		/// Register a firm administered by `admin`, with `engineer_in_charge`
+		/// holding an active license from the same board.
+		#[pallet::weight(T::WeightInfo::register_firm())]
+		pub fn register_firm(
+			origin: OriginFor<T>,
+			admin: <T::Lookup as StaticLookup>::Source,
+			name: Vec<u8>,
+			engineer_in_charge: <T::Lookup as StaticLookup>::Source,
+			expires_at: T::BlockNumber,
+		) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::RegisterFirm)?;
+			let admin = T::Lookup::lookup(admin)?;
+			let engineer_in_charge = T::Lookup::lookup(engineer_in_charge)?;
+			let name: BoundedVec<_, _> = name.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(expires_at > now, Error::<T>::InvalidExpiry);
+			ensure!(
+				Self::has_active_license(&engineer_in_charge, &board, &now),
+				Error::<T>::NoActiveLicense
+			);
+
+			let firm = NextFirmId::<T>::mutate(|id| {
+				let firm = *id;
+				*id = id.saturating_add(1);
+				firm
+			});
+			Firms::<T>::insert(
+				firm,
+				Firm {
+					admin,
+					board,
+					name,
+					engineer_in_charge,
+					status: LicenseStatus::Active,
+					issued_at: now,
+					expires_at,
+				},
+			);
+
+			Self::deposit_event(Event::FirmRegistered { firm, board });
+			Ok(())
+		}
+
+		/// Suspend, reinstate or revoke a firm registration.
+		#[pallet::weight(T::WeightInfo::set_firm_status())]
+		pub fn set_firm_status(
+			origin: OriginFor<T>,
+			firm: FirmId,
+			status: LicenseStatus,
+		) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::SetFirmStatus)?;
+
+			Firms::<T>::try_mutate(firm, |entry| -> DispatchResult {
+				let entry = entry.as_mut().ok_or(Error::<T>::UnknownFirm)?;
+				ensure!(entry.board == board, Error::<T>::WrongBoard);
+				ensure!(
+					Self::transition_allowed(entry.status, status),
+					Error::<T>::InvalidStatusTransition
+				);
+				entry.status = status;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::FirmStatusChanged { firm, status });
+			Ok(())
+		}
+
+		/// Seal `document`, given by its hash, under license `number`.
+		///
+		/// The origin must hold the license, the license must be in good
+		/// standing and the board must have verified the holder's identity.
+		#[pallet::weight(T::WeightInfo::affix_seal())]
+		pub fn affix_seal(
+			origin: OriginFor<T>,
+			number: Vec<u8>,
+			document: T::Hash,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			let number = Self::bounded_number(number)?;
+			let license = Licenses::<T>::get(&number).ok_or(Error::<T>::UnknownLicense)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(license.holder == signer, Error::<T>::NotLicenseHolder);
+			ensure!(license.status_at(&now) == LicenseStatus::Active, Error::<T>::LicenseNotActive);
+			ensure!(
+				Self::identity_verified(license.board, &signer),
+				Error::<T>::IdentityNotVerified
+			);
+			ensure!(!Seals::<T>::contains_key(document), Error::<T>::AlreadySealed);
+
+			Seals::<T>::insert(
+				document,
+				Seal { license: number.clone(), signer: signer.clone(), sealed_at: now },
+			);
+
+			Self::deposit_event(Event::DocumentSealed { document, number, signer });
+			Ok(())
+		}
+
+		/// Judge the identity of `target`, as the board's identity registrar.
+		///
+		/// The origin must be a board. `target` must have set an identity.
+		#[pallet::weight(T::WeightInfo::judge_identity())]
+		pub fn judge_identity(
+			origin: OriginFor<T>,
+			target: <T::Lookup as StaticLookup>::Source,
+			judgement: IdentityJudgement,
+		) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::JudgeIdentity)?;
+			let who = T::Lookup::lookup(target)?;
+
+			T::Registrar::provide_judgement(board, &who, judgement)?;
+
+			Self::deposit_event(Event::IdentityJudged { board, who, judgement });
+			Ok(())
+		}
+
+		/// Set the approval `board` requires for `action`.
+		///
+		/// The origin must be `SettingsOrigin`.
+		#[pallet::weight(T::WeightInfo::set_action_rule())]
+		pub fn set_action_rule(
+			origin: OriginFor<T>,
+			board: T::BoardId,
+			action: LicensureAction,
+			rule: ApprovalRule,
+		) -> DispatchResult {
+			T::SettingsOrigin::ensure_origin(origin)?;
+
+			ActionRules::<T>::insert(board, action, rule);
+
+			Self::deposit_event(Event::ActionRuleSet { board, action, rule });
+			Ok(())
+		}
+
+		/// Set the chair and executive director of `board`. Either may be left
+		/// vacant.
+		///
+		/// The origin must be `SettingsOrigin`.
+		#[pallet::weight(T::WeightInfo::set_board_officers())]
+		pub fn set_board_officers(
+			origin: OriginFor<T>,
+			board: T::BoardId,
+			chair: Option<<T::Lookup as StaticLookup>::Source>,
+			executive_director: Option<<T::Lookup as StaticLookup>::Source>,
+		) -> DispatchResult {
+			T::SettingsOrigin::ensure_origin(origin)?;
+			let officers = BoardOfficers {
+				chair: chair.map(T::Lookup::lookup).transpose()?,
+				executive_director: executive_director.map(T::Lookup::lookup).transpose()?,
+			};
+
+			Officers::<T>::insert(board, &officers);
+
+			Self::deposit_event(Event::BoardOfficersSet { board, officers });
+			Ok(())
+		}
+
+		/// Set the sr25519 public key the board's credentials are signed with,
+		/// or remove it to stop issuing credentials. Credentials signed with the
+		/// previous key no longer verify.
+		///
+		/// The origin must be a board.
+		#[pallet::weight(T::WeightInfo::set_credential_key())]
+		pub fn set_credential_key(origin: OriginFor<T>, key: Option<[u8; 32]>) -> DispatchResult {
+			let board = Self::ensure_board(origin, LicensureAction::SetCredentialKey)?;
+
+			CredentialKeys::<T>::set(board, key);
+
+			Self::deposit_event(Event::CredentialKeySet { board, key });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The board `origin` speaks for, if it carries the approval the board
+	/// requires for `action`. Pallets acting for boards outside of this one
+	/// check their actions' rules through it too.
+	pub fn ensure_board(
+		origin: T::Origin,
+		action: LicensureAction,
+	) -> Result<T::BoardId, DispatchError> {
+		let (board, approval) = T::BoardOrigin::ensure_origin(origin)?;
+		ensure!(
+			ActionRules::<T>::get(board, action).is_met_by(&approval),
+			Error::<T>::InsufficientApproval
+		);
+		Ok(board)
+	}
+
+	fn bounded_number(number: Vec<u8>) -> Result<LicenseNumberOf<T>, Error<T>> {
+		number.try_into().map_err(|_| Error::<T>::LicenseNumberTooLong)
+	}
+
+	fn do_issue_license(
+		number: Vec<u8>,
+		holder: T::AccountId,
+		board: T::BoardId,
+		discipline: Discipline,
+		expires_at: T::BlockNumber,
+	) -> DispatchResult {
+		let number = Self::bounded_number(number)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(!Licenses::<T>::contains_key(&number), Error::<T>::LicenseExists);
+		ensure!(expires_at > now, Error::<T>::InvalidExpiry);
+
+		LicensesOf::<T>::try_append(&holder, number.clone())
+			.map_err(|()| Error::<T>::TooManyLicenses)?;
+		Licenses::<T>::insert(
+			&number,
+			License {
+				holder: holder.clone(),
+				board,
+				discipline,
+				status: LicenseStatus::Active,
+				issued_at: now,
+				expires_at,
+			},
+		);
+
+		Self::deposit_event(Event::LicenseIssued { number, holder, board });
+		Ok(())
+	}
+
+	fn transition_license(
+		board: T::BoardId,
+		number: Vec<u8>,
+		status: LicenseStatus,
+	) -> DispatchResult {
+		let number = Self::bounded_number(number)?;
+
+		Licenses::<T>::try_mutate(&number, |license| -> DispatchResult {
+			let license = license.as_mut().ok_or(Error::<T>::UnknownLicense)?;
+			ensure!(license.board == board, Error::<T>::WrongBoard);
+			ensure!(
+				Self::transition_allowed(license.status, status),
+				Error::<T>::InvalidStatusTransition
+			);
+			license.status = status;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::LicenseStatusChanged { number, status });
+		Ok(())
+	}
+
+	/// Suspension is reversible, revocation is not. `Expired` is derived from the
+	/// expiry block and never set by a board.
+	fn transition_allowed(from: LicenseStatus, to: LicenseStatus) -> bool {
+		matches!(
+			(from, to),
+			(LicenseStatus::Active, LicenseStatus::Suspended) |
+				(LicenseStatus::Suspended, LicenseStatus::Active) |
+				(LicenseStatus::Active, LicenseStatus::Revoked) |
+				(LicenseStatus::Suspended, LicenseStatus::Revoked)
+		)
+	}
+
+	/// Whether `board` vouches for the identity of `who`.
+	pub fn identity_verified(board: T::BoardId, who: &T::AccountId) -> bool {
+		T::Registrar::judgement(board, who).map_or(false, |judgement| judgement.is_verified())
+	}
+
+	/// Whether `who` holds a license from `board` in good standing at `now`.
+	pub fn has_active_license(
+		who: &T::AccountId,
+		board: &T::BoardId,
+		now: &T::BlockNumber,
+	) -> bool {
+		LicensesOf::<T>::get(who).iter().any(|number| {
+			Licenses::<T>::get(number).map_or(false, |license| {
+				license.board == *board && license.status_at(now) == LicenseStatus::Active
+			})
+		})
+	}
+
+	/// Whether `who` holds a license from any board in good standing.
+	pub fn is_licensed(who: &T::AccountId) -> bool {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		LicensesOf::<T>::get(who).iter().any(|number| {
+			Licenses::<T>::get(number)
+				.map_or(false, |license| license.status_at(&now) == LicenseStatus::Active)
+		})
+	}
+
+	/// License `number` with its status as of the current block.
+	pub fn license_info(number: Vec<u8>) -> Option<LicenseOf<T>> {
+		let number: LicenseNumberOf<T> = number.try_into().ok()?;
+		let now = frame_system::Pallet::<T>::block_number();
+
+		Licenses::<T>::get(&number)
+			.map(|license| License { status: license.status_at(&now), ..license })
+	}
+
+	/// All licenses held by `who`, with their status as of the current block.
+	pub fn licenses_info(who: T::AccountId) -> Vec<(Vec<u8>, LicenseOf<T>)> {
+		LicensesOf::<T>::get(&who)
+			.into_iter()
+			.filter_map(|number| {
+				let license = Self::license_info(number.to_vec())?;
+				Some((number.into_inner(), license))
+			})
+			.collect()
+	}
+
+	/// Who sealed `document`, and the current standing of their license.
+	pub fn verify_seal(document: T::Hash) -> Option<SealVerificationOf<T>> {
+		let seal = Seals::<T>::get(document)?;
+		let license = Self::license_info(seal.license.to_vec())?;
+
+		Some(SealVerification {
+			license_number: seal.license.into_inner(),
+			signer_identity_verified: Self::identity_verified(license.board, &seal.signer),
+			signer: seal.signer,
+			board: license.board,
+			sealed_at: seal.sealed_at,
+			license_status: license.status,
+		})
+	}
+
+	/// Disciplinary history of license `number`, oldest first.
+	pub fn disciplinary_history(number: Vec<u8>) -> Vec<DisciplinaryActionOf<T>> {
+		match LicenseNumberOf::<T>::try_from(number) {
+			Ok(number) => DisciplinaryActions::<T>::get(&number).into_inner(),
+			Err(_) => Vec::new(),
+		}
+	}
+}
+
+/// Accounts holding a license from any board in good standing, e.g. the
+/// electorate of council elections.
+pub struct LicensedEngineers<T>(PhantomData<T>);
+impl<T: Config> Contains<T::AccountId> for LicensedEngineers<T> {
+	fn contains(who: &T::AccountId) -> bool {
+		Pallet::<T>::is_licensed(who)
+	}
+}
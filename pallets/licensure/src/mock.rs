@@ -0,0 +1,143 @@
+//! Test runtime of the licensure pallet.
+
+use crate as pallet_licensure;
+use crate::{BoardApproval, BoardRegistrar, IdentityJudgement};
+use frame_support::traits::{ConstU32, ConstU64, EnsureOrigin, Everything, GenesisBuild};
+use frame_system::{EnsureRoot, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchResult,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Licensure: pallet_licensure::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// Members of each board.
+pub const MEMBERS: u32 = 5;
+/// Offset of the accounts speaking for a board, see [`motion`].
+const BOARD_ACCOUNTS: u64 = 1_000;
+const EXECUTIVE_DIRECTOR: u64 = 100;
+const CHAIR_PLUS_ONE: u64 = 200;
+/// Account whose identity no board judged.
+pub const UNVERIFIED: u64 = 99;
+
+/// Origin of a motion of `board` carried by `ayes` of its members.
+pub fn motion(board: u32, ayes: u32) -> Origin {
+	Origin::signed(board as u64 * BOARD_ACCOUNTS + ayes as u64)
+}
+
+/// Origin of the executive director of `board`.
+pub fn executive_director(board: u32) -> Origin {
+	Origin::signed(board as u64 * BOARD_ACCOUNTS + EXECUTIVE_DIRECTOR)
+}
+
+/// Origin of the chair of `board` together with another member.
+pub fn chair_plus_one(board: u32) -> Origin {
+	Origin::signed(board as u64 * BOARD_ACCOUNTS + CHAIR_PLUS_ONE)
+}
+
+/// Boards speak through the accounts of [`motion`], [`executive_director`] and
+/// [`chair_plus_one`], instead of collectives of their own.
+pub struct EnsureBoard;
+impl EnsureOrigin<Origin> for EnsureBoard {
+	type Success = (u32, BoardApproval);
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let who = match o.clone().into() {
+			Ok(RawOrigin::Signed(who)) if who >= BOARD_ACCOUNTS => who,
+			_ => return Err(o),
+		};
+		let board = (who / BOARD_ACCOUNTS) as u32;
+		let approval = match who % BOARD_ACCOUNTS {
+			EXECUTIVE_DIRECTOR => BoardApproval::ExecutiveDirector,
+			CHAIR_PLUS_ONE => BoardApproval::ChairPlusOne,
+			ayes if ayes <= MEMBERS as u64 =>
+				BoardApproval::Motion { ayes: ayes as u32, members: MEMBERS },
+			_ => return Err(o),
+		};
+		Ok((board, approval))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		motion(1, MEMBERS)
+	}
+}
+
+/// Every board vouches for every identity but that of [`UNVERIFIED`].
+pub struct Registrar;
+impl BoardRegistrar<u64, u32> for Registrar {
+	fn provide_judgement(_: u32, _: &u64, _: IdentityJudgement) -> DispatchResult {
+		Ok(())
+	}
+
+	fn judgement(_: u32, who: &u64) -> Option<IdentityJudgement> {
+		(*who != UNVERIFIED).then(|| IdentityJudgement::KnownGood)
+	}
+}
+
+impl pallet_licensure::Config for Test {
+	type Event = Event;
+	type BoardId = u32;
+	type BoardOrigin = EnsureBoard;
+	type SettingsOrigin = EnsureRoot<u64>;
+	type Registrar = Registrar;
+	type MaxLicenseNumberLength = ConstU32<16>;
+	type MaxNameLength = ConstU32<32>;
+	type MaxLicensesPerAccount = ConstU32<4>;
+	type MaxDisciplinaryActions = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_licensure::GenesisConfig::<Test> {
+		licenses: vec![(b"PE-1".to_vec(), 1, 1, crate::Discipline::Civil, 1_000)],
+		action_rules: vec![],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,139 @@
+//! Tests of the licensure pallet.
+
+use crate::{mock::*, ApprovalRule, Discipline, Error, LicensureAction};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchError, DispatchResult},
+};
+
+fn issue(origin: Origin, number: &[u8], holder: u64) -> DispatchResult {
+	Licensure::issue_license(origin, number.to_vec(), holder, Discipline::Structural, 1_000)
+}
+
+#[test]
+fn majority_of_the_board_is_required_by_default() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(issue(motion(1, 2), b"PE-2", 2), Error::<Test>::InsufficientApproval);
+		assert_ok!(issue(motion(1, 3), b"PE-2", 2));
+
+		let license = Licensure::license_info(b"PE-2".to_vec()).unwrap();
+		assert_eq!(license.board, 1);
+		assert_eq!(license.holder, 2);
+	});
+}
+
+#[test]
+fn origins_not_speaking_for_a_board_are_rejected() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(issue(Origin::signed(1), b"PE-2", 2), DispatchError::BadOrigin);
+		assert_noop!(issue(Origin::root(), b"PE-2", 2), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn officers_act_only_where_the_rule_allows() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(issue(executive_director(1), b"PE-2", 2), Error::<Test>::InsufficientApproval);
+		assert_noop!(issue(chair_plus_one(1), b"PE-2", 2), Error::<Test>::InsufficientApproval);
+
+		assert_ok!(Licensure::set_action_rule(
+			Origin::root(),
+			1,
+			LicensureAction::IssueLicense,
+			ApprovalRule::ExecutiveDirector,
+		));
+		assert_noop!(issue(motion(1, MEMBERS), b"PE-2", 2), Error::<Test>::InsufficientApproval);
+		assert_noop!(issue(chair_plus_one(1), b"PE-2", 2), Error::<Test>::InsufficientApproval);
+		assert_ok!(issue(executive_director(1), b"PE-2", 2));
+	});
+}
+
+#[test]
+fn stricter_rules_need_more_ayes() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Licensure::set_action_rule(
+			Origin::root(),
+			1,
+			LicensureAction::RevokeLicense,
+			ApprovalRule::TwoThirds,
+		));
+		assert_noop!(
+			Licensure::revoke_license(motion(1, 3), b"PE-1".to_vec()),
+			Error::<Test>::InsufficientApproval
+		);
+		// Other actions keep the default majority.
+		assert_ok!(Licensure::suspend_license(motion(1, 3), b"PE-1".to_vec()));
+		assert_ok!(Licensure::revoke_license(motion(1, 4), b"PE-1".to_vec()));
+	});
+}
+
+#[test]
+fn rules_are_set_per_board() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Licensure::set_action_rule(
+			Origin::root(),
+			2,
+			LicensureAction::IssueLicense,
+			ApprovalRule::Unanimous,
+		));
+		assert_noop!(issue(motion(2, 4), b"PE-2", 2), Error::<Test>::InsufficientApproval);
+		assert_ok!(issue(motion(2, MEMBERS), b"PE-2", 2));
+		assert_ok!(issue(motion(1, 3), b"PE-3", 3));
+	});
+}
+
+#[test]
+fn only_the_settings_origin_sets_rules() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Licensure::set_action_rule(
+				motion(1, MEMBERS),
+				1,
+				LicensureAction::IssueLicense,
+				ApprovalRule::ExecutiveDirector,
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn boards_govern_only_their_own_licenses() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Licensure::suspend_license(motion(2, MEMBERS), b"PE-1".to_vec()),
+			Error::<Test>::WrongBoard
+		);
+	});
+}
+
+#[test]
+fn ensure_board_checks_rules_of_other_pallets() {
+	new_test_ext().execute_with(|| {
+		let action = LicensureAction::ResolveEscrowDispute;
+		assert_eq!(Licensure::ensure_board(motion(1, 3), action), Ok(1));
+		assert_noop!(
+			Licensure::ensure_board(executive_director(1), action),
+			Error::<Test>::InsufficientApproval
+		);
+
+		assert_ok!(Licensure::set_action_rule(
+			Origin::root(),
+			1,
+			action,
+			ApprovalRule::ChairPlusOne
+		));
+		assert_eq!(Licensure::ensure_board(chair_plus_one(1), action), Ok(1));
+		assert_noop!(
+			Licensure::ensure_board(motion(1, 3), action),
+			Error::<Test>::InsufficientApproval
+		);
+	});
+}
+
+#[test]
+fn unverified_identities_are_not_licensed() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(issue(motion(1, 3), b"PE-2", UNVERIFIED), Error::<Test>::IdentityNotVerified);
+	});
+}
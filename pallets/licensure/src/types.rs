@@ -0,0 +1,262 @@
+//! Records kept by the licensure pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Branch of engineering a license is granted for.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum Discipline {
+	Civil,
+	Structural,
+	Mechanical,
+	Electrical,
+	Chemical,
+	Environmental,
+	Geotechnical,
+	Other,
+}
+
+/// Standing of a license or firm registration.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum LicenseStatus {
+	/// In good standing.
+	Active,
+	/// Past its expiry block without being renewed. Never stored, only reported.
+	Expired,
+	/// Suspended by the board, may be reinstated.
+	Suspended,
+	/// Revoked by the board for good.
+	Revoked,
+}
+
+/// A professional engineering license granted by a board.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct License<AccountId, BoardId, BlockNumber> {
+	/// Account of the licensed engineer.
+	pub holder: AccountId,
+	/// Board which granted the license.
+	pub board: BoardId,
+	pub discipline: Discipline,
+	/// Status as last set by the board, see [`License::status_at`].
+	pub status: LicenseStatus,
+	pub issued_at: BlockNumber,
+	pub expires_at: BlockNumber,
+}
+
+impl<AccountId, BoardId, BlockNumber: PartialOrd> License<AccountId, BoardId, BlockNumber> {
+	/// Status of the license at block `now`, accounting for expiry.
+	pub fn status_at(&self, now: &BlockNumber) -> LicenseStatus {
+		status_at(self.status, &self.expires_at, now)
+	}
+}
+
+/// `status` of a registration expiring at `expires_at`, as of block `now`.
+fn status_at<BlockNumber: PartialOrd>(
+	status: LicenseStatus,
+	expires_at: &BlockNumber,
+	now: &BlockNumber,
+) -> LicenseStatus {
+	match status {
+		LicenseStatus::Active if now >= expires_at => LicenseStatus::Expired,
+		status => status,
+	}
+}
+
+/// A board's judgement of an account's on-chain identity, given as the board's
+/// identity registrar.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum IdentityJudgement {
+	/// The board verified the identity and vouches for it.
+	KnownGood,
+	/// The identity holds up as far as the board could check.
+	Reasonable,
+	/// The identity was correct but no longer is.
+	OutOfDate,
+	/// The identity is incomplete or otherwise of poor quality.
+	LowQuality,
+	/// The identity is wrong, possibly on purpose.
+	Erroneous,
+}
+
+impl IdentityJudgement {
+	/// Whether the judgement lets the account be licensed and seal documents.
+	pub fn is_verified(&self) -> bool {
+		matches!(self, IdentityJudgement::KnownGood | IdentityJudgement::Reasonable)
+	}
+}
+
+/// Board business whose required approval each board sets separately.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum LicensureAction {
+	IssueLicense,
+	RenewLicense,
+	SuspendLicense,
+	ReinstateLicense,
+	RevokeLicense,
+	RecordDisciplinaryAction,
+	RegisterFirm,
+	SetFirmStatus,
+	JudgeIdentity,
+	SetCredentialKey,
+	/// Closing a correction reported by the roster sync pallet.
+	ResolveRosterCorrection,
+	/// Clearing a permit number claimed by a project of the projects pallet.
+	ClearPermit,
+	/// Authorizing or revoking a reviewer of the plan review pallet.
+	AuthorizeReviewer,
+	/// Settling a milestone dispute of the escrow pallet as its arbiter.
+	ResolveEscrowDispute,
+}
+
+/// Approval a board action requires.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ApprovalRule {
+	/// A motion carried by more than half of the board.
+	Majority,
+	/// A motion carried by at least two thirds of the board.
+	TwoThirds,
+	/// A motion carried by every member of the board.
+	Unanimous,
+	/// The board's executive director alone.
+	ExecutiveDirector,
+	/// The board's chair together with any other member of the board.
+	ChairPlusOne,
+}
+
+impl Default for ApprovalRule {
+	fn default() -> Self {
+		ApprovalRule::Majority
+	}
+}
+
+impl ApprovalRule {
+	/// Whether `approval` meets the rule.
+	pub fn is_met_by(&self, approval: &BoardApproval) -> bool {
+		match (self, approval) {
+			(ApprovalRule::Majority, BoardApproval::Motion { ayes, members }) =>
+				ayes * 2 > *members,
+			(ApprovalRule::TwoThirds, BoardApproval::Motion { ayes, members }) =>
+				ayes * 3 >= members * 2,
+			(ApprovalRule::Unanimous, BoardApproval::Motion { ayes, members }) => ayes == members,
+			(ApprovalRule::ExecutiveDirector, BoardApproval::ExecutiveDirector) |
+			(ApprovalRule::ChairPlusOne, BoardApproval::ChairPlusOne) => true,
+			_ => false,
+		}
+	}
+}
+
+/// Approval carried by an origin speaking for a board.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BoardApproval {
+	/// A motion of the board's collective, carried by `ayes` of its `members`.
+	Motion { ayes: u32, members: u32 },
+	/// The board's executive director.
+	ExecutiveDirector,
+	/// The board's chair together with another member.
+	ChairPlusOne,
+}
+
+/// Officers of a board, who may act for it where its rules allow.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BoardOfficers<AccountId> {
+	/// Chair of the board, one of its members.
+	pub chair: Option<AccountId>,
+	pub executive_director: Option<AccountId>,
+}
+
+impl<AccountId> Default for BoardOfficers<AccountId> {
+	fn default() -> Self {
+		Self { chair: None, executive_director: None }
+	}
+}
+
+/// A firm registered with a board to offer engineering services.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Firm<AccountId, BoardId, BlockNumber, Name> {
+	/// Account administering the firm registration.
+	pub admin: AccountId,
+	pub board: BoardId,
+	pub name: Name,
+	/// Licensed engineer in responsible charge of the firm's work.
+	pub engineer_in_charge: AccountId,
+	pub status: LicenseStatus,
+	pub issued_at: BlockNumber,
+	pub expires_at: BlockNumber,
+}
+
+impl<AccountId, BoardId, BlockNumber: PartialOrd, Name>
+	Firm<AccountId, BoardId, BlockNumber, Name>
+{
+	/// Status of the registration at block `now`, accounting for expiry.
+	pub fn status_at(&self, now: &BlockNumber) -> LicenseStatus {
+		status_at(self.status, &self.expires_at, now)
+	}
+}
+
+/// Kind of sanction imposed on a licensee.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum DisciplinaryKind {
+	Reprimand,
+	Fine,
+	Probation,
+	Suspension,
+	Revocation,
+}
+
+/// A disciplinary action recorded against a license.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DisciplinaryAction<BoardId, BlockNumber, Hash> {
+	pub board: BoardId,
+	pub kind: DisciplinaryKind,
+	/// Hash of the board order describing the action.
+	pub order_hash: Hash,
+	pub recorded_at: BlockNumber,
+}
+
+/// An engineer's seal affixed to a document.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Seal<AccountId, BlockNumber, LicenseNumber> {
+	/// License the document was sealed under.
+	pub license: LicenseNumber,
+	pub signer: AccountId,
+	pub sealed_at: BlockNumber,
+}
+
+/// Answer to "who sealed this document, and are they still in good standing?".
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SealVerification<AccountId, BoardId, BlockNumber> {
+	pub license_number: Vec<u8>,
+	pub signer: AccountId,
+	pub board: BoardId,
+	pub sealed_at: BlockNumber,
+	/// Status of the license now, not when the document was sealed.
+	pub license_status: LicenseStatus,
+	/// Whether the board still vouches for the signer's identity.
+	pub signer_identity_verified: bool,
+}
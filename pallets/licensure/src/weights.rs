@@ -0,0 +1,165 @@
+//! Weights for pallet_licensure.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_licensure.
+pub trait WeightInfo {
+	fn issue_license() -> Weight;
+	fn renew_license() -> Weight;
+	fn set_license_status() -> Weight;
+	fn record_disciplinary_action() -> Weight;
+	fn register_firm() -> Weight;
+	fn set_firm_status() -> Weight;
+	fn affix_seal() -> Weight;
+	fn judge_identity() -> Weight;
+	fn set_action_rule() -> Weight;
+	fn set_board_officers() -> Weight;
+	fn set_credential_key() -> Weight;
+}
+
+/// Weights for pallet_licensure using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Licensure Licenses (r:1 w:1)
+	// Storage: Licensure LicensesOf (r:1 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn issue_license() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Licensure Licenses (r:1 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn renew_license() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure Licenses (r:1 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn set_license_status() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure Licenses (r:1 w:0)
+	// Storage: Licensure DisciplinaryActions (r:1 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn record_disciplinary_action() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure LicensesOf (r:1 w:0)
+	// Storage: Licensure Licenses (r:1 w:0)
+	// Storage: Licensure NextFirmId (r:1 w:1)
+	// Storage: Licensure Firms (r:0 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn register_firm() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Licensure Firms (r:1 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn set_firm_status() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure Licenses (r:1 w:0)
+	// Storage: Licensure Seals (r:1 w:1)
+	fn affix_seal() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Identity Registrars (r:1 w:1)
+	// Storage: Identity IdentityOf (r:1 w:1)
+	// Storage: Licensure ActionRules (r:1 w:0)
+	fn judge_identity() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Licensure ActionRules (r:0 w:1)
+	fn set_action_rule() -> Weight {
+		(15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure Officers (r:0 w:1)
+	fn set_board_officers() -> Weight {
+		(16_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure ActionRules (r:1 w:0)
+	// Storage: Licensure CredentialKeys (r:0 w:1)
+	fn set_credential_key() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn issue_license() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn renew_license() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_license_status() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn record_disciplinary_action() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn register_firm() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_firm_status() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn affix_seal() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn judge_identity() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_action_rule() -> Weight {
+		(15_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_board_officers() -> Weight {
+		(16_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_credential_key() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,21 @@
+//! Runtime API definition for the plan review pallet.
+//!
+//! Lets permitting offices tell whether the documents of a project passed plan
+//! review before they issue its permit.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_plan_review::{DisciplineReview, PermitStatus, ReviewState, SubmissionId};
+pub use pallet_projects::ProjectId;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to plan review outcomes.
+	pub trait PlanReviewApi<Hash> where
+		Hash: Codec,
+	{
+		/// Plan review status of project `id`, `None` if no such project exists.
+		fn permit_status(id: ProjectId) -> Option<PermitStatus<Hash>>;
+	}
+}
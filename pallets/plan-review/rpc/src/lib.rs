@@ -0,0 +1,72 @@
+//! Node-specific RPC methods for interaction with the plan review pallet.
+//!
+//! `planReview_getPermitStatus` reports, for each discipline of a project,
+//! where the review of its latest document stands, and whether the project
+//! passed plan review as a whole.
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use pallet_plan_review_rpc_runtime_api::{
+	DisciplineReview, PermitStatus, PlanReviewApi as PlanReviewRuntimeApi, ProjectId, ReviewState,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Plan review RPC methods.
+#[rpc]
+pub trait PlanReviewApi<BlockHash, Hash> {
+	/// Report the plan review status of a project.
+	#[rpc(name = "planReview_getPermitStatus")]
+	fn permit_status(
+		&self,
+		project: ProjectId,
+		at: Option<BlockHash>,
+	) -> Result<Option<PermitStatus<Hash>>>;
+}
+
+/// Provides RPC methods to query plan review outcomes.
+pub struct PlanReview<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> PlanReview<C, B> {
+	/// Create new `PlanReview` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, Hash> PlanReviewApi<<Block as BlockT>::Hash, Hash> for PlanReview<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PlanReviewRuntimeApi<Block, Hash>,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn permit_status(
+		&self,
+		project: ProjectId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PermitStatus<Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.permit_status(&at, project).map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,614 @@
+//! # Plan Review Pallet
+//!
+//! Plan check of sealed project documents by the reviewers of the project's
+//! jurisdiction.
+//!
+//! A project owner submits a document attached to the project by its engineer
+//! of record, reserving the review fee under the pallet's reserve identifier.
+//! A reviewer, who must hold a license in good standing from the jurisdiction's
+//! board or be authorized by the board, opens a review round and records the
+//! hash of their comment set. The round closes by approving the document,
+//! rejecting it or asking for changes. After changes were asked, the owner
+//! resubmits a revision superseding the document for another round with the
+//! same reviewer.
+//!
+//! The fee goes to the reviewer once the document is approved or rejected, or
+//! if the owner withdraws after a round was held. Otherwise withdrawing
+//! refunds it. Each submission and resubmission must be reviewed within
+//! `ReviewPeriod`; once it has passed without a round being closed, the owner
+//! reclaims the fee and the submission is withdrawn.
+//!
+//! Approvals feed the permit status of the project: a project is approved once
+//! the latest document of each discipline with an engineer of record is.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{BalanceStatus, Currency, NamedReservableCurrency},
+};
+use pallet_licensure::{Discipline, LicensureAction};
+use pallet_projects::{Documents, EngineersOfRecord, ProjectId, Projects};
+use sp_runtime::{traits::Saturating, DispatchError};
+use sp_std::prelude::*;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type ReserveIdentifierOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+	<T as frame_system::Config>::AccountId,
+>>::ReserveIdentifier;
+pub type SubmissionOf<T> = Submission<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+pub type ReviewRoundOf<T> = ReviewRound<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: pallet_projects::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency review fees are paid in.
+		type Currency: NamedReservableCurrency<Self::AccountId>;
+
+		/// Identifier review fees are reserved under.
+		#[pallet::constant]
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// Blocks a reviewer has to close a round of a submission, counted from
+		/// its submission or latest resubmission.
+		#[pallet::constant]
+		type ReviewPeriod: Get<Self::BlockNumber>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Reviewers authorized by each board, besides its licensees.
+	#[pallet::storage]
+	pub type Reviewers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BoardId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Identifier given to the next submission.
+	#[pallet::storage]
+	pub type NextSubmissionId<T: Config> = StorageValue<_, SubmissionId, ValueQuery>;
+
+	/// Submissions for review.
+	#[pallet::storage]
+	#[pallet::getter(fn submission)]
+	pub type Submissions<T: Config> =
+		StorageMap<_, Twox64Concat, SubmissionId, SubmissionOf<T>, OptionQuery>;
+
+	/// Latest submission of each project for each discipline.
+	#[pallet::storage]
+	#[pallet::getter(fn latest_submission)]
+	pub type LatestSubmissions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProjectId,
+		Twox64Concat,
+		Discipline,
+		SubmissionId,
+		OptionQuery,
+	>;
+
+	/// Review rounds of each submission, by position.
+	#[pallet::storage]
+	#[pallet::getter(fn round)]
+	pub type Rounds<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		SubmissionId,
+		Twox64Concat,
+		u32,
+		ReviewRoundOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A board authorized a reviewer.
+		ReviewerAuthorized { board: T::BoardId, reviewer: T::AccountId },
+		/// A board revoked the authorization of a reviewer.
+		ReviewerRevoked { board: T::BoardId, reviewer: T::AccountId },
+		/// A document was submitted for review.
+		Submitted {
+			submission: SubmissionId,
+			project: ProjectId,
+			document: T::Hash,
+			fee: BalanceOf<T>,
+		},
+		/// A revision of the document was submitted after changes were asked.
+		Resubmitted { submission: SubmissionId, document: T::Hash },
+		/// A reviewer opened a review round.
+		RoundOpened { submission: SubmissionId, round: u32, reviewer: T::AccountId },
+		/// A reviewer recorded their comment set.
+		CommentsRecorded { submission: SubmissionId, round: u32, comments: T::Hash },
+		/// A review round closed.
+		RoundClosed { submission: SubmissionId, round: u32, outcome: ReviewOutcome },
+		/// The project owner withdrew a submission.
+		Withdrawn { submission: SubmissionId },
+		/// The project owner reclaimed the fee of a submission that was not
+		/// reviewed in time, withdrawing it.
+		FeeReclaimed { submission: SubmissionId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The reviewer is already authorized by the board.
+		AlreadyAuthorized,
+		/// The reviewer is not authorized by the board.
+		NotAuthorized,
+		/// No project with this identifier exists.
+		UnknownProject,
+		/// Only the project owner may do this.
+		NotOwner,
+		/// The document is not attached to the project.
+		UnknownDocument,
+		/// A later revision of the document has been attached to the project.
+		Superseded,
+		/// The discipline already has a submission under review.
+		SubmissionOpen,
+		/// No submission with this identifier exists.
+		UnknownSubmission,
+		/// The submission is not waiting for a reviewer.
+		NotPending,
+		/// The submission has no round open.
+		NotInReview,
+		/// No changes were asked for the submission.
+		NoChangesRequested,
+		/// The document does not supersede the one under review.
+		NotRevision,
+		/// The reviewer holds no active license from the project's jurisdiction
+		/// and is not authorized by its board.
+		NotQualified,
+		/// Reviewers may not review projects they own or documents they sealed.
+		ConflictOfInterest,
+		/// The submission is reviewed by someone else.
+		NotReviewer,
+		/// Changes can only be asked for, or a document rejected, with comments.
+		CommentsRequired,
+		/// The submission can no longer be withdrawn.
+		CannotWithdraw,
+		/// The submission is not overdue for review.
+		NotOverdue,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Authorize `reviewer` to review the projects of the board's
+		/// jurisdiction without being its licensee.
+		///
+		/// The origin must be the board, with the approval it requires for
+		/// `AuthorizeReviewer`.
+		#[pallet::weight(<T as Config>::WeightInfo::authorize_reviewer())]
+		pub fn authorize_reviewer(
+			origin: OriginFor<T>,
+			reviewer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let board = pallet_licensure::Pallet::<T>::ensure_board(
+				origin,
+				LicensureAction::AuthorizeReviewer,
+			)?;
+			let reviewer = T::Lookup::lookup(reviewer)?;
+			ensure!(!Reviewers::<T>::contains_key(board, &reviewer), Error::<T>::AlreadyAuthorized);
+
+			Reviewers::<T>::insert(board, &reviewer, ());
+
+			Self::deposit_event(Event::ReviewerAuthorized { board, reviewer });
+			Ok(())
+		}
+
+		/// Revoke the authorization of `reviewer`. Rounds they opened stay open
+		/// until they close them.
+		///
+		/// The origin must be the board, with the approval it requires for
+		/// `AuthorizeReviewer`.
+		#[pallet::weight(<T as Config>::WeightInfo::revoke_reviewer())]
+		pub fn revoke_reviewer(
+			origin: OriginFor<T>,
+			reviewer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let board = pallet_licensure::Pallet::<T>::ensure_board(
+				origin,
+				LicensureAction::AuthorizeReviewer,
+			)?;
+			let reviewer = T::Lookup::lookup(reviewer)?;
+			ensure!(Reviewers::<T>::contains_key(board, &reviewer), Error::<T>::NotAuthorized);
+
+			Reviewers::<T>::remove(board, &reviewer);
+
+			Self::deposit_event(Event::ReviewerRevoked { board, reviewer });
+			Ok(())
+		}
+
+		/// Submit `document` of `project` for review, reserving `fee` from the
+		/// origin.
+		///
+		/// The origin must own the project. The document must be the latest
+		/// revision attached to the project, and its discipline may not have
+		/// another submission under review.
+		#[pallet::weight(<T as Config>::WeightInfo::submit())]
+		pub fn submit(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			document: T::Hash,
+			#[pallet::compact] fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = Projects::<T>::get(project).ok_or(Error::<T>::UnknownProject)?;
+			ensure!(info.owner == who, Error::<T>::NotOwner);
+			let doc = Documents::<T>::get(project, document).ok_or(Error::<T>::UnknownDocument)?;
+			ensure!(doc.superseded_by.is_none(), Error::<T>::Superseded);
+			if let Some(latest) = LatestSubmissions::<T>::get(project, doc.discipline) {
+				let status = Submissions::<T>::get(latest).map(|submission| submission.status);
+				ensure!(
+					status.map_or(true, |status| status.is_final()),
+					Error::<T>::SubmissionOpen
+				);
+			}
+
+			<T as Config>::Currency::reserve_named(&<T as Config>::ReserveId::get(), &who, fee)?;
+			let submission = NextSubmissionId::<T>::mutate(|id| {
+				let submission = *id;
+				*id = id.saturating_add(1);
+				submission
+			});
+			let now = frame_system::Pallet::<T>::block_number();
+			LatestSubmissions::<T>::insert(project, doc.discipline, submission);
+			Submissions::<T>::insert(
+				submission,
+				Submission {
+					project,
+					discipline: doc.discipline,
+					document,
+					submitter: who,
+					fee,
+					reviewer: None,
+					rounds: 0,
+					status: SubmissionStatus::Pending,
+					submitted_at: now,
+					review_by: now.saturating_add(T::ReviewPeriod::get()),
+				},
+			);
+
+			Self::deposit_event(Event::Submitted { submission, project, document, fee });
+			Ok(())
+		}
+
+		/// Submit `document`, a revision superseding the document of
+		/// `submission`, for another round after changes were asked.
+		///
+		/// The origin must be the submitter.
+		#[pallet::weight(<T as Config>::WeightInfo::resubmit())]
+		pub fn resubmit(
+			origin: OriginFor<T>,
+			submission: SubmissionId,
+			document: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info =
+				Submissions::<T>::get(submission).ok_or(Error::<T>::UnknownSubmission)?;
+			ensure!(info.submitter == who, Error::<T>::NotOwner);
+			ensure!(
+				info.status == SubmissionStatus::ChangesRequested,
+				Error::<T>::NoChangesRequested
+			);
+			let doc =
+				Documents::<T>::get(info.project, document).ok_or(Error::<T>::UnknownDocument)?;
+			ensure!(doc.supersedes == Some(info.document), Error::<T>::NotRevision);
+			ensure!(doc.superseded_by.is_none(), Error::<T>::Superseded);
+
+			info.document = document;
+			info.status = SubmissionStatus::Pending;
+			info.review_by =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::ReviewPeriod::get());
+			Submissions::<T>::insert(submission, info);
+
+			Self::deposit_event(Event::Resubmitted { submission, document });
+			Ok(())
+		}
+
+		/// Open a review round of `submission`.
+		///
+		/// The origin must hold an active license from the project's
+		/// jurisdiction or be authorized by its board, and may neither own the
+		/// project nor have sealed the document. Once a reviewer opened the first
+		/// round, only they open the next ones.
+		#[pallet::weight(<T as Config>::WeightInfo::open_round())]
+		pub fn open_round(origin: OriginFor<T>, submission: SubmissionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info =
+				Submissions::<T>::get(submission).ok_or(Error::<T>::UnknownSubmission)?;
+			ensure!(info.status == SubmissionStatus::Pending, Error::<T>::NotPending);
+			ensure!(
+				info.reviewer.as_ref().map_or(true, |reviewer| *reviewer == who),
+				Error::<T>::NotReviewer
+			);
+			Self::ensure_qualified(&who, &info)?;
+
+			let round = info.rounds;
+			info.reviewer = Some(who.clone());
+			info.rounds = info.rounds.saturating_add(1);
+			info.status = SubmissionStatus::InReview;
+			Rounds::<T>::insert(
+				submission,
+				round,
+				ReviewRound {
+					reviewer: who.clone(),
+					document: info.document,
+					comments: None,
+					outcome: None,
+					opened_at: frame_system::Pallet::<T>::block_number(),
+					closed_at: None,
+				},
+			);
+			Submissions::<T>::insert(submission, info);
+
+			Self::deposit_event(Event::RoundOpened { submission, round, reviewer: who });
+			Ok(())
+		}
+
+		/// Record `comments`, the hash of the comment set of the open round of
+		/// `submission`, replacing comments recorded before.
+		///
+		/// The origin must be the reviewer of the submission.
+		#[pallet::weight(<T as Config>::WeightInfo::record_comments())]
+		pub fn record_comments(
+			origin: OriginFor<T>,
+			submission: SubmissionId,
+			comments: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (_, round) = Self::open_round_of(submission, &who)?;
+
+			Rounds::<T>::mutate(submission, round, |entry| {
+				if let Some(entry) = entry {
+					entry.comments = Some(comments);
+				}
+			});
+
+			Self::deposit_event(Event::CommentsRecorded { submission, round, comments });
+			Ok(())
+		}
+
+		/// Close the open round of `submission` with `outcome`. Rejecting the
+		/// document or asking for changes needs comments recorded for the
+		/// round. Approving or rejecting the document ends the review and pays
+		/// the fee to the reviewer.
+		///
+		/// The origin must be the reviewer of the submission.
+		#[pallet::weight(<T as Config>::WeightInfo::close_round())]
+		pub fn close_round(
+			origin: OriginFor<T>,
+			submission: SubmissionId,
+			outcome: ReviewOutcome,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let (mut info, round) = Self::open_round_of(submission, &who)?;
+			let mut entry = Rounds::<T>::get(submission, round).ok_or(Error::<T>::NotInReview)?;
+			ensure!(
+				outcome == ReviewOutcome::Approved || entry.comments.is_some(),
+				Error::<T>::CommentsRequired
+			);
+
+			entry.outcome = Some(outcome);
+			entry.closed_at = Some(frame_system::Pallet::<T>::block_number());
+			Rounds::<T>::insert(submission, round, entry);
+			info.status = match outcome {
+				ReviewOutcome::Approved => SubmissionStatus::Approved,
+				ReviewOutcome::ChangesRequested => SubmissionStatus::ChangesRequested,
+				ReviewOutcome::Rejected => SubmissionStatus::Rejected,
+			};
+			if info.status.is_final() {
+				Self::pay_fee(&info, &who);
+			}
+			Submissions::<T>::insert(submission, info);
+
+			Self::deposit_event(Event::RoundClosed { submission, round, outcome });
+			Ok(())
+		}
+
+		/// Withdraw `submission` while no round is open. The fee is refunded if
+		/// no round was held, and paid to the reviewer otherwise.
+		///
+		/// The origin must be the submitter.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw())]
+		pub fn withdraw(origin: OriginFor<T>, submission: SubmissionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info =
+				Submissions::<T>::get(submission).ok_or(Error::<T>::UnknownSubmission)?;
+			ensure!(info.submitter == who, Error::<T>::NotOwner);
+			ensure!(
+				matches!(
+					info.status,
+					SubmissionStatus::Pending | SubmissionStatus::ChangesRequested
+				),
+				Error::<T>::CannotWithdraw
+			);
+
+			match info.reviewer.clone() {
+				Some(reviewer) => Self::pay_fee(&info, &reviewer),
+				None => {
+					<T as Config>::Currency::unreserve_named(
+						&<T as Config>::ReserveId::get(),
+						&who,
+						info.fee,
+					);
+				},
+			}
+			info.status = SubmissionStatus::Withdrawn;
+			Submissions::<T>::insert(submission, info);
+
+			Self::deposit_event(Event::Withdrawn { submission });
+			Ok(())
+		}
+
+		/// Reclaim the fee of `submission`, which is waiting for a reviewer or
+		/// in review past its deadline, and withdraw it. An open round is closed
+		/// without an outcome.
+		///
+		/// The origin must be the submitter.
+		#[pallet::weight(<T as Config>::WeightInfo::reclaim_fee())]
+		pub fn reclaim_fee(origin: OriginFor<T>, submission: SubmissionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info =
+				Submissions::<T>::get(submission).ok_or(Error::<T>::UnknownSubmission)?;
+			ensure!(info.submitter == who, Error::<T>::NotOwner);
+			ensure!(
+				matches!(info.status, SubmissionStatus::Pending | SubmissionStatus::InReview),
+				Error::<T>::CannotWithdraw
+			);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > info.review_by, Error::<T>::NotOverdue);
+
+			if info.status == SubmissionStatus::InReview {
+				let round = info.rounds.saturating_sub(1);
+				Rounds::<T>::mutate(submission, round, |entry| {
+					if let Some(entry) = entry {
+						entry.closed_at = Some(now);
+					}
+				});
+			}
+			<T as Config>::Currency::unreserve_named(
+				&<T as Config>::ReserveId::get(),
+				&who,
+				info.fee,
+			);
+			info.status = SubmissionStatus::Withdrawn;
+			Submissions::<T>::insert(submission, info);
+
+			Self::deposit_event(Event::FeeReclaimed { submission });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure `who` may review `submission`.
+	fn ensure_qualified(who: &T::AccountId, submission: &SubmissionOf<T>) -> DispatchResult {
+		let project = Projects::<T>::get(submission.project).ok_or(Error::<T>::UnknownProject)?;
+		let document = Documents::<T>::get(submission.project, submission.document)
+			.ok_or(Error::<T>::UnknownDocument)?;
+		ensure!(project.owner != *who && document.engineer != *who, Error::<T>::ConflictOfInterest);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(
+			Reviewers::<T>::contains_key(project.jurisdiction, who) ||
+				pallet_licensure::Pallet::<T>::has_active_license(
+					who,
+					&project.jurisdiction,
+					&now,
+				),
+			Error::<T>::NotQualified
+		);
+		Ok(())
+	}
+
+	/// Submission `submission` and the position of its open round, if `who`
+	/// reviews it.
+	fn open_round_of(
+		submission: SubmissionId,
+		who: &T::AccountId,
+	) -> Result<(SubmissionOf<T>, u32), DispatchError> {
+		let info = Submissions::<T>::get(submission).ok_or(Error::<T>::UnknownSubmission)?;
+		ensure!(info.status == SubmissionStatus::InReview, Error::<T>::NotInReview);
+		ensure!(info.reviewer.as_ref() == Some(who), Error::<T>::NotReviewer);
+		let round = info.rounds.saturating_sub(1);
+		Ok((info, round))
+	}
+
+	/// Pay the fee of `submission`, reserved from the submitter, to `reviewer`.
+	fn pay_fee(submission: &SubmissionOf<T>, reviewer: &T::AccountId) {
+		// The fee was reserved on submission. Whatever part of it has been
+		// slashed since is lost to the reviewer.
+		let _ = <T as Config>::Currency::repatriate_reserved_named(
+			&<T as Config>::ReserveId::get(),
+			&submission.submitter,
+			reviewer,
+			submission.fee,
+			BalanceStatus::Free,
+		);
+	}
+
+	/// Plan review status of `project`, `None` if no such project exists.
+	pub fn permit_status(project: ProjectId) -> Option<PermitStatus<T::Hash>> {
+		if !Projects::<T>::contains_key(project) {
+			return None
+		}
+
+		let disciplines: Vec<_> = EngineersOfRecord::<T>::iter_prefix(project)
+			.map(|(discipline, _)| {
+				let submission = LatestSubmissions::<T>::get(project, discipline);
+				let info = submission.and_then(Submissions::<T>::get);
+				let state =
+					info.as_ref().map_or(ReviewState::NotSubmitted, |info| match info.status {
+						SubmissionStatus::Pending => ReviewState::Pending,
+						SubmissionStatus::InReview => ReviewState::InReview,
+						SubmissionStatus::ChangesRequested => ReviewState::ChangesRequested,
+						SubmissionStatus::Rejected => ReviewState::Rejected,
+						SubmissionStatus::Withdrawn => ReviewState::NotSubmitted,
+						SubmissionStatus::Approved => {
+							let superseded = Documents::<T>::get(project, info.document)
+								.map_or(true, |doc| doc.superseded_by.is_some());
+							if superseded {
+								ReviewState::Outdated
+							} else {
+								ReviewState::Approved
+							}
+						},
+					});
+				DisciplineReview {
+					discipline,
+					state,
+					submission,
+					document: info.map(|info| info.document),
+				}
+			})
+			.collect();
+
+		Some(PermitStatus {
+			approved: !disciplines.is_empty() &&
+				disciplines.iter().all(|review| review.state == ReviewState::Approved),
+			disciplines,
+		})
+	}
+}
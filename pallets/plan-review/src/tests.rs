@@ -0,0 +1,181 @@
+//! Tests of the plan review pallet.
+
+use crate::{mock::*, Error, ReviewOutcome, Reviewers, SubmissionStatus};
+use frame_support::{assert_noop, assert_ok};
+use pallet_licensure::{ApprovalRule, Discipline, LicensureAction};
+use sp_core::H256;
+
+const DOCUMENT: H256 = H256([1; 32]);
+const REVISION: H256 = H256([2; 32]);
+const FEE: u64 = 50;
+
+/// Register project 0 with `DOCUMENT` attached by the engineer, and submit it
+/// for review as submission 0.
+fn submitted() -> u32 {
+	assert_ok!(Projects::register_project(
+		Origin::signed(OWNER),
+		b"Bridge".to_vec(),
+		b"Richmond".to_vec(),
+		1,
+		b"P-1".to_vec(),
+	));
+	assert_ok!(Projects::assign_engineer(
+		Origin::signed(OWNER),
+		0,
+		Discipline::Structural,
+		ENGINEER
+	));
+	assert_ok!(Projects::accept_assignment(
+		Origin::signed(ENGINEER),
+		0,
+		Discipline::Structural,
+		b"PE-1".to_vec(),
+	));
+	attach(DOCUMENT, None);
+	assert_ok!(PlanReview::submit(Origin::signed(OWNER), 0, DOCUMENT, FEE));
+	0
+}
+
+fn attach(document: H256, supersedes: Option<H256>) {
+	assert_ok!(Licensure::affix_seal(Origin::signed(ENGINEER), b"PE-1".to_vec(), document));
+	assert_ok!(Projects::attach_document(
+		Origin::signed(ENGINEER),
+		0,
+		Discipline::Structural,
+		document,
+		supersedes,
+	));
+}
+
+#[test]
+fn reviews_pay_the_fee_to_the_reviewer() {
+	new_test_ext().execute_with(|| {
+		let submission = submitted();
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT + FEE);
+
+		assert_ok!(PlanReview::open_round(Origin::signed(REVIEWER), submission));
+		assert_ok!(PlanReview::close_round(
+			Origin::signed(REVIEWER),
+			submission,
+			ReviewOutcome::Approved
+		));
+
+		assert_eq!(PlanReview::submission(submission).unwrap().status, SubmissionStatus::Approved);
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+		assert_eq!(Balances::free_balance(REVIEWER), 10 + FEE);
+		assert!(PlanReview::permit_status(0).unwrap().approved);
+	});
+}
+
+#[test]
+fn reviewers_need_a_license_or_the_boards_authorization() {
+	new_test_ext().execute_with(|| {
+		let submission = submitted();
+
+		assert_noop!(
+			PlanReview::open_round(Origin::signed(OUTSIDER), submission),
+			Error::<Test>::NotQualified
+		);
+		assert_noop!(
+			PlanReview::open_round(Origin::signed(ENGINEER), submission),
+			Error::<Test>::ConflictOfInterest
+		);
+
+		assert_ok!(PlanReview::authorize_reviewer(motion(1, 3), OUTSIDER));
+		assert_ok!(PlanReview::open_round(Origin::signed(OUTSIDER), submission));
+	});
+}
+
+#[test]
+fn authorizing_reviewers_needs_the_boards_approval() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PlanReview::authorize_reviewer(motion(1, 2), OUTSIDER),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_noop!(
+			PlanReview::authorize_reviewer(executive_director(1), OUTSIDER),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_ok!(PlanReview::authorize_reviewer(motion(1, 3), OUTSIDER));
+		assert!(Reviewers::<Test>::contains_key(1, OUTSIDER));
+
+		assert_ok!(Licensure::set_action_rule(
+			Origin::root(),
+			1,
+			LicensureAction::AuthorizeReviewer,
+			ApprovalRule::ExecutiveDirector,
+		));
+		assert_noop!(
+			PlanReview::revoke_reviewer(motion(1, MEMBERS), OUTSIDER),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_ok!(PlanReview::revoke_reviewer(executive_director(1), OUTSIDER));
+		assert!(!Reviewers::<Test>::contains_key(1, OUTSIDER));
+	});
+}
+
+#[test]
+fn fees_of_overdue_reviews_are_reclaimed() {
+	new_test_ext().execute_with(|| {
+		let submission = submitted();
+		assert_ok!(PlanReview::open_round(Origin::signed(REVIEWER), submission));
+
+		System::set_block_number(1 + REVIEW_PERIOD);
+		assert_noop!(
+			PlanReview::reclaim_fee(Origin::signed(OWNER), submission),
+			Error::<Test>::NotOverdue
+		);
+		System::set_block_number(2 + REVIEW_PERIOD);
+		assert_noop!(
+			PlanReview::reclaim_fee(Origin::signed(REVIEWER), submission),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(PlanReview::reclaim_fee(Origin::signed(OWNER), submission));
+
+		assert_eq!(PlanReview::submission(submission).unwrap().status, SubmissionStatus::Withdrawn);
+		assert_eq!(PlanReview::round(submission, 0).unwrap().closed_at, Some(2 + REVIEW_PERIOD));
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+		assert_eq!(Balances::free_balance(REVIEWER), 10);
+		assert_noop!(
+			PlanReview::close_round(Origin::signed(REVIEWER), submission, ReviewOutcome::Approved),
+			Error::<Test>::NotInReview
+		);
+	});
+}
+
+#[test]
+fn resubmissions_renew_the_deadline() {
+	new_test_ext().execute_with(|| {
+		let submission = submitted();
+		assert_ok!(PlanReview::open_round(Origin::signed(REVIEWER), submission));
+		assert_ok!(PlanReview::record_comments(
+			Origin::signed(REVIEWER),
+			submission,
+			H256([9; 32])
+		));
+		assert_ok!(PlanReview::close_round(
+			Origin::signed(REVIEWER),
+			submission,
+			ReviewOutcome::ChangesRequested
+		));
+
+		// Waiting for the owner's revision is not the reviewer's delay.
+		System::set_block_number(2 + REVIEW_PERIOD);
+		assert_noop!(
+			PlanReview::reclaim_fee(Origin::signed(OWNER), submission),
+			Error::<Test>::CannotWithdraw
+		);
+
+		attach(REVISION, Some(DOCUMENT));
+		assert_ok!(PlanReview::resubmit(Origin::signed(OWNER), submission, REVISION));
+		System::set_block_number(2 + 2 * REVIEW_PERIOD);
+		assert_noop!(
+			PlanReview::reclaim_fee(Origin::signed(OWNER), submission),
+			Error::<Test>::NotOverdue
+		);
+		System::set_block_number(3 + 2 * REVIEW_PERIOD);
+		assert_ok!(PlanReview::reclaim_fee(Origin::signed(OWNER), submission));
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+	});
+}
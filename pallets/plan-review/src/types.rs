@@ -0,0 +1,126 @@
+//! Records kept by the plan review pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use pallet_licensure::Discipline;
+use pallet_projects::ProjectId;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Identifier of a submission for review.
+pub type SubmissionId = u32;
+
+/// Where a submission stands in its review.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum SubmissionStatus {
+	/// Waiting for a reviewer to open a round.
+	Pending,
+	/// A round is open.
+	InReview,
+	/// The last round asked for a revision of the document.
+	ChangesRequested,
+	/// The document was approved. Final.
+	Approved,
+	/// The document was rejected. Final.
+	Rejected,
+	/// The project owner withdrew the submission. Final.
+	Withdrawn,
+}
+
+impl SubmissionStatus {
+	/// Whether the review is over.
+	pub fn is_final(&self) -> bool {
+		matches!(self, Self::Approved | Self::Rejected | Self::Withdrawn)
+	}
+}
+
+/// A sealed document submitted by a project owner for plan review.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Submission<AccountId, Balance, BlockNumber, Hash> {
+	pub project: ProjectId,
+	pub discipline: Discipline,
+	/// Document under review, the latest revision submitted.
+	pub document: Hash,
+	/// Project owner who submitted the document and pays the fee.
+	pub submitter: AccountId,
+	/// Review fee, reserved from the submitter until the review is over.
+	pub fee: Balance,
+	/// Reviewer who opened the first round. Later rounds are theirs too.
+	pub reviewer: Option<AccountId>,
+	/// Number of rounds opened.
+	pub rounds: u32,
+	pub status: SubmissionStatus,
+	pub submitted_at: BlockNumber,
+	/// Block by which a round must be closed, or the submitter may reclaim the
+	/// fee. Renewed on resubmission.
+	pub review_by: BlockNumber,
+}
+
+/// Decision closing a review round.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ReviewOutcome {
+	Approved,
+	/// The document needs a revision, per the comments of the round.
+	ChangesRequested,
+	Rejected,
+}
+
+/// A round of review of one revision of a submitted document.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ReviewRound<AccountId, BlockNumber, Hash> {
+	pub reviewer: AccountId,
+	/// Revision of the document reviewed.
+	pub document: Hash,
+	/// Hash of the reviewer's comment set, kept off chain.
+	pub comments: Option<Hash>,
+	pub outcome: Option<ReviewOutcome>,
+	pub opened_at: BlockNumber,
+	pub closed_at: Option<BlockNumber>,
+}
+
+/// Where the review of one discipline of a project stands.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ReviewState {
+	/// No submission under review or approved.
+	NotSubmitted,
+	Pending,
+	InReview,
+	ChangesRequested,
+	Rejected,
+	Approved,
+	/// The approved document has since been superseded, and the new revision
+	/// needs review.
+	Outdated,
+}
+
+/// Review of one discipline of a project.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DisciplineReview<Hash> {
+	pub discipline: Discipline,
+	pub state: ReviewState,
+	/// Latest submission for the discipline.
+	pub submission: Option<SubmissionId>,
+	/// Document of that submission.
+	pub document: Option<Hash>,
+}
+
+/// Plan review status of a project, as reported to permitting offices.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PermitStatus<Hash> {
+	/// Every discipline with an engineer of record has its latest document
+	/// approved.
+	pub approved: bool,
+	pub disciplines: Vec<DisciplineReview<Hash>>,
+}
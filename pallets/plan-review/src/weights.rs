@@ -0,0 +1,158 @@
+//! Weights for pallet_plan_review.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_plan_review.
+pub trait WeightInfo {
+	fn authorize_reviewer() -> Weight;
+	fn revoke_reviewer() -> Weight;
+	fn submit() -> Weight;
+	fn resubmit() -> Weight;
+	fn open_round() -> Weight;
+	fn record_comments() -> Weight;
+	fn close_round() -> Weight;
+	fn withdraw() -> Weight;
+	fn reclaim_fee() -> Weight;
+}
+
+/// Weights for pallet_plan_review using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Licensure ActionRules (r:1 w:0)
+	// Storage: PlanReview Reviewers (r:1 w:1)
+	fn authorize_reviewer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure ActionRules (r:1 w:0)
+	// Storage: PlanReview Reviewers (r:1 w:1)
+	fn revoke_reviewer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:0)
+	// Storage: Projects Documents (r:1 w:0)
+	// Storage: PlanReview LatestSubmissions (r:1 w:1)
+	// Storage: PlanReview Submissions (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: PlanReview NextSubmissionId (r:1 w:1)
+	fn submit() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PlanReview Submissions (r:1 w:1)
+	// Storage: Projects Documents (r:1 w:0)
+	fn resubmit() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PlanReview Submissions (r:1 w:1)
+	// Storage: Projects Projects (r:1 w:0)
+	// Storage: Projects Documents (r:1 w:0)
+	// Storage: PlanReview Reviewers (r:1 w:0)
+	// Storage: Licensure LicensesOf (r:1 w:0)
+	// Storage: Licensure Licenses (r:16 w:0)
+	// Storage: PlanReview Rounds (r:0 w:1)
+	fn open_round() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: PlanReview Submissions (r:1 w:0)
+	// Storage: PlanReview Rounds (r:1 w:1)
+	fn record_comments() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: PlanReview Submissions (r:1 w:1)
+	// Storage: PlanReview Rounds (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn close_round() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: PlanReview Submissions (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: PlanReview Submissions (r:1 w:1)
+	// Storage: PlanReview Rounds (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reclaim_fee() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn authorize_reviewer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_reviewer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn submit() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn resubmit() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn open_round() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn record_comments() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn close_round() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn withdraw() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn reclaim_fee() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
@@ -0,0 +1,538 @@
+//! # Polls Pallet
+//!
+//! Polls of licensed engineers, in which voting power comes from license
+//! standing rather than balance: every account holding an active license casts
+//! one vote, however many licenses it holds and whatever its balance.
+//!
+//! The council opens polls of every licensee, and a board polls its own
+//! licensees. A poll's outcome is either advisory, only recorded for the
+//! collective which opened it, or binding: the call it carries is dispatched
+//! with the collective's origin once the ayes carry the poll, as if the
+//! collective had passed the motion itself.
+//!
+//! A binding poll only passes if at least `BindingQuorum` votes were counted,
+//! so that a handful of licensees cannot enact a call nobody else voted on.
+//!
+//! Ballots are open, sealed or secret. With sealed ballots voters first commit
+//! to the hash of their vote and only reveal it once voting is over, so that no
+//! running tally can be read and nobody votes with the trend. Storage only
+//! keeps the tally and whether each account voted, but the side of open and
+//! sealed votes appears in the arguments of the vote or reveal extrinsic.
+//!
+//! Secret ballots keep the side from everybody but a tallier appointed by the
+//! council. Voters encrypt their vote to the tallier's key, and once voting is
+//! over the tallier decrypts the ballots off chain and publishes the tally.
+//! The chain checks that the tally counts no more votes than ballots were cast,
+//! but has to trust the tallier to count them faithfully and to keep the
+//! votes to itself, which is why polls with secret ballots are only advisory.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	dispatch::{DispatchResult, Dispatchable, GetDispatchInfo},
+	traits::OriginTrait,
+	weights::{extract_actual_weight, PostDispatchInfo},
+};
+use sp_runtime::traits::Hash as HashT;
+
+/// Which accounts may vote, provided by the runtime on top of its licensure
+/// pallet.
+pub trait Licensees<AccountId, BoardId> {
+	/// Whether `who` holds an active license from `board`, or from any board if
+	/// `board` is `None`.
+	fn is_licensee(who: &AccountId, board: Option<BoardId>) -> bool;
+}
+
+pub type PollOf<T> = Poll<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+	<T as Config>::PalletsOrigin,
+	<T as Config>::Call,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::without_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The aggregated origin which binding outcomes are dispatched with.
+		type Origin: OriginTrait<PalletsOrigin = Self::PalletsOrigin>
+			+ From<Self::PalletsOrigin>
+			+ IsType<<Self as frame_system::Config>::Origin>;
+
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>> + Parameter;
+
+		/// The calls binding outcomes carry.
+		type Call: Parameter
+			+ Dispatchable<Origin = <Self as Config>::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// Identifies a licensing board.
+		type BoardId: Parameter + Member + Copy;
+
+		/// Origin of the council, which polls every licensee.
+		type CouncilOrigin: EnsureOrigin<<Self as frame_system::Config>::Origin>;
+
+		/// Origin of a board, which polls its own licensees, yielding which
+		/// board it speaks for.
+		type BoardOrigin: EnsureOrigin<
+			<Self as frame_system::Config>::Origin,
+			Success = Self::BoardId,
+		>;
+
+		/// Accounts allowed to vote.
+		type Licensees: Licensees<Self::AccountId, Self::BoardId>;
+
+		/// Shortest time a poll is open for votes.
+		#[pallet::constant]
+		type MinVotingPeriod: Get<Self::BlockNumber>;
+
+		/// Time given to reveal sealed votes, or to tally secret votes, once
+		/// voting is over.
+		#[pallet::constant]
+		type RevealPeriod: Get<Self::BlockNumber>;
+
+		/// Fewest votes a binding poll must count to pass.
+		#[pallet::constant]
+		type BindingQuorum: Get<u32>;
+
+		/// Maximum length of an encrypted secret ballot.
+		#[pallet::constant]
+		type MaxBallotLength: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Number of polls opened so far, and index of the next one.
+	#[pallet::storage]
+	#[pallet::getter(fn poll_count)]
+	pub type PollCount<T: Config> = StorageValue<_, PollIndex, ValueQuery>;
+
+	/// Polls not closed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn poll)]
+	pub type Polls<T: Config> = StorageMap<_, Twox64Concat, PollIndex, PollOf<T>, OptionQuery>;
+
+	/// Ballots of each poll by voter.
+	#[pallet::storage]
+	pub type Ballots<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PollIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		Ballot<T::Hash>,
+		OptionQuery,
+	>;
+
+	/// Encrypted votes of polls with secret ballots, by voter.
+	#[pallet::storage]
+	pub type SecretVotes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PollIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<u8, T::MaxBallotLength>,
+		OptionQuery,
+	>;
+
+	/// The tallier of polls with secret ballots opened from now on.
+	#[pallet::storage]
+	#[pallet::getter(fn tallier)]
+	pub type CurrentTallier<T: Config> = StorageValue<_, Tallier<T::AccountId>, OptionQuery>;
+
+	/// Final tally of closed polls.
+	#[pallet::storage]
+	#[pallet::getter(fn result)]
+	pub type Results<T: Config> = StorageMap<_, Twox64Concat, PollIndex, Tally, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A poll was opened.
+		PollOpened {
+			poll: PollIndex,
+			scope: Option<T::BoardId>,
+			question: T::Hash,
+			binding: bool,
+			voting_ends: T::BlockNumber,
+		},
+		/// A licensee voted, or committed to a sealed vote.
+		Voted { poll: PollIndex, who: T::AccountId },
+		/// A sealed vote was revealed and counted.
+		VoteRevealed { poll: PollIndex, who: T::AccountId },
+		/// The tallier published the tally of a poll with secret ballots.
+		TallyPublished { poll: PollIndex, tally: Tally },
+		/// The council appointed a tallier of secret ballots, or removed it.
+		TallierSet { tallier: Option<T::AccountId> },
+		/// A poll was closed with its final tally.
+		PollClosed { poll: PollIndex, tally: Tally, approved: bool },
+		/// The call of a binding poll was dispatched.
+		OutcomeEnacted { poll: PollIndex, result: DispatchResult },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No open poll with this index.
+		UnknownPoll,
+		/// The board may only poll its own licensees.
+		WrongBoard,
+		/// The voting period is shorter than `MinVotingPeriod`.
+		VotingPeriodTooShort,
+		/// The account holds no active license within the poll's scope.
+		NotLicensee,
+		/// Voting on the poll is over.
+		VotingClosed,
+		/// The account already voted on the poll.
+		AlreadyVoted,
+		/// The poll takes sealed votes.
+		SealedBallot,
+		/// The poll takes open votes.
+		OpenBallot,
+		/// The poll takes secret votes.
+		SecretBallot,
+		/// Sealed votes cannot be revealed, or secret votes tallied, at this
+		/// time.
+		NotRevealPeriod,
+		/// The account did not commit to a vote on the poll.
+		NoCommitment,
+		/// The revealed vote does not match the commitment.
+		CommitmentMismatch,
+		/// The poll cannot be closed before its reveal period is over.
+		PollOngoing,
+		/// The call of the poll weighs more than the given bound.
+		WrongCallWeightBound,
+		/// No tallier is appointed to count secret ballots.
+		NoTallier,
+		/// Only the tallier of the poll may publish its tally.
+		NotTallier,
+		/// The tally of the poll was published already.
+		AlreadyTallied,
+		/// The tally counts more votes than ballots were cast.
+		TallyExceedsBallots,
+		/// Polls with secret ballots cannot be binding, the chain cannot check
+		/// their tally.
+		SecretBinding,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Open a poll on `question`, the hash of the question published off
+		/// chain, lasting `voting_period` blocks.
+		///
+		/// The origin must be `CouncilOrigin` to poll every licensee, with
+		/// `scope` set to `None`, or `BoardOrigin` of `scope` to poll the
+		/// board's own licensees. A `Binding` outcome is dispatched with that
+		/// origin if the poll passes, and needs open or sealed ballots.
+		#[pallet::weight(T::WeightInfo::open_poll())]
+		pub fn open_poll(
+			origin: OriginFor<T>,
+			scope: Option<T::BoardId>,
+			question: T::Hash,
+			outcome: Outcome<<T as Config>::Call>,
+			ballot: BallotKind,
+			voting_period: T::BlockNumber,
+		) -> DispatchResult {
+			match scope {
+				None => {
+					T::CouncilOrigin::ensure_origin(origin.clone())?;
+				},
+				Some(board) => {
+					let caller = T::BoardOrigin::ensure_origin(origin.clone())?;
+					ensure!(caller == board, Error::<T>::WrongBoard);
+				},
+			}
+			ensure!(voting_period >= T::MinVotingPeriod::get(), Error::<T>::VotingPeriodTooShort);
+			let binding = matches!(outcome, Outcome::Binding(_));
+			ensure!(!binding || ballot != BallotKind::Secret, Error::<T>::SecretBinding);
+			let origin = <T as Config>::Origin::from(origin);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let voting_ends = now.saturating_add(voting_period);
+			let reveal_ends = match ballot {
+				BallotKind::Open => voting_ends,
+				BallotKind::Sealed | BallotKind::Secret =>
+					voting_ends.saturating_add(T::RevealPeriod::get()),
+			};
+			let secret = match ballot {
+				BallotKind::Secret => Some(SecretBallots {
+					tallier: CurrentTallier::<T>::get().ok_or(Error::<T>::NoTallier)?,
+					cast: 0,
+					tallied: false,
+				}),
+				_ => None,
+			};
+
+			let poll = PollCount::<T>::get();
+			PollCount::<T>::put(poll.saturating_add(1));
+			Polls::<T>::insert(
+				poll,
+				Poll {
+					scope,
+					question,
+					outcome,
+					origin: origin.caller().clone(),
+					ballot,
+					voting_ends,
+					reveal_ends,
+					tally: Tally::default(),
+					secret,
+				},
+			);
+
+			Self::deposit_event(Event::PollOpened { poll, scope, question, binding, voting_ends });
+			Ok(())
+		}
+
+		/// Vote on a poll with an open ballot, for if `aye`, against otherwise.
+		///
+		/// The origin must hold an active license within the poll's scope.
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(origin: OriginFor<T>, poll: PollIndex, aye: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Self::ensure_can_vote(poll, &who)?;
+			Self::ensure_ballot(&info, BallotKind::Open)?;
+
+			Self::count(&mut info.tally, aye);
+			Polls::<T>::insert(poll, info);
+			Ballots::<T>::insert(poll, &who, Ballot::Counted);
+
+			Self::deposit_event(Event::Voted { poll, who });
+			Ok(())
+		}
+
+		/// Commit to a vote on a poll with a sealed ballot. `commitment` is the
+		/// hash of the SCALE encoded `(who, poll, aye, salt)`, where `salt` is a
+		/// random 32 byte value kept secret until the vote is revealed.
+		///
+		/// The origin must hold an active license within the poll's scope.
+		#[pallet::weight(T::WeightInfo::commit_vote())]
+		pub fn commit_vote(
+			origin: OriginFor<T>,
+			poll: PollIndex,
+			commitment: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = Self::ensure_can_vote(poll, &who)?;
+			Self::ensure_ballot(&info, BallotKind::Sealed)?;
+
+			Ballots::<T>::insert(poll, &who, Ballot::Committed(commitment));
+
+			Self::deposit_event(Event::Voted { poll, who });
+			Ok(())
+		}
+
+		/// Reveal a sealed vote once voting is over, so that it is counted.
+		#[pallet::weight(T::WeightInfo::reveal_vote())]
+		pub fn reveal_vote(
+			origin: OriginFor<T>,
+			poll: PollIndex,
+			aye: bool,
+			salt: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Polls::<T>::get(poll).ok_or(Error::<T>::UnknownPoll)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > info.voting_ends && now <= info.reveal_ends, Error::<T>::NotRevealPeriod);
+			let commitment = match Ballots::<T>::get(poll, &who) {
+				Some(Ballot::Committed(commitment)) => commitment,
+				_ => return Err(Error::<T>::NoCommitment.into()),
+			};
+			ensure!(
+				T::Hashing::hash_of(&(&who, poll, aye, salt)) == commitment,
+				Error::<T>::CommitmentMismatch
+			);
+
+			Self::count(&mut info.tally, aye);
+			Polls::<T>::insert(poll, info);
+			Ballots::<T>::insert(poll, &who, Ballot::Counted);
+
+			Self::deposit_event(Event::VoteRevealed { poll, who });
+			Ok(())
+		}
+
+		/// Vote on a poll with a secret ballot. `ballot` is the SCALE encoded
+		/// `(who, poll, aye)` encrypted to the key of the poll's tallier.
+		///
+		/// The origin must hold an active license within the poll's scope.
+		#[pallet::weight(T::WeightInfo::cast_secret_vote(ballot.len() as u32))]
+		pub fn cast_secret_vote(
+			origin: OriginFor<T>,
+			poll: PollIndex,
+			ballot: BoundedVec<u8, T::MaxBallotLength>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Self::ensure_can_vote(poll, &who)?;
+			Self::ensure_ballot(&info, BallotKind::Secret)?;
+
+			if let Some(secret) = info.secret.as_mut() {
+				secret.cast = secret.cast.saturating_add(1);
+			}
+			Polls::<T>::insert(poll, info);
+			Ballots::<T>::insert(poll, &who, Ballot::Secret);
+			SecretVotes::<T>::insert(poll, &who, ballot);
+
+			Self::deposit_event(Event::Voted { poll, who });
+			Ok(())
+		}
+
+		/// Publish the tally of a poll with secret ballots once voting is over.
+		///
+		/// The origin must be the poll's tallier. Ballots which do not decrypt
+		/// to a vote of their sender on this poll are left out of the tally.
+		#[pallet::weight(T::WeightInfo::publish_tally())]
+		pub fn publish_tally(
+			origin: OriginFor<T>,
+			poll: PollIndex,
+			tally: Tally,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut info = Polls::<T>::get(poll).ok_or(Error::<T>::UnknownPoll)?;
+			Self::ensure_ballot(&info, BallotKind::Secret)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > info.voting_ends && now <= info.reveal_ends, Error::<T>::NotRevealPeriod);
+			let secret = info.secret.as_mut().ok_or(Error::<T>::NoTallier)?;
+			ensure!(secret.tallier.account == who, Error::<T>::NotTallier);
+			ensure!(!secret.tallied, Error::<T>::AlreadyTallied);
+			ensure!(tally.turnout() <= secret.cast, Error::<T>::TallyExceedsBallots);
+
+			secret.tallied = true;
+			info.tally = tally;
+			Polls::<T>::insert(poll, info);
+
+			Self::deposit_event(Event::TallyPublished { poll, tally });
+			Ok(())
+		}
+
+		/// Appoint the tallier of secret ballots, or remove it with `None`. Polls
+		/// opened before keep their tallier.
+		///
+		/// The origin must be `CouncilOrigin`.
+		#[pallet::weight(T::WeightInfo::set_tallier())]
+		pub fn set_tallier(
+			origin: OriginFor<T>,
+			tallier: Option<Tallier<T::AccountId>>,
+		) -> DispatchResult {
+			T::CouncilOrigin::ensure_origin(origin)?;
+
+			let account = tallier.as_ref().map(|tallier| tallier.account.clone());
+			CurrentTallier::<T>::set(tallier);
+
+			Self::deposit_event(Event::TallierSet { tallier: account });
+			Ok(())
+		}
+
+		/// Close a poll once voting and revealing are over, recording its tally
+		/// and dispatching its call if it is binding and passed. Sealed votes not
+		/// revealed by then are not counted, nor are secret votes if the tallier
+		/// did not publish their tally. The poll's ballots are removed.
+		///
+		/// Any signed origin may close a poll. `call_weight_bound` must be at
+		/// least the weight of the call of a binding poll.
+		#[pallet::weight(T::WeightInfo::close_poll().saturating_add(*call_weight_bound))]
+		pub fn close_poll(
+			origin: OriginFor<T>,
+			poll: PollIndex,
+			#[pallet::compact] call_weight_bound: Weight,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let info = Polls::<T>::get(poll).ok_or(Error::<T>::UnknownPoll)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now > info.reveal_ends, Error::<T>::PollOngoing);
+			if let Outcome::Binding(call) = &info.outcome {
+				ensure!(
+					call.get_dispatch_info().weight <= call_weight_bound,
+					Error::<T>::WrongCallWeightBound
+				);
+			}
+
+			Polls::<T>::remove(poll);
+			Ballots::<T>::remove_prefix(poll, None);
+			SecretVotes::<T>::remove_prefix(poll, None);
+			Results::<T>::insert(poll, info.tally);
+			let binding = matches!(info.outcome, Outcome::Binding(_));
+			let approved = info.tally.approved() &&
+				(!binding || info.tally.turnout() >= T::BindingQuorum::get());
+			Self::deposit_event(Event::PollClosed { poll, tally: info.tally, approved });
+
+			let mut weight = T::WeightInfo::close_poll();
+			if let (Outcome::Binding(call), true) = (info.outcome, approved) {
+				let dispatch_info = call.get_dispatch_info();
+				let result = call.dispatch(<T as Config>::Origin::from(info.origin));
+				weight = weight.saturating_add(extract_actual_weight(&result, &dispatch_info));
+				Self::deposit_event(Event::OutcomeEnacted {
+					poll,
+					result: result.map(|_| ()).map_err(|e| e.error),
+				});
+			}
+
+			Ok(Some(weight).into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The open poll `poll`, if `who` may still vote on it.
+	fn ensure_can_vote(poll: PollIndex, who: &T::AccountId) -> Result<PollOf<T>, Error<T>> {
+		let info = Polls::<T>::get(poll).ok_or(Error::<T>::UnknownPoll)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		if now > info.voting_ends {
+			return Err(Error::<T>::VotingClosed)
+		}
+		if Ballots::<T>::contains_key(poll, who) {
+			return Err(Error::<T>::AlreadyVoted)
+		}
+		if !T::Licensees::is_licensee(who, info.scope) {
+			return Err(Error::<T>::NotLicensee)
+		}
+		Ok(info)
+	}
+
+	/// Checks that `info` takes ballots of `kind`.
+	fn ensure_ballot(info: &PollOf<T>, kind: BallotKind) -> Result<(), Error<T>> {
+		match info.ballot {
+			ballot if ballot == kind => Ok(()),
+			BallotKind::Open => Err(Error::<T>::OpenBallot),
+			BallotKind::Sealed => Err(Error::<T>::SealedBallot),
+			BallotKind::Secret => Err(Error::<T>::SecretBallot),
+		}
+	}
+
+	fn count(tally: &mut Tally, aye: bool) {
+		if aye {
+			tally.ayes = tally.ayes.saturating_add(1);
+		} else {
+			tally.nays = tally.nays.saturating_add(1);
+		}
+	}
+}
@@ -0,0 +1,121 @@
+//! Test runtime of the polls pallet.
+
+use crate as pallet_polls;
+use frame_support::{
+	ord_parameter_types,
+	traits::{ConstU32, ConstU64, EnsureOrigin, Everything},
+};
+use frame_system::{EnsureSignedBy, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Polls: pallet_polls::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+ord_parameter_types! {
+	/// Account standing in for the council.
+	pub const Council: u64 = 100;
+}
+
+/// Origin of board `b`, signed by account `1_000 + b`.
+pub fn board(b: u32) -> Origin {
+	Origin::signed(1_000 + b as u64)
+}
+
+/// Accounts signing as `1_000 + b` speak for board `b`.
+pub struct EnsureBoard;
+impl EnsureOrigin<Origin> for EnsureBoard {
+	type Success = u32;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		match o.clone().into() {
+			Ok(RawOrigin::Signed(who)) if who > 1_000 => Ok((who - 1_000) as u32),
+			_ => Err(o),
+		}
+	}
+}
+
+/// Accounts 1 to 5 hold a license from board 1, accounts 6 and 7 from board 2.
+pub struct Licensees;
+impl pallet_polls::Licensees<u64, u32> for Licensees {
+	fn is_licensee(who: &u64, board: Option<u32>) -> bool {
+		let licensed_by = match who {
+			1..=5 => 1,
+			6 | 7 => 2,
+			_ => return false,
+		};
+		board.map_or(true, |board| board == licensed_by)
+	}
+}
+
+/// Not licensed by any board.
+pub const OUTSIDER: u64 = 8;
+pub const TALLIER: u64 = 50;
+
+pub const VOTING_PERIOD: u64 = 10;
+pub const REVEAL_PERIOD: u64 = 5;
+pub const BINDING_QUORUM: u32 = 3;
+
+impl pallet_polls::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type BoardId = u32;
+	type CouncilOrigin = EnsureSignedBy<Council, u64>;
+	type BoardOrigin = EnsureBoard;
+	type Licensees = Licensees;
+	type MinVotingPeriod = ConstU64<VOTING_PERIOD>;
+	type RevealPeriod = ConstU64<REVEAL_PERIOD>;
+	type BindingQuorum = ConstU32<BINDING_QUORUM>;
+	type MaxBallotLength = ConstU32<64>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,287 @@
+//! Tests of the polls pallet.
+
+use crate::{
+	mock::*, BallotKind, Ballots, Error, Event as PollsEvent, Outcome, PollIndex, SecretVotes,
+	Tallier, Tally,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchError, GetDispatchInfo},
+	traits::{ConstU32, Get},
+	BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const QUESTION: H256 = H256([7; 32]);
+const SALT: [u8; 32] = [3; 32];
+
+/// Block after which voting on polls opened at block 1 is over.
+const VOTING_ENDS: u64 = 1 + VOTING_PERIOD;
+
+fn open(scope: Option<u32>, outcome: Outcome<Call>, ballot: BallotKind) -> PollIndex {
+	let poll = Polls::poll_count();
+	let origin = match scope {
+		None => Origin::signed(Council::get()),
+		Some(b) => board(b),
+	};
+	assert_ok!(Polls::open_poll(origin, scope, QUESTION, outcome, ballot, VOTING_PERIOD));
+	poll
+}
+
+fn remark() -> Call {
+	frame_system::Call::remark_with_event { remark: b"enact".to_vec() }.into()
+}
+
+fn commitment(who: u64, poll: PollIndex, aye: bool) -> H256 {
+	BlakeTwo256::hash_of(&(who, poll, aye, SALT))
+}
+
+fn ballot(bytes: &[u8]) -> BoundedVec<u8, ConstU32<64>> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+fn has_ballots(poll: PollIndex) -> bool {
+	Ballots::<Test>::iter_prefix(poll).next().is_some()
+}
+
+#[test]
+fn open_votes_are_counted_and_cleared_on_close() {
+	new_test_ext().execute_with(|| {
+		let poll = open(None, Outcome::Advisory, BallotKind::Open);
+		assert_ok!(Polls::vote(Origin::signed(1), poll, true));
+		assert_ok!(Polls::vote(Origin::signed(6), poll, true));
+		assert_ok!(Polls::vote(Origin::signed(2), poll, false));
+		assert_eq!(Polls::poll(poll).unwrap().tally, Tally { ayes: 2, nays: 1 });
+
+		assert_noop!(Polls::close_poll(Origin::signed(1), poll, 0), Error::<Test>::PollOngoing);
+		System::set_block_number(VOTING_ENDS + 1);
+		assert_noop!(Polls::vote(Origin::signed(3), poll, true), Error::<Test>::VotingClosed);
+		assert_ok!(Polls::close_poll(Origin::signed(1), poll, 0));
+
+		System::assert_has_event(
+			PollsEvent::PollClosed { poll, tally: Tally { ayes: 2, nays: 1 }, approved: true }
+				.into(),
+		);
+		assert_eq!(Polls::result(poll), Some(Tally { ayes: 2, nays: 1 }));
+		assert!(Polls::poll(poll).is_none());
+		assert!(!has_ballots(poll));
+	});
+}
+
+#[test]
+fn only_licensees_in_scope_vote_once() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Polls::open_poll(
+				board(2),
+				Some(1),
+				QUESTION,
+				Outcome::Advisory,
+				BallotKind::Open,
+				VOTING_PERIOD
+			),
+			Error::<Test>::WrongBoard
+		);
+		assert_noop!(
+			Polls::open_poll(
+				board(1),
+				None,
+				QUESTION,
+				Outcome::Advisory,
+				BallotKind::Open,
+				VOTING_PERIOD
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Polls::open_poll(
+				board(1),
+				Some(1),
+				QUESTION,
+				Outcome::Advisory,
+				BallotKind::Open,
+				VOTING_PERIOD - 1
+			),
+			Error::<Test>::VotingPeriodTooShort
+		);
+
+		let poll = open(Some(1), Outcome::Advisory, BallotKind::Open);
+		assert_noop!(Polls::vote(Origin::signed(6), poll, true), Error::<Test>::NotLicensee);
+		assert_noop!(Polls::vote(Origin::signed(OUTSIDER), poll, true), Error::<Test>::NotLicensee);
+		assert_ok!(Polls::vote(Origin::signed(1), poll, true));
+		assert_noop!(Polls::vote(Origin::signed(1), poll, false), Error::<Test>::AlreadyVoted);
+		assert_noop!(Polls::vote(Origin::signed(1), poll + 1, true), Error::<Test>::UnknownPoll);
+	});
+}
+
+#[test]
+fn sealed_votes_count_once_revealed() {
+	new_test_ext().execute_with(|| {
+		let poll = open(None, Outcome::Advisory, BallotKind::Sealed);
+		assert_noop!(Polls::vote(Origin::signed(1), poll, true), Error::<Test>::SealedBallot);
+		for (who, aye) in [(1, true), (2, true), (3, false)] {
+			assert_ok!(Polls::commit_vote(Origin::signed(who), poll, commitment(who, poll, aye)));
+		}
+		assert_eq!(Polls::poll(poll).unwrap().tally, Tally::default());
+		assert_noop!(
+			Polls::reveal_vote(Origin::signed(1), poll, true, SALT),
+			Error::<Test>::NotRevealPeriod
+		);
+
+		System::set_block_number(VOTING_ENDS + 1);
+		assert_noop!(
+			Polls::commit_vote(Origin::signed(4), poll, commitment(4, poll, true)),
+			Error::<Test>::VotingClosed
+		);
+		assert_noop!(
+			Polls::reveal_vote(Origin::signed(1), poll, false, SALT),
+			Error::<Test>::CommitmentMismatch
+		);
+		assert_noop!(
+			Polls::reveal_vote(Origin::signed(4), poll, true, SALT),
+			Error::<Test>::NoCommitment
+		);
+		assert_ok!(Polls::reveal_vote(Origin::signed(1), poll, true, SALT));
+		assert_ok!(Polls::reveal_vote(Origin::signed(3), poll, false, SALT));
+		assert_noop!(
+			Polls::reveal_vote(Origin::signed(1), poll, true, SALT),
+			Error::<Test>::NoCommitment
+		);
+
+		// Account 2 never reveals, its vote is not counted.
+		assert_noop!(Polls::close_poll(Origin::signed(1), poll, 0), Error::<Test>::PollOngoing);
+		System::set_block_number(VOTING_ENDS + REVEAL_PERIOD + 1);
+		assert_noop!(
+			Polls::reveal_vote(Origin::signed(2), poll, true, SALT),
+			Error::<Test>::NotRevealPeriod
+		);
+		assert_ok!(Polls::close_poll(Origin::signed(1), poll, 0));
+		assert_eq!(Polls::result(poll), Some(Tally { ayes: 1, nays: 1 }));
+		assert!(!has_ballots(poll));
+	});
+}
+
+#[test]
+fn secret_votes_are_tallied_by_the_tallier() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Polls::open_poll(
+				Origin::signed(Council::get()),
+				None,
+				QUESTION,
+				Outcome::Advisory,
+				BallotKind::Secret,
+				VOTING_PERIOD
+			),
+			Error::<Test>::NoTallier
+		);
+		assert_noop!(
+			Polls::set_tallier(Origin::signed(1), Some(Tallier { account: TALLIER, key: [9; 32] })),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Polls::set_tallier(
+			Origin::signed(Council::get()),
+			Some(Tallier { account: TALLIER, key: [9; 32] })
+		));
+
+		let poll = open(None, Outcome::Advisory, BallotKind::Secret);
+		assert_noop!(Polls::vote(Origin::signed(1), poll, true), Error::<Test>::SecretBallot);
+		assert_ok!(Polls::cast_secret_vote(Origin::signed(1), poll, ballot(b"sealed aye")));
+		assert_ok!(Polls::cast_secret_vote(Origin::signed(2), poll, ballot(b"sealed nay")));
+		assert_noop!(
+			Polls::publish_tally(Origin::signed(TALLIER), poll, Tally { ayes: 1, nays: 1 }),
+			Error::<Test>::NotRevealPeriod
+		);
+
+		System::set_block_number(VOTING_ENDS + 1);
+		assert_noop!(
+			Polls::publish_tally(Origin::signed(1), poll, Tally { ayes: 1, nays: 1 }),
+			Error::<Test>::NotTallier
+		);
+		assert_noop!(
+			Polls::publish_tally(Origin::signed(TALLIER), poll, Tally { ayes: 2, nays: 1 }),
+			Error::<Test>::TallyExceedsBallots
+		);
+		assert_ok!(Polls::publish_tally(Origin::signed(TALLIER), poll, Tally { ayes: 2, nays: 0 }));
+		assert_noop!(
+			Polls::publish_tally(Origin::signed(TALLIER), poll, Tally { ayes: 1, nays: 1 }),
+			Error::<Test>::AlreadyTallied
+		);
+
+		System::set_block_number(VOTING_ENDS + REVEAL_PERIOD + 1);
+		assert_ok!(Polls::close_poll(Origin::signed(1), poll, 0));
+		assert_eq!(Polls::result(poll), Some(Tally { ayes: 2, nays: 0 }));
+		assert!(!has_ballots(poll));
+		assert!(SecretVotes::<Test>::iter_prefix(poll).next().is_none());
+	});
+}
+
+#[test]
+fn secret_ballots_cannot_be_binding() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Polls::set_tallier(
+			Origin::signed(Council::get()),
+			Some(Tallier { account: TALLIER, key: [9; 32] })
+		));
+		assert_noop!(
+			Polls::open_poll(
+				Origin::signed(Council::get()),
+				None,
+				QUESTION,
+				Outcome::Binding(remark()),
+				BallotKind::Secret,
+				VOTING_PERIOD
+			),
+			Error::<Test>::SecretBinding
+		);
+	});
+}
+
+#[test]
+fn binding_polls_dispatch_their_call_with_a_quorum() {
+	new_test_ext().execute_with(|| {
+		let short = open(None, Outcome::Binding(remark()), BallotKind::Open);
+		let carried = open(None, Outcome::Binding(remark()), BallotKind::Open);
+		for who in 1..BINDING_QUORUM as u64 {
+			assert_ok!(Polls::vote(Origin::signed(who), short, true));
+		}
+		for who in 1..=BINDING_QUORUM as u64 {
+			assert_ok!(Polls::vote(Origin::signed(who), carried, true));
+		}
+
+		System::set_block_number(VOTING_ENDS + 1);
+		let weight = remark().get_dispatch_info().weight;
+		assert_noop!(
+			Polls::close_poll(Origin::signed(1), carried, weight - 1),
+			Error::<Test>::WrongCallWeightBound
+		);
+
+		// Short of the quorum the ayes do not carry the poll.
+		assert_ok!(Polls::close_poll(Origin::signed(1), short, weight));
+		System::assert_has_event(
+			PollsEvent::PollClosed {
+				poll: short,
+				tally: Tally { ayes: BINDING_QUORUM - 1, nays: 0 },
+				approved: false,
+			}
+			.into(),
+		);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			Event::System(frame_system::Event::Remarked { .. })
+		)));
+
+		assert_ok!(Polls::close_poll(Origin::signed(1), carried, weight));
+		System::assert_has_event(
+			PollsEvent::OutcomeEnacted { poll: carried, result: Ok(()) }.into(),
+		);
+		System::assert_has_event(
+			frame_system::Event::Remarked {
+				sender: Council::get(),
+				hash: BlakeTwo256::hash(b"enact"),
+			}
+			.into(),
+		);
+	});
+}
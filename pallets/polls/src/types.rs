@@ -0,0 +1,106 @@
+//! Records kept by the polls pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// Index of a poll.
+pub type PollIndex = u32;
+
+/// What happens when a poll passes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum Outcome<Call> {
+	/// The result is only recorded, for the collective to take into account.
+	Advisory,
+	/// The call is dispatched with the origin of the collective which opened
+	/// the poll.
+	Binding(Call),
+}
+
+/// How ballots are cast.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BallotKind {
+	/// Votes are cast directly.
+	Open,
+	/// Voters commit to the hash of their vote and reveal it once voting is
+	/// over, so that no running tally can be read while the poll is open. The
+	/// side of each vote is public once revealed.
+	Sealed,
+	/// Voters encrypt their vote to the poll's tallier, who publishes the tally
+	/// once voting is over. Nobody but the tallier learns how an account voted.
+	Secret,
+}
+
+/// A voter's ballot. Only whether the account voted is kept, never the side it
+/// voted for.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Ballot<Hash> {
+	/// A sealed vote, yet to be revealed.
+	Committed(Hash),
+	/// A vote counted in the tally.
+	Counted,
+	/// An encrypted vote, counted by the tallier.
+	Secret,
+}
+
+/// Votes counted so far, one per licensee.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct Tally {
+	pub ayes: u32,
+	pub nays: u32,
+}
+
+impl Tally {
+	/// Whether the ayes carry the poll.
+	pub fn approved(&self) -> bool {
+		self.ayes > self.nays
+	}
+
+	/// Number of votes counted.
+	pub fn turnout(&self) -> u32 {
+		self.ayes.saturating_add(self.nays)
+	}
+}
+
+/// The account trusted with counting secret ballots, and the key they are
+/// encrypted to.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Tallier<AccountId> {
+	pub account: AccountId,
+	/// X25519 public key of the tallier.
+	pub key: [u8; 32],
+}
+
+/// Secret ballots of a poll.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SecretBallots<AccountId> {
+	/// Tallier when the poll was opened, who counts its ballots.
+	pub tallier: Tallier<AccountId>,
+	/// Number of ballots cast.
+	pub cast: u32,
+	/// Whether the tallier published the tally.
+	pub tallied: bool,
+}
+
+/// A poll of the licensees of one board, or of every board.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Poll<AccountId, BoardId, BlockNumber, Hash, PalletsOrigin, Call> {
+	/// Board whose licensees vote, every licensee when `None`.
+	pub scope: Option<BoardId>,
+	/// Hash of the question put to the vote, which is published off chain.
+	pub question: Hash,
+	pub outcome: Outcome<Call>,
+	/// Origin of the collective which opened the poll.
+	pub origin: PalletsOrigin,
+	pub ballot: BallotKind,
+	/// Last block at which votes are cast.
+	pub voting_ends: BlockNumber,
+	/// Last block at which sealed votes are revealed, or secret votes
+	/// tallied. Equal to `voting_ends` for open ballots.
+	pub reveal_ends: BlockNumber,
+	pub tally: Tally,
+	/// The ballots of a poll with secret ballots.
+	pub secret: Option<SecretBallots<AccountId>>,
+}
@@ -0,0 +1,137 @@
+//! Weights for pallet_polls.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own. The weight of the call of a binding
+//! poll is added on top when the poll is closed.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_polls.
+pub trait WeightInfo {
+	fn open_poll() -> Weight;
+	fn vote() -> Weight;
+	fn commit_vote() -> Weight;
+	fn reveal_vote() -> Weight;
+	fn cast_secret_vote(b: u32) -> Weight;
+	fn publish_tally() -> Weight;
+	fn set_tallier() -> Weight;
+	fn close_poll() -> Weight;
+}
+
+/// Weights for pallet_polls using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Polls PollCount (r:1 w:1)
+	// Storage: Polls CurrentTallier (r:1 w:0)
+	// Storage: Polls Polls (r:0 w:1)
+	fn open_poll() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Polls Polls (r:1 w:1)
+	// Storage: Polls Ballots (r:1 w:1)
+	// Storage: Licensure LicensesOf (r:1 w:0)
+	// Storage: Licensure Licenses (r:16 w:0)
+	fn vote() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Polls Polls (r:1 w:0)
+	// Storage: Polls Ballots (r:1 w:1)
+	// Storage: Licensure LicensesOf (r:1 w:0)
+	// Storage: Licensure Licenses (r:16 w:0)
+	fn commit_vote() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Polls Polls (r:1 w:1)
+	// Storage: Polls Ballots (r:1 w:1)
+	fn reveal_vote() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Polls Polls (r:1 w:1)
+	// Storage: Polls Ballots (r:1 w:1)
+	// Storage: Polls SecretVotes (r:0 w:1)
+	// Storage: Licensure LicensesOf (r:1 w:0)
+	// Storage: Licensure Licenses (r:16 w:0)
+	fn cast_secret_vote(b: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Polls Polls (r:1 w:1)
+	fn publish_tally() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Polls CurrentTallier (r:0 w:1)
+	fn set_tallier() -> Weight {
+		(15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Polls Polls (r:1 w:1)
+	// Storage: Polls Ballots (r:0 w:1)
+	// Storage: Polls SecretVotes (r:0 w:1)
+	// Storage: Polls Results (r:0 w:1)
+	fn close_poll() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn open_poll() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn vote() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn commit_vote() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn reveal_vote() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn cast_secret_vote(b: u32) -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn publish_tally() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_tallier() -> Weight {
+		(15_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn close_poll() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
@@ -0,0 +1,36 @@
+//! Runtime API definition for the projects pallet.
+//!
+//! What permitting offices need to look up projects, their engineers of record
+//! and their sealed documents.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::prelude::*;
+
+pub use pallet_projects::{
+	EngineerOfRecord, HistoryEntry, Project, ProjectChange, ProjectDetails, ProjectDocument,
+	ProjectId,
+};
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the project registry.
+	pub trait ProjectsApi<AccountId, BoardId, BlockNumber, Hash> where
+		AccountId: Codec,
+		BoardId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Project `id` with its engineers of record and documents.
+		fn project(id: ProjectId) -> Option<ProjectDetails<AccountId, BoardId, BlockNumber, Hash>>;
+
+		/// Project registered in `jurisdiction` under `permit_number`.
+		fn project_by_permit(jurisdiction: BoardId, permit_number: Vec<u8>) -> Option<ProjectId>;
+
+		/// Project `document` is attached to.
+		fn project_of_document(document: Hash) -> Option<ProjectId>;
+
+		/// Changes to project `id`, oldest first.
+		fn history(id: ProjectId) -> Vec<HistoryEntry<AccountId, BlockNumber, Hash>>;
+	}
+}
@@ -0,0 +1,255 @@
+//! Node-specific RPC methods for interaction with the projects pallet.
+//!
+//! The `projects_*` namespace lets permitting offices look up a project by its
+//! identifier, its permit or one of its documents, and audit its history.
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use pallet_licensure::Discipline;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use pallet_projects_rpc_runtime_api::{
+	HistoryEntry, ProjectChange, ProjectDetails, ProjectId, ProjectsApi as ProjectsRuntimeApi,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// A project with its engineers of record and documents.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInfo<AccountId, BoardId, BlockNumber, Hash> {
+	pub id: ProjectId,
+	pub owner: AccountId,
+	pub name: String,
+	pub location: String,
+	pub jurisdiction: BoardId,
+	pub permit_number: String,
+	pub registered_at: BlockNumber,
+	pub engineers_of_record: Vec<EngineerOfRecordInfo<AccountId, BlockNumber>>,
+	/// Documents, oldest first.
+	pub documents: Vec<DocumentInfo<AccountId, BlockNumber, Hash>>,
+}
+
+/// The engineer of record of a project for one discipline.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineerOfRecordInfo<AccountId, BlockNumber> {
+	pub discipline: Discipline,
+	pub engineer: AccountId,
+	/// License the assignment was accepted under, `None` while it awaits the
+	/// engineer.
+	pub license: Option<String>,
+	pub assigned_at: BlockNumber,
+}
+
+/// A sealed document attached to a project.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentInfo<AccountId, BlockNumber, Hash> {
+	pub document: Hash,
+	pub discipline: Discipline,
+	pub engineer: AccountId,
+	pub license: String,
+	pub revision: u32,
+	pub supersedes: Option<Hash>,
+	pub superseded_by: Option<Hash>,
+	pub attached_at: BlockNumber,
+}
+
+/// Projects RPC methods.
+#[rpc]
+pub trait ProjectsApi<BlockHash, AccountId, BoardId, BlockNumber, Hash> {
+	/// Look up a project by its identifier.
+	#[rpc(name = "projects_getProject")]
+	fn project(
+		&self,
+		id: ProjectId,
+		at: Option<BlockHash>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>>;
+
+	/// Look up a project by the jurisdiction and number of its permit.
+	#[rpc(name = "projects_getProjectByPermit")]
+	fn project_by_permit(
+		&self,
+		jurisdiction: BoardId,
+		permit_number: String,
+		at: Option<BlockHash>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>>;
+
+	/// Look up the project a sealed document is attached to.
+	#[rpc(name = "projects_getProjectOfDocument")]
+	fn project_of_document(
+		&self,
+		document: Hash,
+		at: Option<BlockHash>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>>;
+
+	/// List the changes to a project, oldest first.
+	#[rpc(name = "projects_getHistory")]
+	fn history(
+		&self,
+		id: ProjectId,
+		at: Option<BlockHash>,
+	) -> Result<Vec<HistoryEntry<AccountId, BlockNumber, Hash>>>;
+}
+
+/// Provides RPC methods to query the project registry.
+pub struct Projects<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> Projects<C, B> {
+	/// Create new `Projects` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+fn text(bytes: Vec<u8>) -> String {
+	String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn info<AccountId, BoardId, BlockNumber, Hash>(
+	id: ProjectId,
+	details: ProjectDetails<AccountId, BoardId, BlockNumber, Hash>,
+) -> ProjectInfo<AccountId, BoardId, BlockNumber, Hash> {
+	let project = details.project;
+	ProjectInfo {
+		id,
+		owner: project.owner,
+		name: text(project.name),
+		location: text(project.location),
+		jurisdiction: project.jurisdiction,
+		permit_number: text(project.permit_number),
+		registered_at: project.registered_at,
+		engineers_of_record: details
+			.engineers
+			.into_iter()
+			.map(|(discipline, entry)| EngineerOfRecordInfo {
+				discipline,
+				engineer: entry.engineer,
+				license: entry.license.map(text),
+				assigned_at: entry.assigned_at,
+			})
+			.collect(),
+		documents: details
+			.documents
+			.into_iter()
+			.map(|(document, doc)| DocumentInfo {
+				document,
+				discipline: doc.discipline,
+				engineer: doc.engineer,
+				license: text(doc.license),
+				revision: doc.revision,
+				supersedes: doc.supersedes,
+				superseded_by: doc.superseded_by,
+				attached_at: doc.attached_at,
+			})
+			.collect(),
+	}
+}
+
+impl<C, Block> Projects<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+{
+	fn details<AccountId, BoardId, BlockNumber, Hash>(
+		&self,
+		id: Option<ProjectId>,
+		at: &BlockId<Block>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>>
+	where
+		C::Api: ProjectsRuntimeApi<Block, AccountId, BoardId, BlockNumber, Hash>,
+		AccountId: Codec,
+		BoardId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		let id = match id {
+			Some(id) => id,
+			None => return Ok(None),
+		};
+		let details =
+			self.client.runtime_api().project(at, id).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(details.map(|details| info(id, details)))
+	}
+}
+
+impl<C, Block, AccountId, BoardId, BlockNumber, Hash>
+	ProjectsApi<<Block as BlockT>::Hash, AccountId, BoardId, BlockNumber, Hash> for Projects<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ProjectsRuntimeApi<Block, AccountId, BoardId, BlockNumber, Hash>,
+	AccountId: Codec + Send + Sync + 'static,
+	BoardId: Codec + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn project(
+		&self,
+		id: ProjectId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.details(Some(id), &at)
+	}
+
+	fn project_by_permit(
+		&self,
+		jurisdiction: BoardId,
+		permit_number: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let id = api
+			.project_by_permit(&at, jurisdiction, permit_number.into_bytes())
+			.map_err(runtime_error_into_rpc_err)?;
+
+		self.details(id, &at)
+	}
+
+	fn project_of_document(
+		&self,
+		document: Hash,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ProjectInfo<AccountId, BoardId, BlockNumber, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let id = api.project_of_document(&at, document).map_err(runtime_error_into_rpc_err)?;
+
+		self.details(id, &at)
+	}
+
+	fn history(
+		&self,
+		id: ProjectId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<HistoryEntry<AccountId, BlockNumber, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.history(&at, id).map_err(runtime_error_into_rpc_err)
+	}
+}
@@ -0,0 +1,696 @@
+//! # Projects Pallet
+//!
+//! A registry giving seals their project context.
+//!
+//! An owner registers a project with its location, the jurisdiction it is
+//! permitted in and its permit number. The owner then assigns an engineer of
+//! record for each discipline the project needs. An assignment only takes
+//! effect once the engineer accepts it under a license in good standing,
+//! issued for that discipline by the board of the project's jurisdiction.
+//!
+//! Registering a project reserves `ProjectDeposit` from the owner, held as long
+//! as the project claims a permit number. A board clears a permit number
+//! claimed by a project it did not permit, slashing the deposit, so that the
+//! rightful owner can register it. An owner done with a permit closes the
+//! project, giving the permit number up and getting the deposit back.
+//!
+//! Engineers of record attach the documents they sealed under that license,
+//! while it is in good standing, to the project. A document may supersede an
+//! earlier document of the same discipline, forming a chain of revisions. Every
+//! change to a project is kept in its history, so that permitting offices can
+//! audit who was responsible for what, and when.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod types;
+pub mod weights;
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	ensure,
+	traits::{Currency, Imbalance, NamedReservableCurrency, OnUnbalanced},
+	BoundedVec,
+};
+use pallet_licensure::{
+	Discipline, LicenseNumberOf, LicenseStatus, Licenses, LicensureAction, Seals,
+};
+use sp_runtime::{traits::Zero, DispatchError};
+use sp_std::prelude::*;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+pub type ReserveIdentifierOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+	<T as frame_system::Config>::AccountId,
+>>::ReserveIdentifier;
+pub type TextOf<T> = BoundedVec<u8, <T as Config>::MaxTextLength>;
+pub type ProjectOf<T> = Project<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_licensure::Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+	TextOf<T>,
+>;
+pub type EngineerOfRecordOf<T> = EngineerOfRecord<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	LicenseNumberOf<T>,
+>;
+pub type ProjectDocumentOf<T> = ProjectDocument<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+	LicenseNumberOf<T>,
+>;
+pub type HistoryEntryOf<T> = HistoryEntry<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+pub type ProjectDetailsOf<T> = ProjectDetails<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_licensure::Config>::BoardId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::StaticLookup;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: pallet_licensure::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Maximum length of a project name, location or permit number.
+		#[pallet::constant]
+		type MaxTextLength: Get<u32>;
+
+		/// Currency project deposits are held in.
+		type Currency: NamedReservableCurrency<Self::AccountId>;
+
+		/// Identifier project deposits are reserved under.
+		#[pallet::constant]
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// Deposit reserved from the owner while a project claims a permit
+		/// number.
+		#[pallet::constant]
+		type ProjectDeposit: Get<BalanceOf<Self>>;
+
+		/// Handler for the deposits slashed when a board clears a permit.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Identifier given to the next registered project.
+	#[pallet::storage]
+	pub type NextProjectId<T: Config> = StorageValue<_, ProjectId, ValueQuery>;
+
+	/// Registered projects.
+	#[pallet::storage]
+	#[pallet::getter(fn project)]
+	pub type Projects<T: Config> =
+		StorageMap<_, Twox64Concat, ProjectId, ProjectOf<T>, OptionQuery>;
+
+	/// Projects by jurisdiction and permit number.
+	#[pallet::storage]
+	#[pallet::getter(fn project_by_permit)]
+	pub type Permits<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BoardId,
+		Blake2_128Concat,
+		TextOf<T>,
+		ProjectId,
+		OptionQuery,
+	>;
+
+	/// Deposit held from the owner of each project claiming a permit number.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit)]
+	pub type Deposits<T: Config> = StorageMap<_, Twox64Concat, ProjectId, BalanceOf<T>, ValueQuery>;
+
+	/// Engineer of record of each project for each discipline.
+	#[pallet::storage]
+	#[pallet::getter(fn engineer_of_record)]
+	pub type EngineersOfRecord<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProjectId,
+		Twox64Concat,
+		Discipline,
+		EngineerOfRecordOf<T>,
+		OptionQuery,
+	>;
+
+	/// Documents attached to each project, by document hash.
+	#[pallet::storage]
+	#[pallet::getter(fn document)]
+	pub type Documents<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProjectId,
+		Identity,
+		T::Hash,
+		ProjectDocumentOf<T>,
+		OptionQuery,
+	>;
+
+	/// Project each document is attached to.
+	#[pallet::storage]
+	#[pallet::getter(fn project_of_document)]
+	pub type DocumentProjects<T: Config> = StorageMap<_, Identity, T::Hash, ProjectId, OptionQuery>;
+
+	/// History of each project, by position.
+	#[pallet::storage]
+	pub type History<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProjectId,
+		Twox64Concat,
+		u32,
+		HistoryEntryOf<T>,
+		OptionQuery,
+	>;
+
+	/// Number of entries in the history of each project.
+	#[pallet::storage]
+	pub type HistoryLength<T: Config> = StorageMap<_, Twox64Concat, ProjectId, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A project was registered.
+		ProjectRegistered { project: ProjectId, owner: T::AccountId, jurisdiction: T::BoardId },
+		/// The permit number of a project changed.
+		PermitUpdated { project: ProjectId },
+		/// The board of the jurisdiction cleared the permit number of a project
+		/// and slashed its deposit.
+		PermitCleared { project: ProjectId, slashed: BalanceOf<T> },
+		/// The owner closed a project, giving its permit number up, and the
+		/// deposit was returned.
+		ProjectClosed { project: ProjectId, released: BalanceOf<T> },
+		/// An owner asked an engineer to be engineer of record for a discipline.
+		EngineerAssigned { project: ProjectId, discipline: Discipline, engineer: T::AccountId },
+		/// An engineer accepted to be engineer of record.
+		AssignmentAccepted { project: ProjectId, discipline: Discipline, engineer: T::AccountId },
+		/// An engineer declined to be engineer of record.
+		AssignmentDeclined { project: ProjectId, discipline: Discipline, engineer: T::AccountId },
+		/// An owner removed the engineer of record of a discipline.
+		EngineerRemoved { project: ProjectId, discipline: Discipline, engineer: T::AccountId },
+		/// A sealed document was attached to a project.
+		DocumentAttached { project: ProjectId, document: T::Hash, revision: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// A name, location or permit number is longer than `MaxTextLength`.
+		TextTooLong,
+		/// No project with this identifier exists.
+		UnknownProject,
+		/// Only the project owner may do this.
+		NotOwner,
+		/// Another project of the jurisdiction has this permit number.
+		PermitTaken,
+		/// No project of the jurisdiction has this permit number.
+		UnknownPermit,
+		/// The permit number is empty.
+		EmptyPermit,
+		/// The project claims no permit number.
+		NoPermit,
+		/// The discipline already has an engineer of record, or one is awaiting
+		/// acceptance.
+		DisciplineAssigned,
+		/// The discipline has no engineer of record.
+		NotAssigned,
+		/// The assignment is not addressed to the origin.
+		NotAssignedEngineer,
+		/// The assignment has already been accepted.
+		AlreadyAccepted,
+		/// The assignment has not been accepted yet.
+		NotAccepted,
+		/// No license with this number exists.
+		UnknownLicense,
+		/// The license is held by someone else, governed by another board than
+		/// the project's jurisdiction, or issued for another discipline.
+		LicenseMismatch,
+		/// The license is not in good standing.
+		LicenseNotActive,
+		/// The document has not been sealed.
+		NotSealed,
+		/// The document was not sealed by the engineer of record under the
+		/// license of their assignment.
+		SealMismatch,
+		/// The document is already attached to a project.
+		AlreadyAttached,
+		/// The superseded document is not a document of this discipline of the
+		/// project.
+		UnknownRevision,
+		/// The superseded document has already been superseded.
+		AlreadySuperseded,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a project permitted in `jurisdiction` under `permit_number`,
+		/// owned by the origin, reserving `ProjectDeposit` from it.
+		#[pallet::weight(<T as Config>::WeightInfo::register_project())]
+		pub fn register_project(
+			origin: OriginFor<T>,
+			name: Vec<u8>,
+			location: Vec<u8>,
+			jurisdiction: T::BoardId,
+			permit_number: Vec<u8>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let name = Self::bounded_text(name)?;
+			let location = Self::bounded_text(location)?;
+			let permit_number = Self::permit_number(permit_number)?;
+			ensure!(
+				!Permits::<T>::contains_key(jurisdiction, &permit_number),
+				Error::<T>::PermitTaken
+			);
+			let deposit = T::ProjectDeposit::get();
+			T::Currency::reserve_named(&T::ReserveId::get(), &owner, deposit)?;
+
+			let project = NextProjectId::<T>::mutate(|id| {
+				let project = *id;
+				*id = id.saturating_add(1);
+				project
+			});
+			Permits::<T>::insert(jurisdiction, &permit_number, project);
+			Deposits::<T>::insert(project, deposit);
+			Projects::<T>::insert(
+				project,
+				Project {
+					owner: owner.clone(),
+					name,
+					location,
+					jurisdiction,
+					permit_number,
+					registered_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+			Self::record(project, ProjectChange::Registered { owner: owner.clone() });
+
+			Self::deposit_event(Event::ProjectRegistered { project, owner, jurisdiction });
+			Ok(())
+		}
+
+		/// Change the permit number of `project`, e.g. once the permit is
+		/// reissued.
+		///
+		/// The origin must own the project. If its permit number was cleared,
+		/// `ProjectDeposit` is reserved from it again.
+		#[pallet::weight(<T as Config>::WeightInfo::update_permit())]
+		pub fn update_permit(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			permit_number: Vec<u8>,
+		) -> DispatchResult {
+			let info = Self::ensure_owner(origin, project)?;
+			let permit_number = Self::permit_number(permit_number)?;
+			ensure!(
+				!Permits::<T>::contains_key(info.jurisdiction, &permit_number),
+				Error::<T>::PermitTaken
+			);
+			if Deposits::<T>::get(project).is_zero() {
+				let deposit = T::ProjectDeposit::get();
+				T::Currency::reserve_named(&T::ReserveId::get(), &info.owner, deposit)?;
+				Deposits::<T>::insert(project, deposit);
+			}
+
+			if !info.permit_number.is_empty() {
+				Permits::<T>::remove(info.jurisdiction, &info.permit_number);
+			}
+			Permits::<T>::insert(info.jurisdiction, &permit_number, project);
+			Projects::<T>::insert(project, Project { permit_number, ..info });
+			Self::record(project, ProjectChange::PermitUpdated);
+
+			Self::deposit_event(Event::PermitUpdated { project });
+			Ok(())
+		}
+
+		/// Clear `permit_number` of the board's jurisdiction from the project
+		/// claiming it, which the board did not permit, and slash the project's
+		/// deposit. The project stays registered without a permit number.
+		///
+		/// The origin must be the board, with the approval it requires for
+		/// `ClearPermit`.
+		#[pallet::weight(<T as Config>::WeightInfo::clear_permit())]
+		pub fn clear_permit(origin: OriginFor<T>, permit_number: Vec<u8>) -> DispatchResult {
+			let board =
+				pallet_licensure::Pallet::<T>::ensure_board(origin, LicensureAction::ClearPermit)?;
+			let permit_number = Self::bounded_text(permit_number)?;
+			let project =
+				Permits::<T>::take(board, &permit_number).ok_or(Error::<T>::UnknownPermit)?;
+
+			let owner = Projects::<T>::mutate(project, |info| {
+				info.as_mut().map(|info| {
+					info.permit_number = Default::default();
+					info.owner.clone()
+				})
+			});
+			let deposit = Deposits::<T>::take(project);
+			let mut slashed = Zero::zero();
+			if let Some(owner) = owner {
+				let (imbalance, _) =
+					T::Currency::slash_reserved_named(&T::ReserveId::get(), &owner, deposit);
+				slashed = imbalance.peek();
+				T::Slash::on_unbalanced(imbalance);
+			}
+			Self::record(project, ProjectChange::PermitCleared);
+
+			Self::deposit_event(Event::PermitCleared { project, slashed });
+			Ok(())
+		}
+
+		/// Close `project` once its permit is done with, giving its permit
+		/// number up and returning the deposit. The project stays registered
+		/// without a permit number, with its engineers, documents and history.
+		///
+		/// The origin must own the project.
+		#[pallet::weight(<T as Config>::WeightInfo::close_project())]
+		pub fn close_project(origin: OriginFor<T>, project: ProjectId) -> DispatchResult {
+			let info = Self::ensure_owner(origin, project)?;
+			ensure!(!info.permit_number.is_empty(), Error::<T>::NoPermit);
+
+			Permits::<T>::remove(info.jurisdiction, &info.permit_number);
+			let deposit = Deposits::<T>::take(project);
+			T::Currency::unreserve_named(&T::ReserveId::get(), &info.owner, deposit);
+			Projects::<T>::insert(project, Project { permit_number: Default::default(), ..info });
+			Self::record(project, ProjectChange::Closed);
+
+			Self::deposit_event(Event::ProjectClosed { project, released: deposit });
+			Ok(())
+		}
+
+		/// Ask `engineer` to be engineer of record of `project` for
+		/// `discipline`.
+		///
+		/// The origin must own the project.
+		#[pallet::weight(<T as Config>::WeightInfo::assign_engineer())]
+		pub fn assign_engineer(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			discipline: Discipline,
+			engineer: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			Self::ensure_owner(origin, project)?;
+			let engineer = T::Lookup::lookup(engineer)?;
+			ensure!(
+				!EngineersOfRecord::<T>::contains_key(project, discipline),
+				Error::<T>::DisciplineAssigned
+			);
+
+			EngineersOfRecord::<T>::insert(
+				project,
+				discipline,
+				EngineerOfRecord {
+					engineer: engineer.clone(),
+					license: None,
+					assigned_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+			Self::record(
+				project,
+				ProjectChange::EngineerAssigned { discipline, engineer: engineer.clone() },
+			);
+
+			Self::deposit_event(Event::EngineerAssigned { project, discipline, engineer });
+			Ok(())
+		}
+
+		/// Accept to be engineer of record of `project` for `discipline`, under
+		/// license `number`.
+		///
+		/// The origin must be the engineer asked, holding license `number` in
+		/// good standing, issued for `discipline` by the board of the project's
+		/// jurisdiction.
+		#[pallet::weight(<T as Config>::WeightInfo::accept_assignment())]
+		pub fn accept_assignment(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			discipline: Discipline,
+			number: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let info = Projects::<T>::get(project).ok_or(Error::<T>::UnknownProject)?;
+			let number: LicenseNumberOf<T> =
+				number.try_into().map_err(|_| Error::<T>::UnknownLicense)?;
+			let license = Licenses::<T>::get(&number).ok_or(Error::<T>::UnknownLicense)?;
+			ensure!(
+				license.holder == who &&
+					license.board == info.jurisdiction &&
+					license.discipline == discipline,
+				Error::<T>::LicenseMismatch
+			);
+			ensure!(
+				license.status_at(&frame_system::Pallet::<T>::block_number()) ==
+					LicenseStatus::Active,
+				Error::<T>::LicenseNotActive
+			);
+
+			EngineersOfRecord::<T>::try_mutate(project, discipline, |entry| -> DispatchResult {
+				let entry = entry.as_mut().ok_or(Error::<T>::NotAssigned)?;
+				ensure!(entry.engineer == who, Error::<T>::NotAssignedEngineer);
+				ensure!(entry.license.is_none(), Error::<T>::AlreadyAccepted);
+				entry.license = Some(number);
+				Ok(())
+			})?;
+			Self::record(
+				project,
+				ProjectChange::AssignmentAccepted { discipline, engineer: who.clone() },
+			);
+
+			Self::deposit_event(Event::AssignmentAccepted { project, discipline, engineer: who });
+			Ok(())
+		}
+
+		/// Decline to be engineer of record of `project` for `discipline`.
+		///
+		/// The origin must be the engineer asked, who has not accepted yet.
+		#[pallet::weight(<T as Config>::WeightInfo::decline_assignment())]
+		pub fn decline_assignment(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			discipline: Discipline,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let entry =
+				EngineersOfRecord::<T>::get(project, discipline).ok_or(Error::<T>::NotAssigned)?;
+			ensure!(entry.engineer == who, Error::<T>::NotAssignedEngineer);
+			ensure!(entry.license.is_none(), Error::<T>::AlreadyAccepted);
+
+			EngineersOfRecord::<T>::remove(project, discipline);
+			Self::record(
+				project,
+				ProjectChange::AssignmentDeclined { discipline, engineer: who.clone() },
+			);
+
+			Self::deposit_event(Event::AssignmentDeclined { project, discipline, engineer: who });
+			Ok(())
+		}
+
+		/// Remove the engineer of record of `project` for `discipline`, or
+		/// withdraw the request awaiting them. Documents they attached stay
+		/// attached.
+		///
+		/// The origin must own the project.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_engineer())]
+		pub fn remove_engineer(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			discipline: Discipline,
+		) -> DispatchResult {
+			Self::ensure_owner(origin, project)?;
+			let entry =
+				EngineersOfRecord::<T>::take(project, discipline).ok_or(Error::<T>::NotAssigned)?;
+
+			let engineer = entry.engineer;
+			Self::record(
+				project,
+				ProjectChange::EngineerRemoved { discipline, engineer: engineer.clone() },
+			);
+
+			Self::deposit_event(Event::EngineerRemoved { project, discipline, engineer });
+			Ok(())
+		}
+
+		/// Attach `document` to `project` for `discipline`, as a revision of
+		/// `supersedes` if given.
+		///
+		/// The origin must be the engineer of record of the discipline, who
+		/// sealed the document under the license of their assignment. The
+		/// license must still be in good standing.
+		#[pallet::weight(<T as Config>::WeightInfo::attach_document())]
+		pub fn attach_document(
+			origin: OriginFor<T>,
+			project: ProjectId,
+			discipline: Discipline,
+			document: T::Hash,
+			supersedes: Option<T::Hash>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Projects::<T>::contains_key(project), Error::<T>::UnknownProject);
+			let entry =
+				EngineersOfRecord::<T>::get(project, discipline).ok_or(Error::<T>::NotAssigned)?;
+			ensure!(entry.engineer == who, Error::<T>::NotAssignedEngineer);
+			let license = entry.license.ok_or(Error::<T>::NotAccepted)?;
+			let status = Licenses::<T>::get(&license)
+				.map(|info| info.status_at(&frame_system::Pallet::<T>::block_number()));
+			ensure!(status == Some(LicenseStatus::Active), Error::<T>::LicenseNotActive);
+			let seal = Seals::<T>::get(document).ok_or(Error::<T>::NotSealed)?;
+			ensure!(seal.signer == who && seal.license == license, Error::<T>::SealMismatch);
+			ensure!(!DocumentProjects::<T>::contains_key(document), Error::<T>::AlreadyAttached);
+
+			let revision = match supersedes {
+				Some(previous) => {
+					let previous_doc = Documents::<T>::get(project, previous)
+						.filter(|doc| doc.discipline == discipline)
+						.ok_or(Error::<T>::UnknownRevision)?;
+					ensure!(previous_doc.superseded_by.is_none(), Error::<T>::AlreadySuperseded);
+					Documents::<T>::insert(
+						project,
+						previous,
+						ProjectDocument { superseded_by: Some(document), ..previous_doc.clone() },
+					);
+					previous_doc.revision.saturating_add(1)
+				},
+				None => 0,
+			};
+
+			DocumentProjects::<T>::insert(document, project);
+			Documents::<T>::insert(
+				project,
+				document,
+				ProjectDocument {
+					discipline,
+					engineer: who,
+					license,
+					revision,
+					supersedes,
+					superseded_by: None,
+					attached_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+			Self::record(
+				project,
+				ProjectChange::DocumentAttached { document, discipline, revision },
+			);
+
+			Self::deposit_event(Event::DocumentAttached { project, document, revision });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Project `project`, if `origin` owns it.
+	fn ensure_owner(origin: T::Origin, project: ProjectId) -> Result<ProjectOf<T>, DispatchError> {
+		let who = frame_system::ensure_signed(origin)?;
+		let info = Projects::<T>::get(project).ok_or(Error::<T>::UnknownProject)?;
+		ensure!(info.owner == who, Error::<T>::NotOwner);
+		Ok(info)
+	}
+
+	fn bounded_text(text: Vec<u8>) -> Result<TextOf<T>, Error<T>> {
+		text.try_into().map_err(|_| Error::<T>::TextTooLong)
+	}
+
+	fn permit_number(text: Vec<u8>) -> Result<TextOf<T>, Error<T>> {
+		ensure!(!text.is_empty(), Error::<T>::EmptyPermit);
+		Self::bounded_text(text)
+	}
+
+	/// Append `change` to the history of `project`.
+	fn record(project: ProjectId, change: ProjectChange<T::AccountId, T::Hash>) {
+		let index = HistoryLength::<T>::mutate(project, |length| {
+			let index = *length;
+			*length = length.saturating_add(1);
+			index
+		});
+		History::<T>::insert(
+			project,
+			index,
+			HistoryEntry { block: frame_system::Pallet::<T>::block_number(), change },
+		);
+	}
+
+	/// Project `project` with its engineers of record and documents.
+	pub fn project_details(project: ProjectId) -> Option<ProjectDetailsOf<T>> {
+		let info = Projects::<T>::get(project)?;
+		let mut documents: Vec<_> = Documents::<T>::iter_prefix(project)
+			.map(|(hash, doc)| {
+				let doc = ProjectDocument {
+					discipline: doc.discipline,
+					engineer: doc.engineer,
+					license: doc.license.into_inner(),
+					revision: doc.revision,
+					supersedes: doc.supersedes,
+					superseded_by: doc.superseded_by,
+					attached_at: doc.attached_at,
+				};
+				(hash, doc)
+			})
+			.collect();
+		documents.sort_by_key(|(_, doc)| (doc.attached_at, doc.revision));
+
+		Some(ProjectDetails {
+			project: Project {
+				owner: info.owner,
+				name: info.name.into_inner(),
+				location: info.location.into_inner(),
+				jurisdiction: info.jurisdiction,
+				permit_number: info.permit_number.into_inner(),
+				registered_at: info.registered_at,
+			},
+			engineers: EngineersOfRecord::<T>::iter_prefix(project)
+				.map(|(discipline, entry)| {
+					let entry = EngineerOfRecord {
+						engineer: entry.engineer,
+						license: entry.license.map(|license| license.into_inner()),
+						assigned_at: entry.assigned_at,
+					};
+					(discipline, entry)
+				})
+				.collect(),
+			documents,
+		})
+	}
+
+	/// Project registered in `jurisdiction` under `permit_number`, if any.
+	pub fn project_of_permit(
+		jurisdiction: T::BoardId,
+		permit_number: Vec<u8>,
+	) -> Option<ProjectId> {
+		let permit_number: TextOf<T> = permit_number.try_into().ok()?;
+		Permits::<T>::get(jurisdiction, permit_number)
+	}
+
+	/// History of `project`, oldest first.
+	pub fn history(project: ProjectId) -> Vec<HistoryEntryOf<T>> {
+		(0..HistoryLength::<T>::get(project))
+			.filter_map(|index| History::<T>::get(project, index))
+			.collect()
+	}
+}
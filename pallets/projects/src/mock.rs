@@ -0,0 +1,183 @@
+//! Test runtime of the projects pallet.
+
+use crate as pallet_projects;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64, EnsureOrigin, Everything, GenesisBuild},
+};
+use frame_system::{EnsureRoot, RawOrigin};
+use pallet_licensure::{BoardApproval, BoardRegistrar, Discipline, IdentityJudgement};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	DispatchResult,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Licensure: pallet_licensure::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Projects: pallet_projects::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Members of each board.
+pub const MEMBERS: u32 = 5;
+
+/// Origin of a motion of `board` carried by `ayes` of its members.
+pub fn motion(board: u32, ayes: u32) -> Origin {
+	Origin::signed(board as u64 * 1_000 + ayes as u64)
+}
+
+/// Origin of the executive director of `board`.
+pub fn executive_director(board: u32) -> Origin {
+	Origin::signed(board as u64 * 1_000 + 100)
+}
+
+/// Boards speak through the accounts of [`motion`] and [`executive_director`],
+/// instead of collectives of their own.
+pub struct EnsureBoard;
+impl EnsureOrigin<Origin> for EnsureBoard {
+	type Success = (u32, BoardApproval);
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let who = match o.clone().into() {
+			Ok(RawOrigin::Signed(who)) if who >= 1_000 => who,
+			_ => return Err(o),
+		};
+		let approval = match who % 1_000 {
+			100 => BoardApproval::ExecutiveDirector,
+			ayes if ayes <= MEMBERS as u64 =>
+				BoardApproval::Motion { ayes: ayes as u32, members: MEMBERS },
+			_ => return Err(o),
+		};
+		Ok(((who / 1_000) as u32, approval))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		motion(1, MEMBERS)
+	}
+}
+
+/// Every board vouches for every identity.
+pub struct Registrar;
+impl BoardRegistrar<u64, u32> for Registrar {
+	fn provide_judgement(_: u32, _: &u64, _: IdentityJudgement) -> DispatchResult {
+		Ok(())
+	}
+
+	fn judgement(_: u32, _: &u64) -> Option<IdentityJudgement> {
+		Some(IdentityJudgement::KnownGood)
+	}
+}
+
+impl pallet_licensure::Config for Test {
+	type Event = Event;
+	type BoardId = u32;
+	type BoardOrigin = EnsureBoard;
+	type SettingsOrigin = EnsureRoot<u64>;
+	type Registrar = Registrar;
+	type MaxLicenseNumberLength = ConstU32<16>;
+	type MaxNameLength = ConstU32<32>;
+	type MaxLicensesPerAccount = ConstU32<4>;
+	type MaxDisciplinaryActions = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ProjectReserveId: [u8; 8] = *b"b3projdp";
+}
+
+/// Deposit reserved for each project.
+pub const PROJECT_DEPOSIT: u64 = 10;
+
+impl pallet_projects::Config for Test {
+	type Event = Event;
+	type MaxTextLength = ConstU32<64>;
+	type Currency = Balances;
+	type ReserveId = ProjectReserveId;
+	type ProjectDeposit = ConstU64<PROJECT_DEPOSIT>;
+	type Slash = ();
+	type WeightInfo = ();
+}
+
+/// Owner of the projects.
+pub const OWNER: u64 = 1;
+/// Engineer holding license `PE-1` of board 1, for structural engineering.
+pub const ENGINEER: u64 = 2;
+/// Owner rightfully holding a permit someone else claimed.
+pub const OTHER_OWNER: u64 = 3;
+/// Owner unable to afford the deposit.
+pub const POOR: u64 = 4;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![
+			(OWNER, 100),
+			(ENGINEER, 10),
+			(OTHER_OWNER, 100),
+			(POOR, PROJECT_DEPOSIT - 1),
+		],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_licensure::GenesisConfig::<Test> {
+		licenses: vec![(b"PE-1".to_vec(), ENGINEER, 1, Discipline::Structural, 1_000)],
+		action_rules: vec![],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,179 @@
+//! Tests of the projects pallet.
+
+use crate::{mock::*, Error, ProjectChange};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchResult};
+use pallet_licensure::Discipline;
+
+fn register(owner: u64, permit_number: &[u8]) -> DispatchResult {
+	Projects::register_project(
+		Origin::signed(owner),
+		b"Bridge".to_vec(),
+		b"Richmond".to_vec(),
+		1,
+		permit_number.to_vec(),
+	)
+}
+
+fn last_change(project: u32) -> ProjectChange<u64, sp_core::H256> {
+	Projects::history(project).pop().unwrap().change
+}
+
+#[test]
+fn registering_claims_the_permit_and_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(OWNER, b"P-1"));
+		assert_eq!(Projects::project_of_permit(1, b"P-1".to_vec()), Some(0));
+		assert_eq!(Projects::deposit(0), PROJECT_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+		assert_eq!(last_change(0), ProjectChange::Registered { owner: OWNER });
+
+		assert_noop!(register(OTHER_OWNER, b"P-1"), Error::<Test>::PermitTaken);
+		assert_noop!(register(OTHER_OWNER, b""), Error::<Test>::EmptyPermit);
+		assert_noop!(register(POOR, b"P-2"), pallet_balances::Error::<Test>::InsufficientBalance);
+		// The same permit number is free in another jurisdiction.
+		assert_ok!(Projects::register_project(
+			Origin::signed(OTHER_OWNER),
+			b"Tower".to_vec(),
+			b"Seattle".to_vec(),
+			2,
+			b"P-1".to_vec(),
+		));
+	});
+}
+
+#[test]
+fn owners_update_the_permit_number() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(OWNER, b"P-1"));
+		assert_ok!(register(OTHER_OWNER, b"P-2"));
+
+		assert_noop!(
+			Projects::update_permit(Origin::signed(OTHER_OWNER), 0, b"P-3".to_vec()),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Projects::update_permit(Origin::signed(OWNER), 0, b"P-2".to_vec()),
+			Error::<Test>::PermitTaken
+		);
+		assert_noop!(
+			Projects::update_permit(Origin::signed(OWNER), 0, Vec::new()),
+			Error::<Test>::EmptyPermit
+		);
+
+		assert_ok!(Projects::update_permit(Origin::signed(OWNER), 0, b"P-3".to_vec()));
+		assert_eq!(Projects::project_of_permit(1, b"P-1".to_vec()), None);
+		assert_eq!(Projects::project_of_permit(1, b"P-3".to_vec()), Some(0));
+		assert_eq!(Projects::project(0).unwrap().permit_number.into_inner(), b"P-3".to_vec());
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+		assert_eq!(last_change(0), ProjectChange::PermitUpdated);
+	});
+}
+
+#[test]
+fn boards_clear_permits_claimed_by_the_wrong_project() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(OWNER, b"P-1"));
+
+		assert_noop!(
+			Projects::clear_permit(motion(1, 2), b"P-1".to_vec()),
+			pallet_licensure::Error::<Test>::InsufficientApproval
+		);
+		assert_noop!(
+			Projects::clear_permit(motion(2, MEMBERS), b"P-1".to_vec()),
+			Error::<Test>::UnknownPermit
+		);
+		assert_ok!(Projects::clear_permit(motion(1, 3), b"P-1".to_vec()));
+		assert!(Projects::project(0).unwrap().permit_number.is_empty());
+		assert_eq!(Projects::deposit(0), 0);
+		assert_eq!(Balances::reserved_balance(OWNER), 0);
+		assert_eq!(Balances::free_balance(OWNER), 100 - PROJECT_DEPOSIT);
+		assert_eq!(last_change(0), ProjectChange::PermitCleared);
+
+		// The rightful owner claims the permit, and the other project takes a
+		// new one without releasing it.
+		assert_ok!(register(OTHER_OWNER, b"P-1"));
+		assert_ok!(Projects::update_permit(Origin::signed(OWNER), 0, b"P-2".to_vec()));
+		assert_eq!(Projects::project_of_permit(1, b"P-1".to_vec()), Some(1));
+		assert_eq!(Projects::project_of_permit(1, b"P-2".to_vec()), Some(0));
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+	});
+}
+
+#[test]
+fn closing_a_project_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(OWNER, b"P-1"));
+
+		assert_noop!(
+			Projects::close_project(Origin::signed(OTHER_OWNER), 0),
+			Error::<Test>::NotOwner
+		);
+		assert_noop!(
+			Projects::close_project(Origin::signed(OWNER), 1),
+			Error::<Test>::UnknownProject
+		);
+		assert_ok!(Projects::close_project(Origin::signed(OWNER), 0));
+		assert_eq!(Balances::reserved_balance(OWNER), 0);
+		assert_eq!(Balances::free_balance(OWNER), 100);
+		assert_eq!(Projects::deposit(0), 0);
+		assert_eq!(Projects::project_of_permit(1, b"P-1".to_vec()), None);
+		assert!(Projects::project(0).unwrap().permit_number.is_empty());
+		assert_eq!(last_change(0), ProjectChange::Closed);
+		assert_noop!(Projects::close_project(Origin::signed(OWNER), 0), Error::<Test>::NoPermit);
+
+		// Cleared permits have no deposit left to return.
+		assert_ok!(register(OTHER_OWNER, b"P-1"));
+		assert_ok!(Projects::clear_permit(motion(1, 3), b"P-1".to_vec()));
+		assert_noop!(
+			Projects::close_project(Origin::signed(OTHER_OWNER), 1),
+			Error::<Test>::NoPermit
+		);
+
+		// A closed project is reopened under a new permit number.
+		assert_ok!(Projects::update_permit(Origin::signed(OWNER), 0, b"P-2".to_vec()));
+		assert_eq!(Balances::reserved_balance(OWNER), PROJECT_DEPOSIT);
+	});
+}
+
+#[test]
+fn engineers_of_record_accept_under_a_matching_license() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(register(OWNER, b"P-1"));
+		assert_ok!(Projects::assign_engineer(
+			Origin::signed(OWNER),
+			0,
+			Discipline::Structural,
+			ENGINEER
+		));
+		assert_noop!(
+			Projects::assign_engineer(Origin::signed(OWNER), 0, Discipline::Structural, ENGINEER),
+			Error::<Test>::DisciplineAssigned
+		);
+
+		assert_ok!(Projects::assign_engineer(
+			Origin::signed(OWNER),
+			0,
+			Discipline::Civil,
+			ENGINEER
+		));
+		assert_noop!(
+			Projects::accept_assignment(
+				Origin::signed(ENGINEER),
+				0,
+				Discipline::Civil,
+				b"PE-1".to_vec()
+			),
+			Error::<Test>::LicenseMismatch
+		);
+		assert_ok!(Projects::accept_assignment(
+			Origin::signed(ENGINEER),
+			0,
+			Discipline::Structural,
+			b"PE-1".to_vec(),
+		));
+		assert_eq!(
+			Projects::engineer_of_record(0, Discipline::Structural).unwrap().license,
+			Some(b"PE-1".to_vec().try_into().unwrap())
+		);
+	});
+}
@@ -0,0 +1,87 @@
+//! Records kept by the projects pallet.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use pallet_licensure::Discipline;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Identifier of a registered project.
+pub type ProjectId = u32;
+
+/// A construction project as registered by its owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Project<AccountId, BoardId, BlockNumber, Text> {
+	pub owner: AccountId,
+	pub name: Text,
+	/// Site address or coordinates.
+	pub location: Text,
+	/// Board whose licensees may be engineers of record of the project.
+	pub jurisdiction: BoardId,
+	/// Number of the building permit, unique within the jurisdiction.
+	pub permit_number: Text,
+	pub registered_at: BlockNumber,
+}
+
+/// An engineer of record of a project for one discipline.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EngineerOfRecord<AccountId, BlockNumber, LicenseNumber> {
+	pub engineer: AccountId,
+	/// License the engineer accepted the assignment under, `None` while the
+	/// assignment awaits the engineer.
+	pub license: Option<LicenseNumber>,
+	pub assigned_at: BlockNumber,
+}
+
+/// A sealed document attached to a project.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProjectDocument<AccountId, BlockNumber, Hash, LicenseNumber> {
+	pub discipline: Discipline,
+	/// Engineer of record who sealed the document.
+	pub engineer: AccountId,
+	/// License the document was sealed under.
+	pub license: LicenseNumber,
+	/// 0 for a new document, one more than the document it supersedes
+	/// otherwise.
+	pub revision: u32,
+	pub supersedes: Option<Hash>,
+	pub superseded_by: Option<Hash>,
+	pub attached_at: BlockNumber,
+}
+
+/// A change to a project, as kept in its history.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ProjectChange<AccountId, Hash> {
+	Registered { owner: AccountId },
+	PermitUpdated,
+	EngineerAssigned { discipline: Discipline, engineer: AccountId },
+	AssignmentAccepted { discipline: Discipline, engineer: AccountId },
+	AssignmentDeclined { discipline: Discipline, engineer: AccountId },
+	EngineerRemoved { discipline: Discipline, engineer: AccountId },
+	DocumentAttached { document: Hash, discipline: Discipline, revision: u32 },
+	PermitCleared,
+	Closed,
+}
+
+/// An entry of a project's history.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct HistoryEntry<AccountId, BlockNumber, Hash> {
+	pub block: BlockNumber,
+	pub change: ProjectChange<AccountId, Hash>,
+}
+
+/// A project with its engineers of record and documents, as returned to
+/// permitting offices.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ProjectDetails<AccountId, BoardId, BlockNumber, Hash> {
+	pub project: Project<AccountId, BoardId, BlockNumber, Vec<u8>>,
+	pub engineers: Vec<(Discipline, EngineerOfRecord<AccountId, BlockNumber, Vec<u8>>)>,
+	/// Documents by hash, oldest revision first.
+	pub documents: Vec<(Hash, ProjectDocument<AccountId, BlockNumber, Hash, Vec<u8>>)>,
+}
@@ -0,0 +1,179 @@
+//! Weights for pallet_projects.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_projects.
+pub trait WeightInfo {
+	fn register_project() -> Weight;
+	fn update_permit() -> Weight;
+	fn clear_permit() -> Weight;
+	fn close_project() -> Weight;
+	fn assign_engineer() -> Weight;
+	fn accept_assignment() -> Weight;
+	fn decline_assignment() -> Weight;
+	fn remove_engineer() -> Weight;
+	fn attach_document() -> Weight;
+}
+
+/// Weights for pallet_projects using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Projects Permits (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Projects NextProjectId (r:1 w:1)
+	// Storage: Projects Deposits (r:0 w:1)
+	// Storage: Projects Projects (r:0 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn register_project() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:1)
+	// Storage: Projects Permits (r:1 w:2)
+	// Storage: Projects Deposits (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn update_permit() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: Licensure ActionRules (r:1 w:0)
+	// Storage: Projects Permits (r:1 w:1)
+	// Storage: Projects Projects (r:1 w:1)
+	// Storage: Projects Deposits (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn clear_permit() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:1)
+	// Storage: Projects Permits (r:0 w:1)
+	// Storage: Projects Deposits (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn close_project() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:0)
+	// Storage: Projects EngineersOfRecord (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn assign_engineer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:0)
+	// Storage: Licensure Licenses (r:1 w:0)
+	// Storage: Projects EngineersOfRecord (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn accept_assignment() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Projects EngineersOfRecord (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn decline_assignment() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:0)
+	// Storage: Projects EngineersOfRecord (r:1 w:1)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn remove_engineer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: Projects Projects (r:1 w:0)
+	// Storage: Projects EngineersOfRecord (r:1 w:0)
+	// Storage: Licensure Licenses (r:1 w:0)
+	// Storage: Licensure Seals (r:1 w:0)
+	// Storage: Projects DocumentProjects (r:1 w:1)
+	// Storage: Projects Documents (r:1 w:2)
+	// Storage: Projects HistoryLength (r:1 w:1)
+	// Storage: Projects History (r:0 w:1)
+	fn attach_document() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn register_project() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn update_permit() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn clear_permit() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn close_project() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn assign_engineer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn accept_assignment() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn decline_assignment() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn remove_engineer() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn attach_document() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+}
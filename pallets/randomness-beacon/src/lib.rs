@@ -0,0 +1,567 @@
+//! # Randomness Beacon Pallet
+//!
+//! Randomness produced by the block authorities through commit-reveal, in place
+//! of `pallet_randomness_collective_flip`, whose output the block author can
+//! predict and bias.
+//!
+//! Time is divided into rounds of `RoundLength` blocks. During the first half
+//! of a round every participant commits to a secret by submitting
+//! `hash(account, secret)`, during the second half it reveals the secret. When
+//! the round ends the hashes of all revealed secrets are XOR-ed together and
+//! mixed into the seed. As long as a single participant keeps its secret until
+//! the reveal phase nobody can predict the new seed, and as the combination
+//! does not depend on the order of reveals, the block author cannot steer it by
+//! reordering them.
+//!
+//! A round produces a new seed only if at least `RevealThreshold` of the
+//! participants, rounded up, revealed; otherwise it keeps the previous seed.
+//! Committing reserves `RevealBond` from the participant's account, which is
+//! returned when it reveals and slashed, through [`Event::RevealMissed`], when
+//! the round closes without its reveal.
+//!
+//! ## Remaining bias
+//!
+//! Commit-reveal does not make the seed unbiasable. The last participant to
+//! reveal has seen every other reveal, and can work out the seed with and
+//! without its own. By withholding its reveal it picks one of the two, or keeps
+//! the old seed if that leaves the round short of the threshold; `k` colluding
+//! participants pick among `2^k` outcomes. Each withheld reveal costs a bond,
+//! which makes this expensive but does not prevent it.
+//!
+//! Block authors also choose which reveals to include. An author who controls
+//! the last blocks of the reveal phase can leave out reveals submitted late,
+//! and so choose between outcomes the same way, while the participants it
+//! censors lose their bond. Offchain workers reveal from the first block of the
+//! reveal phase so that any author of the phase can include the reveal, but
+//! enough colluding authors can keep one out.
+//!
+//! The seed is therefore fit for low stakes uses such as contract lotteries or
+//! sampling, not for anything a few authorities would profit from steering.
+//!
+//! Participants contribute from their offchain worker through unsigned
+//! transactions carrying a payload signed with their `AuthorityId` key, so they
+//! need neither a funded account nor extra keys beyond it.
+//!
+//! The pallet implements [`Randomness`], and can stand in wherever
+//! `RandomnessCollectiveFlip` was used. The output for a subject only changes
+//! once per round.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Currency, Get, Imbalance, NamedReservableCurrency, OnUnbalanced, Randomness},
+	weights::Weight,
+};
+use frame_system::offchain::{AppCrypto, SignedPayload, SigningTypes, SubmitTransaction};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	traits::{Hash, IdentifyAccount, One, Saturating, UniqueSaturatedInto, Zero},
+	Perbill, RuntimeAppPublic, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+const LOG_TARGET: &str = "runtime::randomness-beacon";
+
+/// Prefix of the offchain storage keys holding a participant's secrets.
+const SECRET_PREFIX: &[u8] = b"randomness-beacon/secret";
+
+/// Keys contributions are signed with.
+///
+/// These are the authorities' Aura keys, which every block author already has
+/// in its keystore.
+pub mod crypto {
+	use sp_core::crypto::key_types::AURA;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+
+	app_crypto!(sr25519, AURA);
+
+	pub struct AuthorityId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthorityId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Phase of a round.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Phase {
+	/// Participants commit to their secret.
+	Commit,
+	/// Participants reveal the secret they committed to.
+	Reveal,
+}
+
+/// A participant's commitment in the current round.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Commitment<AccountId, Hash> {
+	pub who: AccountId,
+	/// `hash(who, secret)`.
+	pub hash: Hash,
+	pub revealed: bool,
+}
+
+/// A commitment or a reveal, signed by the participant.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Contribution<Public, BlockNumber, Hash> {
+	pub public: Public,
+	/// Round the contribution is for.
+	pub round: BlockNumber,
+	/// The commitment when committing, the secret when revealing.
+	pub value: Hash,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for Contribution<T::Public, T::BlockNumber, T::Hash> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+pub type ReserveIdentifierOf<T> = <<T as Config>::Currency as NamedReservableCurrency<
+	<T as frame_system::Config>::AccountId,
+>>::ReserveIdentifier;
+
+pub type ContributionOf<T> = Contribution<
+	<T as SigningTypes>::Public,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::{offchain::SendTransactionTypes, pallet_prelude::*};
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config + SendTransactionTypes<Call<Self>> + SigningTypes
+	{
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Key participants sign their contributions with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Accounts taking part in the beacon, usually the block authorities.
+		type Participants: Get<Vec<Self::AccountId>>;
+
+		/// Number of blocks in a round, the first half for commitments and the
+		/// second half for reveals. Must be at least 2.
+		#[pallet::constant]
+		type RoundLength: Get<Self::BlockNumber>;
+
+		/// Share of the participants, rounded up, that must reveal for a round
+		/// to produce a new seed.
+		#[pallet::constant]
+		type RevealThreshold: Get<Perbill>;
+
+		/// Currency the reveal bonds are held in.
+		type Currency: NamedReservableCurrency<Self::AccountId>;
+
+		/// Identifier the reveal bonds are reserved under.
+		#[pallet::constant]
+		type ReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// Amount reserved from a participant when it commits, returned when it
+		/// reveals and slashed when it does not.
+		#[pallet::constant]
+		type RevealBond: Get<BalanceOf<Self>>;
+
+		/// Handler for the slashed reveal bonds.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Maximum number of participants.
+		#[pallet::constant]
+		type MaxParticipants: Get<u32>;
+
+		/// Priority of the unsigned contribution transactions.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	/// Current random seed.
+	#[pallet::storage]
+	#[pallet::getter(fn seed)]
+	pub type Seed<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	/// Block at which the current seed was produced.
+	#[pallet::storage]
+	#[pallet::getter(fn seed_block)]
+	pub type SeedBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Commitments made in the current round.
+	#[pallet::storage]
+	pub type Commitments<T: Config> = StorageValue<
+		_,
+		BoundedVec<Commitment<T::AccountId, T::Hash>, T::MaxParticipants>,
+		ValueQuery,
+	>;
+
+	/// XOR of the hashes of the secrets revealed so far in the current round.
+	#[pallet::storage]
+	pub type Accumulator<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A participant committed to a secret.
+		Committed { who: T::AccountId, round: T::BlockNumber },
+		/// A participant revealed its secret.
+		Revealed { who: T::AccountId, round: T::BlockNumber },
+		/// A participant committed to a secret but never revealed it, and lost
+		/// its bond.
+		RevealMissed { who: T::AccountId, round: T::BlockNumber, slashed: BalanceOf<T> },
+		/// A round produced a new seed.
+		NewSeed { round: T::BlockNumber, reveals: u32 },
+		/// A round had too few reveals, the seed is unchanged.
+		RoundFailed { round: T::BlockNumber, reveals: u32 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The contribution is not for the current round.
+		WrongRound,
+		/// The round is not in the phase for this contribution.
+		WrongPhase,
+		/// The signer does not take part in the beacon.
+		NotParticipant,
+		/// The participant already committed in this round.
+		AlreadyCommitted,
+		/// The participant did not commit in this round.
+		NotCommitted,
+		/// The participant already revealed in this round.
+		AlreadyRevealed,
+		/// The secret does not match the commitment.
+		CommitmentMismatch,
+		/// More participants committed than `MaxParticipants`.
+		TooManyParticipants,
+		/// The participant cannot reserve the reveal bond.
+		InsufficientBond,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if now.is_zero() || !(now % T::RoundLength::get()).is_zero() {
+				return 0
+			}
+			Self::close_round(Self::round_of(now).saturating_sub(One::one()), now)
+		}
+
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(e) = Self::contribute(now) {
+				log::warn!(target: LOG_TARGET, "Randomness contribution failed: {}", e);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Commit to a secret for the current round.
+		///
+		/// Submitted unsigned by a participant's offchain worker, the contribution
+		/// being signed with the participant's key.
+		#[pallet::weight(T::WeightInfo::commit(T::MaxParticipants::get()))]
+		pub fn commit(
+			origin: OriginFor<T>,
+			contribution: ContributionOf<T>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let who = Self::check(&contribution, Phase::Commit)?;
+
+			T::Currency::reserve_named(&T::ReserveId::get(), &who, T::RevealBond::get())?;
+			Commitments::<T>::try_mutate(|commitments| {
+				commitments.try_push(Commitment {
+					who: who.clone(),
+					hash: contribution.value,
+					revealed: false,
+				})
+			})
+			.map_err(|_| Error::<T>::TooManyParticipants)?;
+
+			Self::deposit_event(Event::Committed { who, round: contribution.round });
+			Ok(())
+		}
+
+		/// Reveal the secret committed to earlier in the round.
+		///
+		/// Submitted unsigned by a participant's offchain worker, the contribution
+		/// being signed with the participant's key.
+		#[pallet::weight(T::WeightInfo::reveal(T::MaxParticipants::get()))]
+		pub fn reveal(
+			origin: OriginFor<T>,
+			contribution: ContributionOf<T>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let who = Self::check(&contribution, Phase::Reveal)?;
+
+			Commitments::<T>::mutate(|commitments| {
+				if let Some(commitment) = commitments.iter_mut().find(|c| c.who == who) {
+					commitment.revealed = true;
+				}
+			});
+			T::Currency::unreserve_named(&T::ReserveId::get(), &who, T::RevealBond::get());
+			let hash = T::Hashing::hash_of(&contribution.value);
+			Accumulator::<T>::mutate(|accumulator| {
+				accumulator.as_mut().iter_mut().zip(hash.as_ref()).for_each(|(a, h)| *a ^= h)
+			});
+
+			Self::deposit_event(Event::Revealed { who, round: contribution.round });
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let (contribution, signature, phase) = match call {
+				Call::commit { contribution, signature } =>
+					(contribution, signature, Phase::Commit),
+				Call::reveal { contribution, signature } =>
+					(contribution, signature, Phase::Reveal),
+				_ => return InvalidTransaction::Call.into(),
+			};
+
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(contribution, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			// Failed contributions would be included for free, so everything the
+			// dispatch checks is checked here already.
+			let who = Self::check(contribution, phase).map_err(|e| match e {
+				Error::<T>::NotParticipant => InvalidTransaction::BadSigner,
+				Error::<T>::CommitmentMismatch => InvalidTransaction::BadProof,
+				Error::<T>::TooManyParticipants => InvalidTransaction::ExhaustsResources,
+				Error::<T>::InsufficientBond => InvalidTransaction::Payment,
+				_ => InvalidTransaction::Stale,
+			})?;
+
+			ValidTransaction::with_tag_prefix("RandomnessBeacon")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((phase == Phase::Commit, contribution.round, who))
+				.longevity((T::RoundLength::get() / 2u32.into()).unique_saturated_into())
+				.propagate(true)
+				.build()
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Round `now` belongs to.
+	pub fn round_of(now: T::BlockNumber) -> T::BlockNumber {
+		now / T::RoundLength::get()
+	}
+
+	/// Phase of its round `now` is in.
+	pub fn phase_of(now: T::BlockNumber) -> Phase {
+		let length = T::RoundLength::get();
+		if now % length < length / 2u32.into() {
+			Phase::Commit
+		} else {
+			Phase::Reveal
+		}
+	}
+
+	fn commitment_of(who: &T::AccountId, secret: &T::Hash) -> T::Hash {
+		T::Hashing::hash_of(&(who, secret))
+	}
+
+	/// Checks that `contribution` can be applied in `phase` at the current block,
+	/// returning the contributing account.
+	fn check(contribution: &ContributionOf<T>, phase: Phase) -> Result<T::AccountId, Error<T>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(contribution.round == Self::round_of(now), Error::<T>::WrongRound);
+		ensure!(Self::phase_of(now) == phase, Error::<T>::WrongPhase);
+
+		let who = contribution.public.clone().into_account();
+		ensure!(T::Participants::get().contains(&who), Error::<T>::NotParticipant);
+
+		let commitments = Commitments::<T>::get();
+		let commitment = commitments.iter().find(|c| c.who == who);
+		match phase {
+			Phase::Commit => {
+				ensure!(commitment.is_none(), Error::<T>::AlreadyCommitted);
+				ensure!(
+					commitments.len() < T::MaxParticipants::get() as usize,
+					Error::<T>::TooManyParticipants
+				);
+				ensure!(
+					T::Currency::can_reserve(&who, T::RevealBond::get()),
+					Error::<T>::InsufficientBond
+				);
+			},
+			Phase::Reveal => {
+				let commitment = commitment.ok_or(Error::<T>::NotCommitted)?;
+				ensure!(!commitment.revealed, Error::<T>::AlreadyRevealed);
+				ensure!(
+					commitment.hash == Self::commitment_of(&who, &contribution.value),
+					Error::<T>::CommitmentMismatch
+				);
+			},
+		}
+		Ok(who)
+	}
+
+	/// Number of reveals a round needs to produce a new seed.
+	pub fn min_reveals() -> u32 {
+		T::RevealThreshold::get().mul_ceil(T::Participants::get().len() as u32).max(1)
+	}
+
+	/// Mixes the secrets revealed in `round` into the seed, and slashes the bonds
+	/// of the participants who did not reveal.
+	fn close_round(round: T::BlockNumber, now: T::BlockNumber) -> Weight {
+		let commitments = Commitments::<T>::take();
+		let accumulator = Accumulator::<T>::take();
+
+		let mut reveals = 0u32;
+		for commitment in &commitments {
+			if commitment.revealed {
+				reveals += 1;
+			} else {
+				let (imbalance, _) = T::Currency::slash_reserved_named(
+					&T::ReserveId::get(),
+					&commitment.who,
+					T::RevealBond::get(),
+				);
+				let slashed = imbalance.peek();
+				T::Slash::on_unbalanced(imbalance);
+				Self::deposit_event(Event::RevealMissed {
+					who: commitment.who.clone(),
+					round,
+					slashed,
+				});
+			}
+		}
+
+		if reveals >= Self::min_reveals() {
+			Seed::<T>::put(T::Hashing::hash_of(&(Seed::<T>::get(), accumulator, round)));
+			SeedBlock::<T>::put(now);
+			Self::deposit_event(Event::NewSeed { round, reveals });
+		} else {
+			Self::deposit_event(Event::RoundFailed { round, reveals });
+		}
+
+		T::WeightInfo::close_round(commitments.len() as u32)
+	}
+
+	/// Commits or reveals for every participant key in the local keystore.
+	fn contribute(now: T::BlockNumber) -> Result<(), &'static str> {
+		if !sp_io::offchain::is_validator() {
+			return Ok(())
+		}
+
+		// Contributions are included in the next block at the earliest.
+		let next = now.saturating_add(One::one());
+		let round = Self::round_of(next);
+		let phase = Self::phase_of(next);
+		let commitments = if Self::round_of(now) == round {
+			Commitments::<T>::get().into_inner()
+		} else {
+			Vec::new()
+		};
+		let participants = T::Participants::get();
+
+		for key in <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all() {
+			let generic: <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic =
+				key.into();
+			let public: T::Public = generic.into();
+			let who = public.clone().into_account();
+			if !participants.contains(&who) {
+				continue
+			}
+
+			let commitment = commitments.iter().find(|c| c.who == who);
+			let value = match (phase, commitment) {
+				(Phase::Commit, None) => {
+					if !T::Currency::can_reserve(&who, T::RevealBond::get()) {
+						log::debug!(target: LOG_TARGET, "{:?} cannot reserve the reveal bond", who);
+						continue
+					}
+					if !round.is_zero() {
+						let previous = Self::secret_key(round - One::one(), &who);
+						StorageValueRef::persistent(&previous).clear();
+					}
+					Self::commitment_of(&who, &Self::local_secret(round, &who))
+				},
+				(Phase::Reveal, Some(commitment)) if !commitment.revealed => {
+					let key = Self::secret_key(round, &who);
+					match StorageValueRef::persistent(&key).get::<T::Hash>() {
+						Ok(Some(secret)) => secret,
+						_ => continue,
+					}
+				},
+				_ => continue,
+			};
+
+			let contribution = Contribution { public: public.clone(), round, value };
+			let signature = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::sign(
+				&contribution.encode(),
+				public,
+			)
+			.ok_or("Failed to sign the contribution")?;
+			let call = match phase {
+				Phase::Commit => Call::commit { contribution, signature },
+				Phase::Reveal => Call::reveal { contribution, signature },
+			};
+			SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into())
+				.map_err(|_| "Failed to submit the contribution")?;
+		}
+		Ok(())
+	}
+
+	/// Offchain storage key of the secret `who` commits to in `round`.
+	fn secret_key(round: T::BlockNumber, who: &T::AccountId) -> Vec<u8> {
+		(SECRET_PREFIX, round, who).encode()
+	}
+
+	/// The secret `who` commits to in `round`, drawn when first asked for.
+	fn local_secret(round: T::BlockNumber, who: &T::AccountId) -> T::Hash {
+		let key = Self::secret_key(round, who);
+		let storage = StorageValueRef::persistent(&key);
+		if let Ok(Some(secret)) = storage.get::<T::Hash>() {
+			return secret
+		}
+		let secret = T::Hashing::hash(&sp_io::offchain::random_seed());
+		storage.set(&secret);
+		secret
+	}
+}
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+	/// Mixes `subject` into the current seed, which has been known since the
+	/// returned block.
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		(T::Hashing::hash_of(&(Seed::<T>::get(), subject)), SeedBlock::<T>::get())
+	}
+}
@@ -0,0 +1,149 @@
+//! Test runtime of the randomness beacon pallet.
+
+use crate as pallet_randomness_beacon;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU32, ConstU64, Everything, GenesisBuild, Get, Hooks},
+};
+use frame_system::offchain::{SendTransactionTypes, SigningTypes};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup},
+	AccountId32, MultiSignature, MultiSigner, Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		RandomnessBeacon: pallet_randomness_beacon::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<50>;
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl SigningTypes for Test {
+	type Public = MultiSigner;
+	type Signature = MultiSignature;
+}
+
+impl<C> SendTransactionTypes<C> for Test
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = TestXt<Call, ()>;
+}
+
+/// Key of participant `i`.
+pub fn key(i: u8) -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[i; 32])
+}
+
+/// Account of participant `i`.
+pub fn account(i: u8) -> AccountId32 {
+	MultiSigner::from(key(i).public()).into_account()
+}
+
+/// Participants 1 to `PARTICIPANTS`.
+pub const PARTICIPANTS: u8 = 4;
+
+pub struct Participants;
+impl Get<Vec<AccountId32>> for Participants {
+	fn get() -> Vec<AccountId32> {
+		(1..=PARTICIPANTS).map(account).collect()
+	}
+}
+
+parameter_types! {
+	pub const RandomnessReserveId: [u8; 8] = *b"b3rndbnd";
+	pub const RevealThreshold: Perbill = Perbill::from_percent(50);
+}
+
+/// Blocks in a round, rounds start at multiples of it.
+pub const ROUND_LENGTH: u64 = 10;
+pub const REVEAL_BOND: u64 = 10;
+
+impl pallet_randomness_beacon::Config for Test {
+	type Event = Event;
+	type AuthorityId = pallet_randomness_beacon::crypto::AuthorityId;
+	type Participants = Participants;
+	type RoundLength = ConstU64<ROUND_LENGTH>;
+	type RevealThreshold = RevealThreshold;
+	type Currency = Balances;
+	type ReserveId = RandomnessReserveId;
+	type RevealBond = ConstU64<REVEAL_BOND>;
+	type Slash = ();
+	type MaxParticipants = ConstU32<{ PARTICIPANTS as u32 }>;
+	type UnsignedPriority = ConstU64<100>;
+	type WeightInfo = ();
+}
+
+/// Balance of every participant but the last, who cannot afford the bond.
+pub const BALANCE: u64 = 100;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut balances: Vec<_> = (1..PARTICIPANTS).map(|i| (account(i), BALANCE)).collect();
+	balances.push((account(PARTICIPANTS), REVEAL_BOND - 1));
+	pallet_balances::GenesisConfig::<Test> { balances }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Runs the beacon's block initialization up to block `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		RandomnessBeacon::on_initialize(next);
+	}
+}
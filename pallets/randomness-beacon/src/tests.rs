@@ -0,0 +1,179 @@
+//! Tests of the randomness beacon pallet.
+
+use crate::{mock::*, Call, Contribution, ContributionOf, Error, Event as BeaconEvent, Seed};
+use codec::Encode;
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Randomness, ValidateUnsigned},
+};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	MultiSignature, MultiSigner,
+};
+
+fn secret(i: u8) -> H256 {
+	H256::repeat_byte(i)
+}
+
+fn contribution(i: u8, value: H256) -> ContributionOf<Test> {
+	Contribution {
+		public: MultiSigner::from(key(i).public()),
+		round: RandomnessBeacon::round_of(System::block_number()),
+		value,
+	}
+}
+
+fn signature(contribution: &ContributionOf<Test>, signer: &sr25519::Pair) -> MultiSignature {
+	signer.sign(&contribution.encode()).into()
+}
+
+fn commit(i: u8) -> frame_support::dispatch::DispatchResult {
+	let commitment = RandomnessBeacon::commitment_of(&account(i), &secret(i));
+	let contribution = contribution(i, commitment);
+	let signature = signature(&contribution, &key(i));
+	RandomnessBeacon::commit(Origin::none(), contribution, signature)
+}
+
+fn reveal(i: u8, value: H256) -> frame_support::dispatch::DispatchResult {
+	let contribution = contribution(i, value);
+	let signature = signature(&contribution, &key(i));
+	RandomnessBeacon::reveal(Origin::none(), contribution, signature)
+}
+
+/// Block at which the reveal phase of round 0 starts.
+const REVEAL_PHASE: u64 = ROUND_LENGTH / 2;
+
+#[test]
+fn rounds_with_enough_reveals_renew_the_seed() {
+	new_test_ext().execute_with(|| {
+		for i in 1..=3 {
+			assert_ok!(commit(i));
+		}
+		assert_eq!(Balances::reserved_balance(account(1)), REVEAL_BOND);
+
+		run_to_block(REVEAL_PHASE);
+		assert_ok!(reveal(1, secret(1)));
+		assert_ok!(reveal(2, secret(2)));
+		assert_eq!(Balances::reserved_balance(account(1)), 0);
+
+		run_to_block(ROUND_LENGTH);
+		assert_ne!(RandomnessBeacon::seed(), H256::zero());
+		assert_eq!(RandomnessBeacon::seed_block(), ROUND_LENGTH);
+		System::assert_has_event(BeaconEvent::NewSeed { round: 0, reveals: 2 }.into());
+
+		let (random, known_since) = RandomnessBeacon::random(b"subject");
+		assert_eq!(known_since, ROUND_LENGTH);
+		assert_ne!(random, RandomnessBeacon::random(b"other subject").0);
+	});
+}
+
+#[test]
+fn missed_reveals_lose_their_bond() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(commit(1));
+		assert_ok!(commit(2));
+		run_to_block(REVEAL_PHASE);
+		assert_ok!(reveal(1, secret(1)));
+
+		run_to_block(ROUND_LENGTH);
+		System::assert_has_event(
+			BeaconEvent::RevealMissed { who: account(2), round: 0, slashed: REVEAL_BOND }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(account(2)), 0);
+		assert_eq!(Balances::free_balance(account(2)), BALANCE - REVEAL_BOND);
+		assert_eq!(Balances::free_balance(account(1)), BALANCE);
+	});
+}
+
+#[test]
+fn rounds_short_of_the_threshold_keep_the_seed() {
+	new_test_ext().execute_with(|| {
+		// Two of the four participants must reveal.
+		assert_eq!(RandomnessBeacon::min_reveals(), 2);
+		assert_ok!(commit(1));
+		run_to_block(REVEAL_PHASE);
+		assert_ok!(reveal(1, secret(1)));
+
+		run_to_block(ROUND_LENGTH);
+		System::assert_has_event(BeaconEvent::RoundFailed { round: 0, reveals: 1 }.into());
+		assert_eq!(RandomnessBeacon::seed(), H256::zero());
+		assert_eq!(RandomnessBeacon::seed_block(), 0);
+	});
+}
+
+#[test]
+fn the_seed_does_not_depend_on_the_order_of_reveals() {
+	let seed = |order: [u8; 3]| {
+		new_test_ext().execute_with(|| {
+			for i in 1..=3 {
+				assert_ok!(commit(i));
+			}
+			run_to_block(REVEAL_PHASE);
+			for i in order {
+				assert_ok!(reveal(i, secret(i)));
+			}
+			run_to_block(ROUND_LENGTH);
+			Seed::<Test>::get()
+		})
+	};
+
+	assert_eq!(seed([1, 2, 3]), seed([3, 1, 2]));
+}
+
+#[test]
+fn contributions_follow_the_phases() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(commit(1));
+		assert_noop!(commit(1), Error::<Test>::AlreadyCommitted);
+		assert_noop!(reveal(1, secret(1)), Error::<Test>::WrongPhase);
+
+		run_to_block(REVEAL_PHASE);
+		assert_noop!(commit(2), Error::<Test>::WrongPhase);
+		assert_noop!(reveal(2, secret(2)), Error::<Test>::NotCommitted);
+		assert_noop!(reveal(1, secret(2)), Error::<Test>::CommitmentMismatch);
+		assert_ok!(reveal(1, secret(1)));
+		assert_noop!(reveal(1, secret(1)), Error::<Test>::AlreadyRevealed);
+
+		// A reveal for the round that ended is stale.
+		let late = contribution(1, secret(1));
+		run_to_block(ROUND_LENGTH);
+		assert_noop!(
+			RandomnessBeacon::reveal(Origin::none(), late.clone(), signature(&late, &key(1))),
+			Error::<Test>::WrongRound
+		);
+	});
+}
+
+#[test]
+fn only_participants_able_to_bond_commit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(commit(PARTICIPANTS + 1), Error::<Test>::NotParticipant);
+		assert_noop!(commit(PARTICIPANTS), Error::<Test>::InsufficientBond);
+	});
+}
+
+#[test]
+fn unsigned_contributions_must_be_signed_by_the_participant() {
+	new_test_ext().execute_with(|| {
+		let validate = |contribution: &ContributionOf<Test>, signer: &sr25519::Pair| {
+			let call = Call::commit {
+				contribution: contribution.clone(),
+				signature: signature(contribution, signer),
+			};
+			RandomnessBeacon::validate_unsigned(TransactionSource::External, &call)
+		};
+		let contribution = contribution(1, H256::repeat_byte(9));
+
+		assert!(validate(&contribution, &key(1)).is_ok());
+		assert_eq!(validate(&contribution, &key(2)), InvalidTransaction::BadProof.into());
+
+		let outsider = self::contribution(PARTICIPANTS + 1, H256::repeat_byte(9));
+		assert_eq!(
+			validate(&outsider, &key(PARTICIPANTS + 1)),
+			InvalidTransaction::BadSigner.into()
+		);
+		let unbonded = self::contribution(PARTICIPANTS, H256::repeat_byte(9));
+		assert_eq!(validate(&unbonded, &key(PARTICIPANTS)), InvalidTransaction::Payment.into());
+	});
+}
@@ -0,0 +1,83 @@
+//! Weights for pallet_randomness_beacon.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_randomness_beacon.
+pub trait WeightInfo {
+	fn commit(p: u32) -> Weight;
+	fn reveal(p: u32) -> Weight;
+	fn close_round(p: u32) -> Weight;
+}
+
+/// Weights for pallet_randomness_beacon using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: RandomnessBeacon Commitments (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn commit(p: u32) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: RandomnessBeacon Commitments (r:1 w:1)
+	// Storage: RandomnessBeacon Accumulator (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn reveal(p: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: RandomnessBeacon Commitments (r:1 w:1)
+	// Storage: RandomnessBeacon Accumulator (r:1 w:1)
+	// Storage: RandomnessBeacon Seed (r:1 w:1)
+	// Storage: RandomnessBeacon SeedBlock (r:0 w:1)
+	// Storage: Aura Authorities (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn close_round(p: u32) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((15_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn commit(p: u32) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn reveal(p: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((100_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn close_round(p: u32) -> Weight {
+		(10_000_000 as Weight)
+			.saturating_add((15_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+	}
+}
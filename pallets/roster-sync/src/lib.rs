@@ -0,0 +1,494 @@
+//! # Roster Sync Pallet
+//!
+//! Keeps on-chain licenses in line with the official rosters boards maintain in
+//! their state databases.
+//!
+//! Each board can have a roster feed: an HTTP endpoint serving the board's
+//! roster signed by the licensing authority, see [`roster`] for the format.
+//! Every `SyncInterval` blocks the offchain worker of each node holding the
+//! feed's reporter key fetches the roster, checks its signature and compares it
+//! with the board's licenses on chain. Every difference is reported through a
+//! signed transaction as a [`Correction`] proposed to the board, which applies
+//! it through the licensure pallet and then resolves it. A roster is only
+//! compared once, so corrections the board dismisses are not reported again
+//! until the roster changes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod roster;
+pub mod weights;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, traits::Get, BoundedVec};
+use frame_system::offchain::{AppCrypto, SendSignedTransaction, Signer};
+use pallet_licensure::{Discipline, LicenseNumberOf, LicenseStatus, Licenses, LicensureAction};
+use roster::RosterEntry;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	offchain::{http, storage::StorageValueRef, Duration},
+	traits::{Hash, IdentifyAccount, Zero},
+	RuntimeAppPublic, RuntimeDebug,
+};
+use sp_std::{collections::btree_set::BTreeSet, prelude::*};
+
+const LOG_TARGET: &str = "runtime::roster-sync";
+
+/// Prefix of the offchain storage keys holding the hash of the last roster
+/// compared for each board.
+const SYNCED_PREFIX: &[u8] = b"roster-sync/synced";
+
+/// How long the offchain worker waits for a roster feed.
+const FETCH_TIMEOUT_MILLIS: u64 = 10_000;
+
+/// Keys roster reporters sign their transactions with.
+pub mod crypto {
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		KeyTypeId, MultiSignature, MultiSigner,
+	};
+
+	/// Key type of roster reporter keys.
+	pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"rost");
+
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct ReporterId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for ReporterId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Where a board's roster is published.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RosterFeed<AccountId, Url> {
+	/// URL the roster is fetched from.
+	pub url: Url,
+	/// sr25519 public key the licensing authority signs the roster with.
+	pub signer: [u8; 32],
+	/// Account whose offchain worker reports corrections.
+	pub reporter: AccountId,
+}
+
+/// A difference between a board's roster and its licenses on chain, as the
+/// roster has it.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Correction<AccountId, BlockNumber> {
+	/// The roster lists a license which is not on chain.
+	Missing {
+		holder: AccountId,
+		discipline: Discipline,
+		status: LicenseStatus,
+		expires_at: BlockNumber,
+	},
+	/// The roster differs from the license on chain in the fields set.
+	Mismatch {
+		holder: Option<AccountId>,
+		discipline: Option<Discipline>,
+		status: Option<LicenseStatus>,
+		expires_at: Option<BlockNumber>,
+	},
+	/// The license is on chain but not on the roster.
+	Unlisted,
+}
+
+/// A correction awaiting the board.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ReportedCorrection<AccountId, BlockNumber, Hash> {
+	pub correction: Correction<AccountId, BlockNumber>,
+	/// Hash of the roster file the correction comes from.
+	pub roster: Hash,
+	pub reported_at: BlockNumber,
+}
+
+pub type RosterFeedOf<T> =
+	RosterFeed<<T as frame_system::Config>::AccountId, BoundedVec<u8, <T as Config>::MaxUrlLength>>;
+pub type CorrectionOf<T> =
+	Correction<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+pub type ReportedCorrectionOf<T> = ReportedCorrection<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::{offchain::CreateSignedTransaction, pallet_prelude::*};
+	use sp_runtime::traits::StaticLookup;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: pallet_licensure::Config + CreateSignedTransaction<Call<Self>> {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Key reporters sign their transactions with.
+		type ReporterId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Origin allowed to set the roster feeds of boards.
+		type FeedOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Blocks between two comparisons of the rosters.
+		#[pallet::constant]
+		type SyncInterval: Get<Self::BlockNumber>;
+
+		/// Maximum length of a roster feed URL.
+		#[pallet::constant]
+		type MaxUrlLength: Get<u32>;
+
+		/// Maximum number of corrections a reporter submits per board and sync.
+		/// The rest follow at the next sync.
+		#[pallet::constant]
+		type MaxCorrectionsPerSync: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Roster feed of each board.
+	#[pallet::storage]
+	#[pallet::getter(fn feed)]
+	pub type Feeds<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::BoardId, RosterFeedOf<T>, OptionQuery>;
+
+	/// Corrections awaiting each board, by license number.
+	#[pallet::storage]
+	#[pallet::getter(fn correction)]
+	pub type Corrections<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::BoardId,
+		Blake2_128Concat,
+		LicenseNumberOf<T>,
+		ReportedCorrectionOf<T>,
+		OptionQuery,
+	>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Roster feeds from genesis: board, URL, signer key and reporter.
+		pub feeds: Vec<(T::BoardId, Vec<u8>, [u8; 32], T::AccountId)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { feeds: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (board, url, signer, reporter) in &self.feeds {
+				let url = url.clone().try_into().expect("Genesis feed URLs must fit; qed");
+				Feeds::<T>::insert(
+					board,
+					RosterFeed { url, signer: *signer, reporter: reporter.clone() },
+				);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The roster feed of a board was set.
+		FeedSet { board: T::BoardId, reporter: T::AccountId },
+		/// The roster feed of a board was removed.
+		FeedRemoved { board: T::BoardId },
+		/// A difference with the roster was reported to the board.
+		CorrectionReported {
+			board: T::BoardId,
+			number: LicenseNumberOf<T>,
+			correction: CorrectionOf<T>,
+		},
+		/// The board dealt with a correction, applying it or not.
+		CorrectionResolved { board: T::BoardId, number: LicenseNumberOf<T>, applied: bool },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The feed URL is longer than `MaxUrlLength`.
+		UrlTooLong,
+		/// The board has no roster feed.
+		NoFeed,
+		/// Only the feed's reporter may report corrections.
+		NotReporter,
+		/// License number is longer than the licensure pallet allows.
+		LicenseNumberTooLong,
+		/// No correction is awaiting the board for this license.
+		UnknownCorrection,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn offchain_worker(now: T::BlockNumber) {
+			if !(now % T::SyncInterval::get()).is_zero() {
+				return
+			}
+			for (board, feed) in Feeds::<T>::iter() {
+				if let Err(e) = Self::sync(board, feed) {
+					log::warn!(target: LOG_TARGET, "Roster sync of {:?} failed: {}", board, e);
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Publish the roster of `board` at `url`, signed with the sr25519 key
+		/// `signer`, with `reporter` reporting the corrections.
+		///
+		/// The origin must be `FeedOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_feed())]
+		pub fn set_feed(
+			origin: OriginFor<T>,
+			board: T::BoardId,
+			url: Vec<u8>,
+			signer: [u8; 32],
+			reporter: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			let url: BoundedVec<_, _> = url.try_into().map_err(|_| Error::<T>::UrlTooLong)?;
+			let reporter = T::Lookup::lookup(reporter)?;
+
+			Feeds::<T>::insert(board, RosterFeed { url, signer, reporter: reporter.clone() });
+
+			Self::deposit_event(Event::FeedSet { board, reporter });
+			Ok(())
+		}
+
+		/// Stop syncing the roster of `board`. Corrections already reported
+		/// stay until the board resolves them.
+		///
+		/// The origin must be `FeedOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_feed())]
+		pub fn remove_feed(origin: OriginFor<T>, board: T::BoardId) -> DispatchResult {
+			T::FeedOrigin::ensure_origin(origin)?;
+			ensure!(Feeds::<T>::contains_key(board), Error::<T>::NoFeed);
+
+			Feeds::<T>::remove(board);
+
+			Self::deposit_event(Event::FeedRemoved { board });
+			Ok(())
+		}
+
+		/// Report a difference between the roster of `board`, the file with hash
+		/// `roster`, and license `number` on chain, replacing any correction
+		/// already reported for the license.
+		///
+		/// Submitted by the offchain worker of the feed's reporter.
+		#[pallet::weight(<T as Config>::WeightInfo::report_correction())]
+		pub fn report_correction(
+			origin: OriginFor<T>,
+			board: T::BoardId,
+			number: Vec<u8>,
+			correction: CorrectionOf<T>,
+			roster: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let feed = Feeds::<T>::get(board).ok_or(Error::<T>::NoFeed)?;
+			ensure!(feed.reporter == who, Error::<T>::NotReporter);
+			let number: LicenseNumberOf<T> =
+				number.try_into().map_err(|_| Error::<T>::LicenseNumberTooLong)?;
+
+			Corrections::<T>::insert(
+				board,
+				&number,
+				ReportedCorrection {
+					correction: correction.clone(),
+					roster,
+					reported_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+
+			Self::deposit_event(Event::CorrectionReported { board, number, correction });
+			Ok(())
+		}
+
+		/// Resolve the correction reported for license `number`, once the board
+		/// applied it through the licensure pallet or decided against it.
+		///
+		/// The origin must be the board, with the approval it requires for
+		/// `ResolveRosterCorrection`.
+		#[pallet::weight(<T as Config>::WeightInfo::resolve_correction())]
+		pub fn resolve_correction(
+			origin: OriginFor<T>,
+			number: Vec<u8>,
+			applied: bool,
+		) -> DispatchResult {
+			let board = pallet_licensure::Pallet::<T>::ensure_board(
+				origin,
+				LicensureAction::ResolveRosterCorrection,
+			)?;
+			let number: LicenseNumberOf<T> =
+				number.try_into().map_err(|_| Error::<T>::LicenseNumberTooLong)?;
+			ensure!(Corrections::<T>::contains_key(board, &number), Error::<T>::UnknownCorrection);
+
+			Corrections::<T>::remove(board, &number);
+
+			Self::deposit_event(Event::CorrectionResolved { board, number, applied });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Compare the roster of `board` with its licenses on chain and report the
+	/// differences, if this node holds the feed's reporter key.
+	fn sync(board: T::BoardId, feed: RosterFeedOf<T>) -> Result<(), &'static str> {
+		let reporter = match Self::local_reporter_key(&feed.reporter) {
+			Some(public) => public,
+			None => return Ok(()),
+		};
+
+		let file = Self::fetch(&feed.url)?;
+		let roster_hash = T::Hashing::hash(&file);
+		let synced_key = (SYNCED_PREFIX, board).encode();
+		let synced = StorageValueRef::persistent(&synced_key);
+		if let Ok(Some(hash)) = synced.get::<T::Hash>() {
+			if hash == roster_hash {
+				return Ok(())
+			}
+		}
+
+		let entries =
+			roster::parse::<T::AccountId, T::BlockNumber>(&file, &feed.signer).map_err(|e| {
+				log::warn!(target: LOG_TARGET, "Rejected roster of {:?}: {:?}", board, e);
+				"Invalid roster"
+			})?;
+		let corrections = Self::diff(board, entries);
+
+		let signer = Signer::<T, T::ReporterId>::all_accounts().with_filter(vec![reporter]);
+		let limit = T::MaxCorrectionsPerSync::get() as usize;
+		for (number, correction) in corrections.iter().take(limit) {
+			let results = signer.send_signed_transaction(|_| Call::report_correction {
+				board,
+				number: number.to_vec(),
+				correction: correction.clone(),
+				roster: roster_hash,
+			});
+			for (_, result) in results {
+				result.map_err(|()| "Failed to submit a correction")?;
+			}
+		}
+
+		// Corrections left over are submitted at the next sync.
+		if corrections.len() <= limit {
+			synced.set(&roster_hash);
+		}
+		Ok(())
+	}
+
+	/// The key of `reporter` in the local keystore, if any.
+	fn local_reporter_key(reporter: &T::AccountId) -> Option<T::Public> {
+		<T::ReporterId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+			.into_iter()
+			.map(|key| {
+				let generic: <T::ReporterId as AppCrypto<T::Public, T::Signature>>::GenericPublic =
+					key.into();
+				let public: T::Public = generic.into();
+				public
+			})
+			.find(|public| public.clone().into_account() == *reporter)
+	}
+
+	/// Body of the roster served at `url`.
+	fn fetch(url: &[u8]) -> Result<Vec<u8>, &'static str> {
+		let url = sp_std::str::from_utf8(url).map_err(|_| "Roster feed URL is not UTF-8")?;
+		let deadline =
+			sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT_MILLIS));
+		let pending = http::Request::get(url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| "Failed to request the roster")?;
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| "Roster feed timed out")?
+			.map_err(|_| "Roster request failed")?;
+		if response.code != 200 {
+			return Err("Roster feed answered with an error")
+		}
+		Ok(response.body().collect())
+	}
+
+	/// Differences between the roster `entries` of `board` and its licenses on
+	/// chain which are not awaiting the board already.
+	fn diff(
+		board: T::BoardId,
+		entries: Vec<RosterEntry<T::AccountId, T::BlockNumber>>,
+	) -> Vec<(LicenseNumberOf<T>, CorrectionOf<T>)> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut listed = BTreeSet::new();
+		let mut corrections = Vec::new();
+
+		for entry in entries {
+			let number: LicenseNumberOf<T> = match entry.number.try_into() {
+				Ok(number) => number,
+				Err(_) => continue,
+			};
+			let correction = match Licenses::<T>::get(&number) {
+				None => Some(Correction::Missing {
+					holder: entry.holder,
+					discipline: entry.discipline,
+					status: entry.status,
+					expires_at: entry.expires_at,
+				}),
+				// Numbers are unique across boards; one issued by another board
+				// is that board's business.
+				Some(license) if license.board != board => None,
+				Some(license) => {
+					let status = license.status_at(&now);
+					let mismatch = Correction::Mismatch {
+						holder: Some(entry.holder).filter(|holder| *holder != license.holder),
+						discipline: Some(entry.discipline)
+							.filter(|discipline| *discipline != license.discipline),
+						status: Some(entry.status).filter(|s| *s != status),
+						expires_at: Some(entry.expires_at)
+							.filter(|expires_at| *expires_at != license.expires_at),
+					};
+					match mismatch {
+						Correction::Mismatch {
+							holder: None,
+							discipline: None,
+							status: None,
+							expires_at: None,
+						} => None,
+						mismatch => Some(mismatch),
+					}
+				},
+			};
+			if let Some(correction) = correction {
+				corrections.push((number.clone(), correction));
+			}
+			listed.insert(number);
+		}
+
+		for (number, license) in Licenses::<T>::iter() {
+			if license.board == board && !listed.contains(&number) {
+				corrections.push((number, Correction::Unlisted));
+			}
+		}
+
+		corrections.retain(|(number, correction)| {
+			Corrections::<T>::get(board, number).map_or(true, |r| r.correction != *correction)
+		});
+		corrections
+	}
+}
@@ -0,0 +1,151 @@
+//! Test runtime of the roster sync pallet.
+
+use crate as pallet_roster_sync;
+use frame_support::traits::{ConstU32, ConstU64, EnsureOrigin, Everything};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes},
+	EnsureRoot,
+};
+use pallet_licensure::{BoardApproval, BoardRegistrar, IdentityJudgement};
+use sp_core::H256;
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, IdentityLookup},
+	AccountId32, DispatchResult, MultiSignature, MultiSigner,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Licensure: pallet_licensure::{Pallet, Call, Storage, Config<T>, Event<T>},
+		RosterSync: pallet_roster_sync::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// Root speaks for [`BOARD`] with the approval of all its members.
+pub struct EnsureBoard;
+impl EnsureOrigin<Origin> for EnsureBoard {
+	type Success = (u32, BoardApproval);
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		EnsureRoot::<AccountId32>::try_origin(o)
+			.map(|()| (BOARD, BoardApproval::Motion { ayes: 1, members: 1 }))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::root()
+	}
+}
+
+/// Every board vouches for every identity.
+pub struct Registrar;
+impl BoardRegistrar<AccountId32, u32> for Registrar {
+	fn provide_judgement(_: u32, _: &AccountId32, _: IdentityJudgement) -> DispatchResult {
+		Ok(())
+	}
+
+	fn judgement(_: u32, _: &AccountId32) -> Option<IdentityJudgement> {
+		Some(IdentityJudgement::KnownGood)
+	}
+}
+
+impl pallet_licensure::Config for Test {
+	type Event = Event;
+	type BoardId = u32;
+	type BoardOrigin = EnsureBoard;
+	type SettingsOrigin = EnsureRoot<AccountId32>;
+	type Registrar = Registrar;
+	type MaxLicenseNumberLength = ConstU32<16>;
+	type MaxNameLength = ConstU32<32>;
+	type MaxLicensesPerAccount = ConstU32<4>;
+	type MaxDisciplinaryActions = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+impl SigningTypes for Test {
+	type Public = MultiSigner;
+	type Signature = MultiSignature;
+}
+
+impl<C> SendTransactionTypes<C> for Test
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = TestXt<Call, ()>;
+}
+
+impl<C> CreateSignedTransaction<C> for Test
+where
+	Call: From<C>,
+{
+	fn create_transaction<A: AppCrypto<MultiSigner, MultiSignature>>(
+		call: Call,
+		_: MultiSigner,
+		_: AccountId32,
+		nonce: u64,
+	) -> Option<(Call, (u64, ()))> {
+		Some((call, (nonce, ())))
+	}
+}
+
+impl pallet_roster_sync::Config for Test {
+	type Event = Event;
+	type ReporterId = pallet_roster_sync::crypto::ReporterId;
+	type FeedOrigin = EnsureRoot<AccountId32>;
+	type SyncInterval = ConstU64<10>;
+	type MaxUrlLength = ConstU32<64>;
+	type MaxCorrectionsPerSync = ConstU32<4>;
+	type WeightInfo = ();
+}
+
+/// The board whose roster is compared.
+pub const BOARD: u32 = 1;
+/// Another board, issuing licenses of its own.
+pub const OTHER_BOARD: u32 = 2;
+
+pub fn account(i: u8) -> AccountId32 {
+	[i; 32].into()
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,118 @@
+//! Roster files published by licensing authorities.
+//!
+//! A roster is a text file with one license per line:
+//!
+//! ```text
+//! <number>,<holder>,<discipline>,<status>,<expires at>
+//! ```
+//!
+//! `holder` is the hex encoded account of the licensee, `discipline` and
+//! `status` are the lower case names of [`Discipline`] and [`LicenseStatus`]
+//! variants and `expires at` is the block at which the license expires. Empty
+//! lines and lines starting with `#` are skipped. The last line reads
+//! `signature,<hex>`: the sr25519 signature of the authority's roster key over
+//! every byte before that line.
+
+use codec::Decode;
+use pallet_licensure::{Discipline, LicenseStatus};
+use sp_core::sr25519;
+use sp_runtime::RuntimeDebug;
+use sp_std::{prelude::*, str};
+
+const SIGNATURE_PREFIX: &[u8] = b"signature,";
+
+/// A license as listed on a roster.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RosterEntry<AccountId, BlockNumber> {
+	pub number: Vec<u8>,
+	pub holder: AccountId,
+	pub discipline: Discipline,
+	pub status: LicenseStatus,
+	pub expires_at: BlockNumber,
+}
+
+/// Why a roster file was rejected.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum RosterError {
+	/// The file does not end with a signature line.
+	MissingSignature,
+	/// The signature is not the authority's.
+	BadSignature,
+	/// The line with this 1-based number cannot be read.
+	InvalidLine(u32),
+}
+
+/// Check the signature of `file` against `signer` and read its entries.
+pub fn parse<AccountId: Decode, BlockNumber: From<u32>>(
+	file: &[u8],
+	signer: &[u8; 32],
+) -> Result<Vec<RosterEntry<AccountId, BlockNumber>>, RosterError> {
+	let file = file.strip_suffix(b"\n").unwrap_or(file);
+	let signature_start = file.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+	let (body, signature_line) = file.split_at(signature_start);
+	let signature = signature_line
+		.strip_prefix(SIGNATURE_PREFIX)
+		.and_then(|hex| decode_hex(trim(hex)))
+		.and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+		.ok_or(RosterError::MissingSignature)?;
+	if !sp_io::crypto::sr25519_verify(
+		&sr25519::Signature::from_raw(signature),
+		body,
+		&sr25519::Public::from_raw(*signer),
+	) {
+		return Err(RosterError::BadSignature)
+	}
+
+	body.split(|b| *b == b'\n')
+		.enumerate()
+		.filter(|(_, line)| {
+			let line = trim(line);
+			!line.is_empty() && !line.starts_with(b"#")
+		})
+		.map(|(i, line)| parse_entry(trim(line)).ok_or(RosterError::InvalidLine(i as u32 + 1)))
+		.collect()
+}
+
+fn parse_entry<AccountId: Decode, BlockNumber: From<u32>>(
+	line: &[u8],
+) -> Option<RosterEntry<AccountId, BlockNumber>> {
+	let mut fields = line.split(|b| *b == b',').map(trim);
+	let number = fields.next().filter(|number| !number.is_empty())?.to_vec();
+	let holder = decode_hex(fields.next()?)?;
+	let holder = AccountId::decode(&mut &holder[..]).ok()?;
+	let discipline = match fields.next()? {
+		b"civil" => Discipline::Civil,
+		b"structural" => Discipline::Structural,
+		b"mechanical" => Discipline::Mechanical,
+		b"electrical" => Discipline::Electrical,
+		b"chemical" => Discipline::Chemical,
+		b"environmental" => Discipline::Environmental,
+		b"geotechnical" => Discipline::Geotechnical,
+		b"other" => Discipline::Other,
+		_ => return None,
+	};
+	let status = match fields.next()? {
+		b"active" => LicenseStatus::Active,
+		b"expired" => LicenseStatus::Expired,
+		b"suspended" => LicenseStatus::Suspended,
+		b"revoked" => LicenseStatus::Revoked,
+		_ => return None,
+	};
+	let expires_at: u32 = str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+	if fields.next().is_some() {
+		return None
+	}
+
+	Some(RosterEntry { number, holder, discipline, status, expires_at: expires_at.into() })
+}
+
+/// Bytes of `hex`, with or without a `0x` prefix.
+fn decode_hex(hex: &[u8]) -> Option<Vec<u8>> {
+	hex::decode(hex.strip_prefix(b"0x").unwrap_or(hex)).ok()
+}
+
+fn trim(bytes: &[u8]) -> &[u8] {
+	let start = bytes.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+	let end = bytes.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(start, |i| i + 1);
+	&bytes[start..end]
+}
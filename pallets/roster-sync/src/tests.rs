@@ -0,0 +1,247 @@
+//! Tests of the roster sync pallet.
+
+use crate::{
+	mock::*,
+	roster::{self, RosterEntry, RosterError},
+	Correction, CorrectionOf, Corrections, ReportedCorrection,
+};
+use codec::Encode;
+use pallet_licensure::{Discipline, License, LicenseNumberOf, LicenseStatus, Licenses};
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::AccountId32;
+
+fn authority() -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[1; 32])
+}
+
+/// `body` followed by the signature of `signer` over it.
+fn signed(body: &str, signer: &sr25519::Pair) -> Vec<u8> {
+	let signature = signer.sign(body.as_bytes());
+	format!("{}signature,0x{}\n", body, hex::encode(signature.0)).into_bytes()
+}
+
+fn line(number: &str, holder: u8, rest: &str) -> String {
+	format!("{},{},{}\n", number, hex::encode(account(holder).encode()), rest)
+}
+
+fn parse(file: &[u8]) -> Result<Vec<RosterEntry<AccountId32, u64>>, RosterError> {
+	roster::parse(file, &authority().public().0)
+}
+
+fn number(number: &str) -> LicenseNumberOf<Test> {
+	number.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn entry(number: &str, holder: u8, status: LicenseStatus) -> RosterEntry<AccountId32, u64> {
+	RosterEntry {
+		number: number.as_bytes().to_vec(),
+		holder: account(holder),
+		discipline: Discipline::Civil,
+		status,
+		expires_at: 100,
+	}
+}
+
+fn license(number: &str, board: u32, holder: u8) {
+	Licenses::<Test>::insert(
+		self::number(number),
+		License {
+			holder: account(holder),
+			board,
+			discipline: Discipline::Civil,
+			status: LicenseStatus::Active,
+			issued_at: 1,
+			expires_at: 100,
+		},
+	);
+}
+
+fn diff(
+	entries: Vec<RosterEntry<AccountId32, u64>>,
+) -> Vec<(LicenseNumberOf<Test>, CorrectionOf<Test>)> {
+	RosterSync::diff(BOARD, entries)
+}
+
+#[test]
+fn signed_rosters_are_read() {
+	let body = format!(
+		"# Roster of board 1\n{}\n  {}",
+		line("PE-1", 2, "civil,active,100"),
+		line("PE-2", 3, "structural,suspended,200"),
+	);
+
+	assert_eq!(
+		parse(&signed(&body, &authority())),
+		Ok(vec![
+			entry("PE-1", 2, LicenseStatus::Active),
+			RosterEntry {
+				number: b"PE-2".to_vec(),
+				holder: account(3),
+				discipline: Discipline::Structural,
+				status: LicenseStatus::Suspended,
+				expires_at: 200,
+			},
+		])
+	);
+	assert_eq!(parse(&signed("", &authority())), Ok(vec![]));
+}
+
+#[test]
+fn rosters_must_carry_the_authoritys_signature() {
+	let body = line("PE-1", 2, "civil,active,100");
+	let file = signed(&body, &authority());
+
+	assert_eq!(
+		parse(&signed(&body, &sr25519::Pair::from_seed(&[2; 32]))),
+		Err(RosterError::BadSignature)
+	);
+	let tampered = String::from_utf8(file.clone()).unwrap().replace("PE-1", "PE-9");
+	assert_eq!(parse(tampered.as_bytes()), Err(RosterError::BadSignature));
+
+	assert_eq!(parse(body.as_bytes()), Err(RosterError::MissingSignature));
+	let truncated = &file[..file.len() - 3];
+	assert_eq!(parse(truncated), Err(RosterError::MissingSignature));
+	let not_hex = String::from_utf8(file).unwrap().replace("signature,0x", "signature,0xzz");
+	assert_eq!(parse(not_hex.as_bytes()), Err(RosterError::MissingSignature));
+}
+
+#[test]
+fn malformed_lines_are_reported_by_number() {
+	let holder = hex::encode(account(2).encode());
+	for (malformed, case) in [
+		(line("PE-2", 2, "mining,active,100"), "unknown discipline"),
+		(line("PE-2", 2, "civil,retired,100"), "unknown status"),
+		(line("PE-2", 2, "civil,active,soon"), "expiry not a block"),
+		(line("PE-2", 2, "civil,active,100,extra"), "extra field"),
+		(line("PE-2", 2, "civil,active"), "missing field"),
+		(line("", 2, "civil,active,100"), "empty number"),
+		(format!("PE-2,{},civil,active,100\n", &holder[1..]), "odd length holder"),
+		(format!("PE-2,{},civil,active,100\n", &holder[2..]), "short holder"),
+	] {
+		let body = format!("{}\n# comment\n{}", line("PE-1", 2, "civil,active,100"), malformed);
+		assert_eq!(
+			parse(&signed(&body, &authority())),
+			Err(RosterError::InvalidLine(4)),
+			"{}",
+			case
+		);
+	}
+}
+
+#[test]
+fn licenses_missing_from_the_chain_are_reported() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			diff(vec![entry("PE-1", 2, LicenseStatus::Active)]),
+			vec![(
+				number("PE-1"),
+				Correction::Missing {
+					holder: account(2),
+					discipline: Discipline::Civil,
+					status: LicenseStatus::Active,
+					expires_at: 100,
+				}
+			)]
+		);
+	});
+}
+
+#[test]
+fn mismatches_only_carry_the_fields_that_differ() {
+	new_test_ext().execute_with(|| {
+		license("PE-1", BOARD, 2);
+		license("PE-2", BOARD, 3);
+		assert_eq!(
+			diff(vec![
+				entry("PE-1", 2, LicenseStatus::Active),
+				entry("PE-2", 3, LicenseStatus::Active)
+			]),
+			vec![]
+		);
+
+		let mut moved = entry("PE-2", 4, LicenseStatus::Active);
+		moved.expires_at = 200;
+		assert_eq!(
+			diff(vec![entry("PE-1", 2, LicenseStatus::Revoked), moved]),
+			vec![
+				(
+					number("PE-1"),
+					Correction::Mismatch {
+						holder: None,
+						discipline: None,
+						status: Some(LicenseStatus::Revoked),
+						expires_at: None,
+					}
+				),
+				(
+					number("PE-2"),
+					Correction::Mismatch {
+						holder: Some(account(4)),
+						discipline: None,
+						status: None,
+						expires_at: Some(200),
+					}
+				),
+			]
+		);
+
+		// The roster lists the license as expired once it expired on chain too.
+		System::set_block_number(100);
+		assert_eq!(
+			diff(vec![
+				entry("PE-1", 2, LicenseStatus::Expired),
+				entry("PE-2", 3, LicenseStatus::Expired)
+			]),
+			vec![]
+		);
+	});
+}
+
+#[test]
+fn licenses_missing_from_the_roster_are_reported() {
+	new_test_ext().execute_with(|| {
+		license("PE-1", BOARD, 2);
+		license("PE-2", BOARD, 3);
+		license("PE-3", OTHER_BOARD, 4);
+
+		// Licenses of other boards are neither unlisted nor compared.
+		assert_eq!(
+			diff(vec![
+				entry("PE-1", 2, LicenseStatus::Active),
+				entry("PE-3", 5, LicenseStatus::Revoked)
+			]),
+			vec![(number("PE-2"), Correction::Unlisted)]
+		);
+	});
+}
+
+#[test]
+fn corrections_awaiting_the_board_are_not_reported_again() {
+	new_test_ext().execute_with(|| {
+		license("PE-1", BOARD, 2);
+		Corrections::<Test>::insert(
+			BOARD,
+			number("PE-1"),
+			ReportedCorrection {
+				correction: Correction::Unlisted,
+				roster: H256::zero(),
+				reported_at: 1,
+			},
+		);
+
+		assert_eq!(diff(vec![]), vec![]);
+		// A different correction of the same license replaces the one awaiting.
+		assert_eq!(
+			diff(vec![entry("PE-1", 2, LicenseStatus::Suspended)]),
+			vec![(
+				number("PE-1"),
+				Correction::Mismatch {
+					holder: None,
+					discipline: None,
+					status: Some(LicenseStatus::Suspended),
+					expires_at: None,
+				}
+			)]
+		);
+	});
+}
@@ -0,0 +1,76 @@
+//! Weights for pallet_roster_sync.
+//!
+//! These are conservative estimates from the storage accessed by each call until
+//! the pallet gets benchmarks of its own.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_roster_sync.
+pub trait WeightInfo {
+	fn set_feed() -> Weight;
+	fn remove_feed() -> Weight;
+	fn report_correction() -> Weight;
+	fn resolve_correction() -> Weight;
+}
+
+/// Weights for pallet_roster_sync using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: RosterSync Feeds (r:0 w:1)
+	fn set_feed() -> Weight {
+		(15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: RosterSync Feeds (r:1 w:1)
+	fn remove_feed() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: RosterSync Feeds (r:1 w:0)
+	// Storage: RosterSync Corrections (r:0 w:1)
+	fn report_correction() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Licensure ActionRules (r:1 w:0)
+	// Storage: RosterSync Corrections (r:1 w:1)
+	fn resolve_correction() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: RosterSync Feeds (r:0 w:1)
+	fn set_feed() -> Weight {
+		(15_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: RosterSync Feeds (r:1 w:1)
+	fn remove_feed() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: RosterSync Feeds (r:1 w:0)
+	// Storage: RosterSync Corrections (r:0 w:1)
+	fn report_correction() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resolve_correction() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
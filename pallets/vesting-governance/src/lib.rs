@@ -0,0 +1,70 @@
+//! # Vesting Governance Pallet
+//!
+//! `pallet_vesting` only lets root force a vested transfer. This pallet lets a
+//! governance origin, such as a council majority, do the same, so that vesting
+//! grants do not depend on the sudo key.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use pallet::*;
+
+use frame_support::traits::{Currency, Get, LockableCurrency};
+use pallet_vesting::VestingInfo;
+use sp_runtime::traits::StaticLookup;
+
+type BalanceOf<T> = <<T as pallet_vesting::Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::Balance;
+
+type MaxLocksOf<T> = <<T as pallet_vesting::Config>::Currency as LockableCurrency<
+	<T as frame_system::Config>::AccountId,
+>>::MaxLocks;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use pallet_vesting::WeightInfo;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_vesting::Config {
+		/// Origin allowed to force vested transfers.
+		type ForceOrigin: EnsureOrigin<Self::Origin>;
+	}
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Force a vested transfer from `source` to `target`, as
+		/// `pallet_vesting::force_vested_transfer` does for root.
+		///
+		/// The dispatch origin for this call must be `ForceOrigin`.
+		#[pallet::weight(<T as pallet_vesting::Config>::WeightInfo::force_vested_transfer(
+			MaxLocksOf::<T>::get(),
+			T::MAX_VESTING_SCHEDULES,
+		))]
+		pub fn force_vested_transfer(
+			origin: OriginFor<T>,
+			source: <T::Lookup as StaticLookup>::Source,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			pallet_vesting::Pallet::<T>::force_vested_transfer(
+				frame_system::RawOrigin::Root.into(),
+				source,
+				target,
+				schedule,
+			)
+		}
+	}
+}
@@ -0,0 +1,69 @@
+//! Tests of the vesting governance pallet.
+
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError};
+use pallet_vesting::VestingInfo;
+
+#[test]
+fn the_force_origin_makes_vested_transfers() {
+	new_test_ext().execute_with(|| {
+		let schedule = VestingInfo::new(100, 10, 1);
+		assert_ok!(VestingGovernance::force_vested_transfer(
+			Origin::signed(Council::get()),
+			SOURCE,
+			TARGET,
+			schedule,
+		));
+
+		assert_eq!(Balances::free_balance(SOURCE), 900);
+		assert_eq!(Balances::free_balance(TARGET), 110);
+		assert_eq!(pallet_vesting::Vesting::<Test>::get(TARGET).unwrap().to_vec(), vec![schedule]);
+		// Nothing has vested at the starting block.
+		assert_eq!(Balances::usable_balance(TARGET), 10);
+	});
+}
+
+#[test]
+fn other_origins_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let schedule = VestingInfo::new(100, 10, 1);
+		assert_noop!(
+			VestingGovernance::force_vested_transfer(
+				Origin::signed(SOURCE),
+				SOURCE,
+				TARGET,
+				schedule,
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			VestingGovernance::force_vested_transfer(Origin::root(), SOURCE, TARGET, schedule),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn schedules_are_checked_as_for_root() {
+	new_test_ext().execute_with(|| {
+		let council = || Origin::signed(Council::get());
+		assert_noop!(
+			VestingGovernance::force_vested_transfer(
+				council(),
+				SOURCE,
+				TARGET,
+				VestingInfo::new(MIN_VESTED_TRANSFER - 1, 1, 1),
+			),
+			pallet_vesting::Error::<Test>::AmountLow
+		);
+		assert_noop!(
+			VestingGovernance::force_vested_transfer(
+				council(),
+				SOURCE,
+				TARGET,
+				VestingInfo::new(100, 0, 1),
+			),
+			pallet_vesting::Error::<Test>::InvalidScheduleParams
+		);
+	});
+}
@@ -0,0 +1,65 @@
+//! Runtime API definition for the reserves RPC.
+//!
+//! The reserved balance of an account, broken down by the reason each part of
+//! it is reserved for.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Identifier of a named reserve.
+pub type ReserveIdentifier = [u8; 8];
+
+/// Why part of an account's balance is reserved.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum ReserveReason {
+	Escrow,
+	PlanReviewFee,
+	RandomnessBond,
+	ProjectDeposit,
+	DidDeposit,
+	/// A named reserve the runtime does not know the reason of.
+	Other,
+	/// Reserved without a name, as identity, multisig, proxy and contract
+	/// deposits are.
+	Unnamed,
+}
+
+/// Part of an account's reserved balance.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReservedBalance<Balance> {
+	pub reason: ReserveReason,
+	/// Identifier of the named reserve, `None` for the unnamed part.
+	pub id: Option<ReserveIdentifier>,
+	pub amount: Balance,
+}
+
+/// The reserved balance of an account and what it is reserved for.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReserveBreakdown<Balance> {
+	pub total: Balance,
+	/// Parts of `total`, named reserves first. Empty parts are left out.
+	pub reserves: Vec<ReservedBalance<Balance>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the reserved balances of accounts.
+	pub trait ReservesApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Reserved balance of `account`, broken down by reason.
+		fn breakdown(account: AccountId) -> ReserveBreakdown<Balance>;
+	}
+}
@@ -0,0 +1,109 @@
+//! Node-specific RPC methods breaking down the reserved balances of accounts.
+//!
+//! `reserves_getBreakdown` tells apart what an account has reserved as escrowed
+//! payments, plan review fees, randomness bonds, and project and DID deposits
+//! from the unnamed deposits of other pallets. Amounts are numbers, or hex
+//! strings when they do not fit in 64 bits.
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use build3_reserves_rpc_runtime_api::{
+	ReserveBreakdown, ReserveIdentifier, ReserveReason, ReservedBalance,
+	ReservesApi as ReservesRuntimeApi,
+};
+
+const RUNTIME_ERROR: i64 = 1;
+
+/// Part of an account's reserved balance.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReservedBalanceDetails {
+	pub reason: ReserveReason,
+	/// Identifier of the named reserve, `None` for the unnamed part.
+	pub id: Option<String>,
+	pub amount: NumberOrHex,
+}
+
+/// The reserved balance of an account and what it is reserved for.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReserveBreakdownDetails {
+	pub total: NumberOrHex,
+	pub reserves: Vec<ReservedBalanceDetails>,
+}
+
+/// Reserves RPC methods.
+#[rpc]
+pub trait ReservesApi<BlockHash, AccountId> {
+	/// Break down the reserved balance of an account by reason.
+	#[rpc(name = "reserves_getBreakdown")]
+	fn breakdown(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> Result<ReserveBreakdownDetails>;
+}
+
+/// Provides RPC methods to break down reserved balances.
+pub struct Reserves<C, B, Balance> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<C, B, Balance> Reserves<C, B, Balance> {
+	/// Create new `Reserves` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
+	Error {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: "Runtime error".into(),
+		data: Some(format!("{:?}", err).into()),
+	}
+}
+
+impl<C, Block, AccountId, Balance> ReservesApi<<Block as BlockT>::Hash, AccountId>
+	for Reserves<C, Block, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ReservesRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Send + Sync + 'static,
+	Balance: Codec + Into<NumberOrHex> + Send + Sync + 'static,
+{
+	fn breakdown(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<ReserveBreakdownDetails> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let breakdown = api.breakdown(&at, account).map_err(runtime_error_into_rpc_err)?;
+
+		Ok(ReserveBreakdownDetails {
+			total: breakdown.total.into(),
+			reserves: breakdown
+				.reserves
+				.into_iter()
+				.map(|reserve| ReservedBalanceDetails {
+					reason: reserve.reason,
+					id: reserve.id.map(|id| String::from_utf8_lossy(&id).into_owned()),
+					amount: reserve.amount.into(),
+				})
+				.collect(),
+		})
+	}
+}
@@ -0,0 +1,104 @@
+//! Local stand-in for a licensing authority's roster feed.
+//!
+//! Serves a roster file over HTTP, signed the way `pallet-roster-sync` expects,
+//! so the roster sync offchain worker can be exercised on a dev chain. The file
+//! is read and signed again on every request, so edits show up at the next
+//! sync.
+
+use clap::Parser;
+use sp_core::{sr25519, Pair};
+use std::{
+	fs,
+	io::{self, BufRead, BufReader, Write},
+	net::{TcpListener, TcpStream},
+	path::PathBuf,
+};
+
+#[derive(Debug, Parser)]
+#[clap(name = "build3-roster-feed", about = "Serve a signed license roster over HTTP.")]
+struct Opts {
+	/// Roster file to serve, without its signature line.
+	#[clap(long, parse(from_os_str), default_value = "roster-feed/fixtures/virginia.csv")]
+	roster: PathBuf,
+
+	/// Path the roster is served at.
+	#[clap(long, default_value = "/virginia.csv")]
+	path: String,
+
+	/// Address to listen on.
+	#[clap(long, default_value = "127.0.0.1:8090")]
+	listen: String,
+
+	/// Secret URI of the authority's roster key.
+	#[clap(long, default_value = "//VirginiaRoster")]
+	suri: String,
+}
+
+fn main() -> Result<(), String> {
+	let opts = Opts::parse();
+
+	let pair = sr25519::Pair::from_string(&opts.suri, None)
+		.map_err(|e| format!("Invalid roster key: {:?}", e))?;
+	let listener =
+		TcpListener::bind(&opts.listen).map_err(|e| format!("Binding {}: {}", opts.listen, e))?;
+
+	eprintln!(
+		"build3-roster-feed serving {} at http://{}{}, signed by 0x{}",
+		opts.roster.display(),
+		opts.listen,
+		opts.path,
+		hex(&pair.public().0),
+	);
+
+	for stream in listener.incoming() {
+		let stream = stream.map_err(|e| e.to_string())?;
+		if let Err(e) = handle_connection(stream, &opts, &pair) {
+			eprintln!("Connection closed: {}", e);
+		}
+	}
+
+	Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, opts: &Opts, pair: &sr25519::Pair) -> io::Result<()> {
+	let mut reader = BufReader::new(stream.try_clone()?);
+	let mut request_line = String::new();
+	reader.read_line(&mut request_line)?;
+	// Skip the headers, the roster does not depend on them.
+	let mut header = String::new();
+	while reader.read_line(&mut header)? > 2 {
+		header.clear();
+	}
+
+	let mut parts = request_line.split_whitespace();
+	let (status, body) = match (parts.next(), parts.next()) {
+		(Some("GET"), Some(path)) if path == opts.path => match fs::read(&opts.roster) {
+			Ok(roster) => ("200 OK", signed(roster, pair)),
+			Err(e) => ("500 Internal Server Error", format!("{}\n", e).into_bytes()),
+		},
+		(Some("GET"), _) => ("404 Not Found", b"Not found\n".to_vec()),
+		_ => ("405 Method Not Allowed", b"Only GET is supported\n".to_vec()),
+	};
+
+	write!(
+		stream,
+		"HTTP/1.1 {}\r\nContent-Type: text/csv\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+		status,
+		body.len(),
+	)?;
+	stream.write_all(&body)
+}
+
+/// `roster` followed by the signature line over it.
+fn signed(mut roster: Vec<u8>, pair: &sr25519::Pair) -> Vec<u8> {
+	if !roster.is_empty() && !roster.ends_with(b"\n") {
+		roster.push(b'\n');
+	}
+	let signature = pair.sign(&roster);
+	roster.extend_from_slice(format!("signature,0x{}\n", hex(&signature.0)).as_bytes());
+	roster
+}
+
+fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
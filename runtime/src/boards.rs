@@ -0,0 +1,229 @@
+//! Licensing boards and the origins they act through.
+//!
+//! Every state board is a `pallet_collective` instance. In the licensure pallet
+//! a board speaks through a motion of its collective, through its executive
+//! director, or through the 2-of-2 multisig account of its chair and another
+//! member. Which of these each action requires is set per board in the
+//! licensure pallet.
+//!
+//! Every board is also an identity registrar, acting through an account derived
+//! from the board. It is added to the identity pallet on its first judgement, so
+//! registrars no longer need to be added by hand.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, traits::EnsureOrigin, PalletId};
+use frame_system::RawOrigin;
+use pallet_identity::{Judgement, RegistrarIndex};
+use pallet_licensure::{BoardApproval, BoardRegistrar, IdentityJudgement};
+use pallet_licensure_rpc_runtime_api::BoardMotion;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::AccountIdConversion, MultiAddress, RuntimeDebug};
+
+/// Boards' registrar accounts are derived from this id.
+const BOARDS_PALLET_ID: PalletId = PalletId(*b"b3/board");
+
+/// A state licensing board.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum Board {
+	/// Virginia Board for Architects, Professional Engineers, Land Surveyors, ...
+	Virginia,
+}
+
+impl Board {
+	/// Every board of the runtime.
+	pub fn all() -> Vec<Board> {
+		vec![Board::Virginia]
+	}
+
+	/// The account the board gives identity judgements from.
+	pub fn registrar_account(&self) -> AccountId {
+		BOARDS_PALLET_ID.into_sub_account(self)
+	}
+}
+
+/// Ensures the origin is a majority of a board's collective, yielding that board.
+pub struct EnsureBoardMajority;
+impl EnsureOrigin<Origin> for EnsureBoardMajority {
+	type Success = Board;
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let o: Result<pallet_collective::RawOrigin<AccountId, BoardVirginiaCollective>, Origin> =
+			o.into();
+		match o {
+			Ok(pallet_collective::RawOrigin::Members(n, m)) if n * 2 > m => Ok(Board::Virginia),
+			Ok(o) => Err(Origin::from(o)),
+			Err(o) => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::from(pallet_collective::RawOrigin::<AccountId, BoardVirginiaCollective>::Members(
+			1, 1,
+		))
+	}
+}
+
+/// Ensures the origin speaks for a board in the licensure pallet, yielding the
+/// board and the approval the origin carries.
+pub struct EnsureBoard;
+impl EnsureOrigin<Origin> for EnsureBoard {
+	type Success = (Board, BoardApproval);
+
+	fn try_origin(o: Origin) -> Result<Self::Success, Origin> {
+		let o: Result<pallet_collective::RawOrigin<AccountId, BoardVirginiaCollective>, Origin> =
+			o.into();
+		let o = match o {
+			Ok(pallet_collective::RawOrigin::Members(ayes, members)) =>
+				return Ok((Board::Virginia, BoardApproval::Motion { ayes, members })),
+			Ok(o) => return Err(Origin::from(o)),
+			Err(o) => o,
+		};
+		match o.clone().into() {
+			Ok(RawOrigin::Signed(who)) => officer_approval(&who).ok_or(o),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> Origin {
+		Origin::from(pallet_collective::RawOrigin::<AccountId, BoardVirginiaCollective>::Members(
+			1, 1,
+		))
+	}
+}
+
+/// The board `who` acts for as an officer: as its executive director, or as the
+/// 2-of-2 multisig account of its chair and another of its members.
+fn officer_approval(who: &AccountId) -> Option<(Board, BoardApproval)> {
+	Board::all().into_iter().find_map(|board| {
+		let officers = Licensure::board_officers(board);
+		if officers.executive_director.as_ref() == Some(who) {
+			return Some((board, BoardApproval::ExecutiveDirector))
+		}
+
+		let chair = officers.chair?;
+		let members = members(board);
+		if !members.contains(&chair) {
+			return None
+		}
+		members
+			.into_iter()
+			.filter(|member| *member != chair)
+			.any(|member| {
+				let mut signatories = [chair.clone(), member];
+				signatories.sort();
+				Multisig::multi_account_id(&signatories, 2) == *who
+			})
+			.then(|| (board, BoardApproval::ChairPlusOne))
+	})
+}
+
+/// Current members of `board`.
+pub fn members(board: Board) -> Vec<AccountId> {
+	match board {
+		Board::Virginia => BoardVirginia::members(),
+	}
+}
+
+/// Motions pending before `board`.
+pub fn motions(board: Board) -> Vec<BoardMotion<AccountId, BlockNumber, Hash>> {
+	match board {
+		Board::Virginia => collective_motions::<BoardVirginiaCollective>(),
+	}
+}
+
+fn collective_motions<I: 'static>() -> Vec<BoardMotion<AccountId, BlockNumber, Hash>>
+where
+	Runtime: pallet_collective::Config<I>,
+{
+	pallet_collective::Pallet::<Runtime, I>::proposals()
+		.into_iter()
+		.filter_map(|hash| {
+			let votes = pallet_collective::Pallet::<Runtime, I>::voting(&hash)?;
+			Some(BoardMotion {
+				hash,
+				index: votes.index,
+				threshold: votes.threshold,
+				ayes: votes.ayes,
+				nays: votes.nays,
+				end: votes.end,
+			})
+		})
+		.collect()
+}
+
+/// Gives judgements in the identity pallet from each board's registrar account.
+pub struct BoardRegistrars;
+impl BoardRegistrar<AccountId, Board> for BoardRegistrars {
+	fn provide_judgement(
+		board: Board,
+		who: &AccountId,
+		judgement: IdentityJudgement,
+	) -> DispatchResult {
+		let index = match registrar_index(board) {
+			Some(index) => index,
+			None => {
+				Identity::add_registrar(RawOrigin::Root.into(), board.registrar_account())
+					.map_err(|e| e.error)?;
+				registrar_index(board).expect("registrar was just added; qed")
+			},
+		};
+		let judgement = match judgement {
+			IdentityJudgement::KnownGood => Judgement::KnownGood,
+			IdentityJudgement::Reasonable => Judgement::Reasonable,
+			IdentityJudgement::OutOfDate => Judgement::OutOfDate,
+			IdentityJudgement::LowQuality => Judgement::LowQuality,
+			IdentityJudgement::Erroneous => Judgement::Erroneous,
+		};
+
+		Identity::provide_judgement(
+			RawOrigin::Signed(board.registrar_account()).into(),
+			index,
+			MultiAddress::Id(who.clone()),
+			judgement,
+		)
+		.map(|_| ())
+		.map_err(|e| e.error)
+	}
+
+	fn judgement(board: Board, who: &AccountId) -> Option<IdentityJudgement> {
+		let index = registrar_index(board)?;
+		let registration = Identity::identity(who)?;
+		let (_, judgement) = registration.judgements.iter().find(|(i, _)| *i == index)?;
+		match judgement {
+			Judgement::KnownGood => Some(IdentityJudgement::KnownGood),
+			Judgement::Reasonable => Some(IdentityJudgement::Reasonable),
+			Judgement::OutOfDate => Some(IdentityJudgement::OutOfDate),
+			Judgement::LowQuality => Some(IdentityJudgement::LowQuality),
+			Judgement::Erroneous => Some(IdentityJudgement::Erroneous),
+			Judgement::Unknown | Judgement::FeePaid(_) => None,
+		}
+	}
+}
+
+/// Index of `board`'s registrar in the identity pallet, if it has been added.
+fn registrar_index(board: Board) -> Option<RegistrarIndex> {
+	let account = board.registrar_account();
+	Identity::registrars()
+		.iter()
+		.position(|registrar| registrar.as_ref().map_or(false, |r| r.account == account))
+		.map(|index| index as RegistrarIndex)
+}
@@ -0,0 +1,109 @@
+//! The runtime's collectives, as seen by the governance RPC.
+//!
+//! Every `pallet_collective` instance is named by a [`Collective`], so that
+//! portals can follow the motions of the council and of each board alike.
+
+use super::*;
+use build3_governance_rpc_runtime_api::{CollectiveProposal, CollectiveState};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::dispatch::GetCallMetadata;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+
+/// A collective instance of the runtime.
+#[derive(
+	Encode,
+	Decode,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum Collective {
+	/// The council.
+	Council,
+	/// The collective of a state board.
+	Board(Board),
+}
+
+impl Collective {
+	/// Every collective of the runtime.
+	pub fn all() -> Vec<Collective> {
+		vec![Collective::Council, Collective::Board(Board::Virginia)]
+	}
+}
+
+/// Members, prime and open motions of `collective`.
+pub fn state(collective: Collective) -> CollectiveState<AccountId, BlockNumber, Hash> {
+	match collective {
+		Collective::Council => state_of::<CouncilCollective>(),
+		Collective::Board(Board::Virginia) => state_of::<BoardVirginiaCollective>(),
+	}
+}
+
+/// Motions of `collective` closed in the current block, with whether each was
+/// approved.
+pub fn closed_proposals(collective: Collective) -> Vec<(Hash, bool)> {
+	match collective {
+		Collective::Council => closed_proposals_of::<CouncilCollective>(),
+		Collective::Board(Board::Virginia) => closed_proposals_of::<BoardVirginiaCollective>(),
+	}
+}
+
+fn state_of<I: 'static>() -> CollectiveState<AccountId, BlockNumber, Hash>
+where
+	Runtime: pallet_collective::Config<I, Proposal = Call>,
+{
+	let now = System::block_number();
+	let proposals = pallet_collective::Pallet::<Runtime, I>::proposals()
+		.into_iter()
+		.filter_map(|hash| {
+			let votes = pallet_collective::Pallet::<Runtime, I>::voting(&hash)?;
+			let call = pallet_collective::Pallet::<Runtime, I>::proposal_of(&hash)?;
+			let metadata = call.get_call_metadata();
+			Some(CollectiveProposal {
+				hash,
+				index: votes.index,
+				pallet: metadata.pallet_name.as_bytes().to_vec(),
+				method: metadata.function_name.as_bytes().to_vec(),
+				call: call.encode(),
+				threshold: votes.threshold,
+				ayes: votes.ayes,
+				nays: votes.nays,
+				end: votes.end,
+				blocks_remaining: votes.end.saturating_sub(now),
+			})
+		})
+		.collect();
+
+	CollectiveState {
+		members: pallet_collective::Pallet::<Runtime, I>::members(),
+		prime: pallet_collective::Pallet::<Runtime, I>::prime(),
+		motion_duration: <Runtime as pallet_collective::Config<I>>::MotionDuration::get(),
+		proposals,
+	}
+}
+
+fn closed_proposals_of<I: 'static>() -> Vec<(Hash, bool)>
+where
+	Runtime: pallet_collective::Config<I>,
+	Event: TryInto<pallet_collective::Event<Runtime, I>>,
+{
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event.try_into().ok()? {
+			pallet_collective::Event::Approved { proposal_hash } => Some((proposal_hash, true)),
+			pallet_collective::Event::Disapproved { proposal_hash } => Some((proposal_hash, false)),
+			_ => None,
+		})
+		.collect()
+}
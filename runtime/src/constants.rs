@@ -49,6 +49,7 @@ pub mod block_time {
 	pub const MAXIMUM_BLOCK_WEIGHT: Weight = 2 * WEIGHT_PER_SECOND;
 
 	// Prints debug output of the `contracts` pallet to stdout if the node is
-	// started with `-lruntime::contracts=debug`.
-	pub const CONTRACTS_DEBUG_OUTPUT: bool = true;
+	// started with `-lruntime::contracts=debug`. Only the dev runtime collects
+	// it, production RPC calls never return it.
+	pub const CONTRACTS_DEBUG_OUTPUT: bool = cfg!(feature = "dev-runtime");
 }
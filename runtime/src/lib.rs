@@ -11,8 +11,13 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 /// FRAME crates
 use frame_support::{
-	construct_runtime, parameter_types,
-	traits::{ConstU16, ConstU32, EnsureOneOf, KeyOwnerProofSystem},
+	construct_runtime,
+	dispatch::DispatchResult,
+	parameter_types,
+	traits::{
+		ConstU16, ConstU32, Contains, EnsureOneOf, Get, KeyOwnerProofSystem, LockIdentifier,
+		U128CurrencyToVote,
+	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight},
 		DispatchClass, IdentityFee, Weight,
@@ -40,13 +45,18 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, NumberFor},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult,
+	traits::{
+		BlakeTwo256, Block as BlockT, ConvertInto, Extrinsic as ExtrinsicT, NumberFor,
+		SaturatedConversion, Verify,
+	},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, Perbill,
 };
 
 use sp_std::prelude::*;
 
+use codec::Encode;
+
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 
@@ -59,6 +69,27 @@ pub use sp_version::RuntimeVersion;
 /// Constant values used within the runtime.
 pub mod constants;
 
+/// Licensing boards and their origins.
+pub mod boards;
+
+pub use boards::Board;
+
+/// Collective instances followed by the governance RPC.
+pub mod collectives;
+
+pub use collectives::Collective;
+
+/// Kinds of proxies and the calls they may make.
+pub mod proxy;
+
+pub use proxy::ProxyType;
+
+/// Named reserves and their reasons.
+pub mod reserves;
+
+/// Storage migrations of runtime upgrades.
+pub mod migrations;
+
 pub use constants::{block_time::*, currency::*};
 
 /// Node primitives
@@ -73,6 +104,8 @@ pub use pallet_collective::{
 	Call as CollectiveCall, MoreThanMajorityThenPrimeDefaultVote, RawOrigin as CollectiveOrigin,
 };
 
+pub use pallet_licensure::{ApprovalRule, Discipline, LicenseStatus, LicensureAction};
+
 use pallet_transaction_payment::CurrencyAdapter;
 
 /// Complex storage builder stuff
@@ -110,34 +143,58 @@ pub fn native_version() -> NativeVersion {
 	NativeVersion { runtime_version: VERSION, can_author_with: Default::default() }
 }
 
-// To learn more about runtime versioning and what each of the following value means:
-//   https://docs.substrate.io/v3/runtime/upgrades#runtime-versioning
-#[sp_version::runtime_version]
-/// The runtime version
-pub const VERSION: RuntimeVersion = RuntimeVersion {
-	/// The identified for the different Substrate runtimes.
-	spec_name: create_runtime_str!("build3-node"),
-	// The name of the implementation of the spec. This is of little
-	// consequence for the node and serves only to differentiate code of
-	// different implementation teams.
-	impl_name: create_runtime_str!("build3-node"),
-	authoring_version: 1,
-	// Per convention: if the runtime behavior changes, increment spec_version
-	// and set impl_version to 0. If only runtime
-	// implementation changes and behavior does not, then leave spec_version as
-	// is and increment impl_version.
-
-	// The version of the runtime specification. A full node will not attempt to use its native
-	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
-	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
-	impl_version: 1,
-	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
-	state_version: 1,
-};
+/// `spec_name` of the dev runtime, built with the `dev-runtime` feature. It
+/// enables unstable contract interfaces and contract debug output, and the node
+/// refuses to run it on a live chain.
+pub const DEV_SPEC_NAME: &str = "build3-node-dev";
+
+/// Whether this is the dev runtime.
+pub const IS_DEV_RUNTIME: bool = cfg!(feature = "dev-runtime");
+
+/// Defines `VERSION` under `spec_name`.
+///
+/// The dev runtime only differs from the production runtime in its `spec_name`,
+/// so that neither can be mistaken for the other on chain. Both are defined
+/// here as `#[runtime_version]` only accepts literals, not shared constants.
+macro_rules! runtime_version {
+	($spec_name:tt) => {
+		// To learn more about runtime versioning and what each of the following value means:
+		//   https://docs.substrate.io/v3/runtime/upgrades#runtime-versioning
+		#[sp_version::runtime_version]
+		/// The runtime version
+		pub const VERSION: RuntimeVersion = RuntimeVersion {
+			/// The identified for the different Substrate runtimes.
+			spec_name: create_runtime_str!($spec_name),
+			// The name of the implementation of the spec. This is of little
+			// consequence for the node and serves only to differentiate code of
+			// different implementation teams.
+			impl_name: create_runtime_str!("build3-node"),
+			authoring_version: 1,
+			// Per convention: if the runtime behavior changes, increment spec_version
+			// and set impl_version to 0. If only runtime
+			// implementation changes and behavior does not, then leave spec_version as
+			// is and increment impl_version.
+
+			// The version of the runtime specification. A full node will not attempt to use its
+			//   native runtime in substitute for the on-chain Wasm runtime unless all of
+			//   `spec_name`, `spec_version`, and `authoring_version` are the same between Wasm
+			//   and native.
+			// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps)
+			//   to use the compatible custom types.
+			spec_version: 101,
+			impl_version: 1,
+			apis: RUNTIME_API_VERSIONS,
+			transaction_version: 2,
+			state_version: 1,
+		};
+	};
+}
+
+#[cfg(not(feature = "dev-runtime"))]
+runtime_version!("build3-node");
+// `DEV_SPEC_NAME`, spelled out for the same reason.
+#[cfg(feature = "dev-runtime")]
+runtime_version!("build3-node-dev");
 
 // Prepare the frame_system storage
 parameter_types! {
@@ -172,10 +229,31 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
 }
 
+/// Calls which may not be dispatched at all.
+///
+/// Contract code is only uploaded through `ContractsRegistry::upload_code`,
+/// which checks the uploader allowlist, so the upload calls of the contracts
+/// pallet itself are filtered out. Likewise council votes are only placed
+/// through `CouncilElections::vote`, which checks the voter is licensed, and
+/// the retired council membership pallet takes no calls.
+pub struct BaseFilter;
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		!matches!(
+			call,
+			Call::Contracts(
+				pallet_contracts::Call::upload_code { .. } |
+					pallet_contracts::Call::instantiate_with_code { .. }
+			) | Call::Elections(pallet_elections_phragmen::Call::vote { .. }) |
+				Call::CouncilMemberManager(_)
+		)
+	}
+}
+
 /// Configure FRAME pallets to include in runtime.
 impl frame_system::Config for Runtime {
 	/// The basic call filter to use in dispatchable.
-	type BaseCallFilter = frame_support::traits::Everything;
+	type BaseCallFilter = BaseFilter;
 	/// Block & extrinsics weights: base values and limits.
 	type BlockWeights = RuntimeBlockWeights;
 	/// The maximum length of a block (in bytes).
@@ -185,7 +263,8 @@ impl frame_system::Config for Runtime {
 	/// The aggregated dispatch type that is available for extrinsics.
 	type Call = Call;
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
-	type Lookup = AccountIdLookup<AccountId, ()>;
+	/// Accepts full account IDs as well as the short indices of `pallet_indices`.
+	type Lookup = Indices;
 	/// The index type for storing how many extrinsics an account has signed.
 	type Index = Index;
 	/// The index type for blocks.
@@ -225,8 +304,109 @@ impl frame_system::Config for Runtime {
 	type MaxConsumers = ConstU32<16>;
 }
 
+/// Transactions the runtime's offchain workers submit are signed with the same
+/// keys as user transactions.
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+/// Offchain workers sign their transactions like wallets do, with a mortal era
+/// and the account's next nonce.
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: Extra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
+}
+
+/// Accounts of the Aura authorities, which take part in the randomness beacon
+/// with their Aura keys.
+pub struct AuraAccounts;
+impl Get<Vec<AccountId>> for AuraAccounts {
+	fn get() -> Vec<AccountId> {
+		Aura::authorities()
+			.into_iter()
+			.map(|id| sp_core::sr25519::Public::from(id).into())
+			.collect()
+	}
+}
+
+// Randomness beacon constant configurations
+parameter_types! {
+	pub const RandomnessRoundLength: BlockNumber = 10 * MINUTES;
+	// A supermajority of the authorities must reveal for the seed to change.
+	pub const RandomnessRevealThreshold: Perbill = Perbill::from_percent(67);
+	pub const RandomnessReserveId: [u8; 8] = reserves::RANDOMNESS_BOND;
+	pub const RandomnessRevealBond: Balance = 10 * DOLLARS;
+	pub const RandomnessUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+// Indices storage
+parameter_types! {
+	pub const IndexDeposit: Balance = DOLLARS;
+}
+
+/// Indices configuration
+/// Short numeric indices standing in for accounts, for references typed by
+/// hand. Claiming an index reserves `IndexDeposit` until it is freed.
+impl pallet_indices::Config for Runtime {
+	type AccountIndex = AccountIndex;
+	type Currency = Balances;
+	type Deposit = IndexDeposit;
+	type Event = Event;
+	type WeightInfo = pallet_indices::weights::SubstrateWeight<Runtime>;
+}
+
 /// A pallet which provides a randomness function
-impl pallet_randomness_collective_flip::Config for Runtime {}
+/// The authorities commit to and reveal secrets each round, so that no single
+/// authority can predict the output. Authorities bond `RandomnessRevealBond`
+/// from their Aura account for each commitment, burned if they do not reveal.
+/// See the pallet documentation for the bias that remains.
+impl pallet_randomness_beacon::Config for Runtime {
+	type Event = Event;
+	type AuthorityId = pallet_randomness_beacon::crypto::AuthorityId;
+	type Participants = AuraAccounts;
+	type RoundLength = RandomnessRoundLength;
+	type RevealThreshold = RandomnessRevealThreshold;
+	type Currency = Balances;
+	type ReserveId = RandomnessReserveId;
+	type RevealBond = RandomnessRevealBond;
+	type Slash = ();
+	type MaxParticipants = MaxAuthorities;
+	type UnsignedPriority = RandomnessUnsignedPriority;
+	type WeightInfo = pallet_randomness_beacon::weights::SubstrateWeight<Runtime>;
+}
 
 parameter_types! {
 	// One storage item; key size is 32; value is size 4+4+16+32 bytes = 56 bytes.
@@ -287,8 +467,8 @@ impl pallet_identity::Config for Runtime {
 	/// the council.
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 
-	/// The origin which may add or remove registrars. Right now only set to
-	/// root but can eventually be at the vote of a council.
+	/// The origin which may add or remove registrars by hand. Boards become
+	/// registrars on their own when they first judge an identity.
 	type RegistrarOrigin = EnsureRootOrHalfCouncil;
 	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
 }
@@ -332,12 +512,14 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
 	pub const ExistentialDeposit: u128 = EXISTENTIAL_DEPOSIT;
 	pub const MaxLocks: u32 = 50;
+	// One per named reserve of `reserves`, with room for more.
+	pub const MaxReserves: u32 = 50;
 }
 
 /// Balances configuration
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	type Balance = Balance;
 	type Event = Event;
@@ -373,22 +555,66 @@ type EnsureRootOrHalfCouncil = EnsureOneOf<
 	EnsureRoot<AccountId>,
 	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
 >;
+// Council elections constant configurations
+parameter_types! {
+	pub const ElectionsPhragmenPalletId: LockIdentifier = *b"phrelect";
+	pub const CandidacyBond: Balance = 100 * DOLLARS;
+	// 1 storage item created, key size is 32 bytes, value size is 16 + 16.
+	pub const VotingBondBase: Balance = deposit(1, 64);
+	// Additional data per vote is 32 bytes (account id).
+	pub const VotingBondFactor: Balance = deposit(0, 32);
+	pub const CouncilTermDuration: BlockNumber = 90 * DAYS;
+	pub const DesiredMembers: u32 = 9;
+	pub const DesiredRunnersUp: u32 = 7;
+}
+
+/// Council elections configuration
+/// Council seats are held for a term and filled by a Phragmen election among
+/// bonded candidates, with runners-up stepping in when a seat is vacated.
+impl pallet_elections_phragmen::Config for Runtime {
+	type Event = Event;
+	type PalletId = ElectionsPhragmenPalletId;
+	type Currency = Balances;
+	type ChangeMembers = Council;
+	type InitializeMembers = Council;
+	type CurrencyToVote = U128CurrencyToVote;
+	type CandidacyBond = CandidacyBond;
+	type VotingBondBase = VotingBondBase;
+	type VotingBondFactor = VotingBondFactor;
+	type LoserCandidate = ();
+	type KickedMember = ();
+	type DesiredMembers = DesiredMembers;
+	type DesiredRunnersUp = DesiredRunnersUp;
+	type TermDuration = CouncilTermDuration;
+	type WeightInfo = pallet_elections_phragmen::weights::SubstrateWeight<Runtime>;
+}
+
 /// Define the name for the membership instance of the Build3 Council
 type CouncilMembership = pallet_collective::Instance1;
-/// Build3 Council membership configuration
+/// Retired Build3 Council membership configuration
+/// The council is elected by `Elections` now. The pallet keeps its place so
+/// that the pallets after it keep their index; its calls are filtered out, it
+/// no longer changes the council, and its members were handed to `Elections`
+/// by `migrations::SeedElectionsFromMembership`.
 impl pallet_membership::Config<CouncilMembership> for Runtime {
 	type Event = Event;
-	type AddOrigin = EnsureRootOrHalfCouncil;
-	type RemoveOrigin = EnsureRootOrHalfCouncil;
-	type SwapOrigin = EnsureRootOrHalfCouncil;
-	type ResetOrigin = EnsureRootOrHalfCouncil;
-	type PrimeOrigin = EnsureRootOrHalfCouncil;
-	type MembershipInitialized = Council;
-	type MembershipChanged = Council;
+	type AddOrigin = EnsureRoot<AccountId>;
+	type RemoveOrigin = EnsureRoot<AccountId>;
+	type SwapOrigin = EnsureRoot<AccountId>;
+	type ResetOrigin = EnsureRoot<AccountId>;
+	type PrimeOrigin = EnsureRoot<AccountId>;
+	type MembershipInitialized = ();
+	type MembershipChanged = ();
 	type MaxMembers = CouncilMaxMembers;
 	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
 }
 
+/// Council elections voter configuration
+/// Only engineers holding a license in good standing vote for the council.
+impl pallet_council_elections::Config for Runtime {
+	type Electorate = pallet_licensure::LicensedEngineers<Runtime>;
+}
+
 // Board of VA constant configurations
 parameter_types! {
 	pub const BoardVirginiaMotionDuration: BlockNumber = 5 * DAYS;
@@ -432,6 +658,98 @@ impl pallet_membership::Config<BoardVirginiaMembership> for Runtime {
 	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
 }
 
+// Vesting constant configurations
+parameter_types! {
+	pub const MinVestedTransfer: Balance = 100 * DOLLARS;
+}
+
+/// Vesting configuration
+/// Foundation and early-contributor balances unlock over time, following the
+/// schedules set at genesis or granted later.
+impl pallet_vesting::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	// `VestingInfo` encode length is 36bytes. 28 schedules gets encoded as 1009 bytes, which is the
+	// highest number of schedules that encodes less than 2^10.
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+/// Forced vested transfers approved by the council rather than the sudo key.
+impl pallet_vesting_governance::Config for Runtime {
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+}
+
+/// Utility configuration
+/// Batches let a board approve many renewals in one motion and let multisig
+/// signatories approve several calls at once. A batch weighs the sum of its
+/// calls, so collective motions and multisig proposals carrying one are charged
+/// correctly.
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
+}
+
+// Proxy constant configurations
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = deposit(0, 33);
+	pub const MaxProxies: u32 = 32;
+	pub const AnnouncementDepositBase: Balance = deposit(1, 8);
+	// Additional storage item size of 68 bytes: account, call hash and block number.
+	pub const AnnouncementDepositFactor: Balance = deposit(0, 68);
+	pub const MaxPending: u32 = 32;
+}
+
+/// Proxy configuration
+/// Engineers and firms delegate licensure, governance or contract calls to
+/// staff accounts. Proxies added with a delay must announce each call and wait
+/// out the delay, giving the principal time to reject it.
+impl pallet_proxy::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+// Licensure constant configurations
+parameter_types! {
+	pub const MaxLicenseNumberLength: u32 = 32;
+	pub const MaxFirmNameLength: u32 = 128;
+	pub const MaxLicensesPerAccount: u32 = 16;
+	pub const MaxDisciplinaryActions: u32 = 64;
+}
+
+/// Licensure configuration
+/// Licenses, firms, seals and disciplinary records, each governed by the board
+/// which issued them. The council sets the approval boards need for each action.
+impl pallet_licensure::Config for Runtime {
+	type Event = Event;
+	type BoardId = Board;
+	type BoardOrigin = boards::EnsureBoard;
+	type SettingsOrigin = EnsureRootOrHalfCouncil;
+	type Registrar = boards::BoardRegistrars;
+	type MaxLicenseNumberLength = MaxLicenseNumberLength;
+	type MaxNameLength = MaxFirmNameLength;
+	type MaxLicensesPerAccount = MaxLicensesPerAccount;
+	type MaxDisciplinaryActions = MaxDisciplinaryActions;
+	type WeightInfo = pallet_licensure::weights::SubstrateWeight<Runtime>;
+}
+
 // Transaction storage
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1;
@@ -518,7 +836,7 @@ parameter_types! {
 /// Contracts pallet configuration
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = RandomnessBeacon;
 	type Currency = Balances;
 	type Event = Event;
 	type Call = Call;
@@ -541,6 +859,182 @@ impl pallet_contracts::Config for Runtime {
 	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
 }
 
+/// Stores code uploaded through the contracts registry in the contracts pallet.
+pub struct ContractsCodeStore;
+impl pallet_contracts_registry::CodeStore<AccountId, Hash> for ContractsCodeStore {
+	type Balance = Balance;
+
+	fn upload_code(
+		who: AccountId,
+		code: Vec<u8>,
+		storage_deposit_limit: Option<Balance>,
+	) -> DispatchResult {
+		Contracts::bare_upload_code(who, code, storage_deposit_limit).map(|_| ())
+	}
+
+	fn upload_code_weight(code_len: u32) -> Weight {
+		<Runtime as pallet_contracts::Config>::WeightInfo::upload_code(code_len / 1024)
+	}
+
+	fn code_hash(contract: &AccountId) -> Option<Hash> {
+		Contracts::code_hash(contract)
+	}
+}
+
+/// Contracts registry configuration
+/// The council decides who may upload contract code and keeps the registry of
+/// audited code.
+impl pallet_contracts_registry::Config for Runtime {
+	type Event = Event;
+	type CodeStore = ContractsCodeStore;
+	type UploaderOrigin = EnsureRootOrHalfCouncil;
+	type AuditOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_contracts_registry::weights::SubstrateWeight<Runtime>;
+}
+
+// Polls constant configurations
+parameter_types! {
+	pub const PollMinVotingPeriod: BlockNumber = 7 * DAYS;
+	pub const PollRevealPeriod: BlockNumber = 2 * DAYS;
+	pub const PollBindingQuorum: u32 = 50;
+	// Room for an X25519 sealed box of a vote.
+	pub const PollMaxBallotLength: u32 = 256;
+}
+
+/// Voters in polls: engineers holding a license in good standing.
+pub struct PollLicensees;
+impl pallet_polls::Licensees<AccountId, Board> for PollLicensees {
+	fn is_licensee(who: &AccountId, board: Option<Board>) -> bool {
+		match board {
+			Some(board) => Licensure::has_active_license(who, &board, &System::block_number()),
+			None => Licensure::is_licensed(who),
+		}
+	}
+}
+
+/// Polls configuration
+/// The council polls every licensee and each board its own, one vote per
+/// licensed engineer. Binding outcomes need at least `PollBindingQuorum` votes,
+/// and are dispatched with the origin of the collective which opened the poll.
+impl pallet_polls::Config for Runtime {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type BoardId = Board;
+	type CouncilOrigin = EnsureRootOrHalfCouncil;
+	type BoardOrigin = boards::EnsureBoardMajority;
+	type Licensees = PollLicensees;
+	type MinVotingPeriod = PollMinVotingPeriod;
+	type RevealPeriod = PollRevealPeriod;
+	type BindingQuorum = PollBindingQuorum;
+	type MaxBallotLength = PollMaxBallotLength;
+	type WeightInfo = pallet_polls::weights::SubstrateWeight<Runtime>;
+}
+
+// Roster sync constant configurations
+parameter_types! {
+	pub const RosterSyncInterval: BlockNumber = 10 * MINUTES;
+	pub const RosterMaxUrlLength: u32 = 256;
+	pub const RosterMaxCorrectionsPerSync: u32 = 20;
+}
+
+/// Roster sync configuration
+/// The offchain workers of roster reporters compare each board's official
+/// roster with its licenses and propose corrections to the board.
+impl pallet_roster_sync::Config for Runtime {
+	type Event = Event;
+	type ReporterId = pallet_roster_sync::crypto::ReporterId;
+	type FeedOrigin = EnsureRootOrHalfCouncil;
+	type SyncInterval = RosterSyncInterval;
+	type MaxUrlLength = RosterMaxUrlLength;
+	type MaxCorrectionsPerSync = RosterMaxCorrectionsPerSync;
+	type WeightInfo = pallet_roster_sync::weights::SubstrateWeight<Runtime>;
+}
+
+// DID constant configurations
+parameter_types! {
+	pub const DidMaxKeys: u32 = 10;
+	pub const DidMaxServices: u32 = 10;
+	pub const DidMaxDelegates: u32 = 20;
+	pub const DidMaxServiceFieldLength: u32 = 256;
+	pub const DidReserveId: [u8; 8] = reserves::DID_DEPOSIT;
+	pub const DidDeposit: Balance = deposit(1, 48);
+	pub const DidItemDeposit: Balance = deposit(1, 64);
+}
+
+/// DID configuration
+/// Every account is a `did:build3` DID; the pallet keeps the keys, services
+/// and delegates its controller adds to the DID document, against a deposit
+/// from the controller.
+impl pallet_did::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ReserveId = DidReserveId;
+	type DidDeposit = DidDeposit;
+	type ItemDeposit = DidItemDeposit;
+	type MaxKeys = DidMaxKeys;
+	type MaxServices = DidMaxServices;
+	type MaxDelegates = DidMaxDelegates;
+	type MaxServiceFieldLength = DidMaxServiceFieldLength;
+	type WeightInfo = pallet_did::weights::SubstrateWeight<Runtime>;
+}
+
+// Projects constant configurations
+parameter_types! {
+	pub const ProjectMaxTextLength: u32 = 256;
+	pub const ProjectReserveId: [u8; 8] = reserves::PROJECT_DEPOSIT;
+	pub const ProjectDeposit: Balance = 20 * DOLLARS;
+}
+
+/// Projects configuration
+/// Registry of construction projects, their engineers of record and the
+/// documents those sealed for them.
+impl pallet_projects::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ReserveId = ProjectReserveId;
+	type ProjectDeposit = ProjectDeposit;
+	type Slash = ();
+	type MaxTextLength = ProjectMaxTextLength;
+	type WeightInfo = pallet_projects::weights::SubstrateWeight<Runtime>;
+}
+
+// Plan review constant configurations
+parameter_types! {
+	pub const PlanReviewReserveId: [u8; 8] = reserves::PLAN_REVIEW_FEE;
+	pub const PlanReviewPeriod: BlockNumber = 14 * DAYS;
+}
+
+/// Plan review configuration
+/// Reviewers of each jurisdiction check the documents submitted for a permit,
+/// for a fee reserved from the project owner.
+impl pallet_plan_review::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ReserveId = PlanReviewReserveId;
+	type ReviewPeriod = PlanReviewPeriod;
+	type WeightInfo = pallet_plan_review::weights::SubstrateWeight<Runtime>;
+}
+
+// Escrow constant configurations
+parameter_types! {
+	pub const EscrowReserveId: [u8; 8] = reserves::ESCROW;
+	pub const EscrowApprovalPeriod: BlockNumber = 7 * DAYS;
+}
+
+/// Escrow configuration
+/// Milestone payments held in escrow until the engineer seals the deliverable
+/// and the client approves it. The council sits as arbitrator panel.
+impl pallet_escrow::Config for Runtime {
+	type Event = Event;
+	type Currency = Balances;
+	type ReserveId = EscrowReserveId;
+	type ArbitratorOrigin = EnsureRootOrHalfCouncil;
+	type ApprovalPeriod = EscrowApprovalPeriod;
+	type WeightInfo = pallet_escrow::weights::SubstrateWeight<Runtime>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -549,7 +1043,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system,
-		RandomnessCollectiveFlip: pallet_randomness_collective_flip,
+		RandomnessBeacon: pallet_randomness_beacon,
 		Timestamp: pallet_timestamp,
 		Aura: pallet_aura,
 		Grandpa: pallet_grandpa,
@@ -563,12 +1057,27 @@ construct_runtime!(
 		BoardVirginia: pallet_collective::<Instance2>,
 		BoardVirginiaMemberManager: pallet_membership::<Instance2>,
 		Multisig: pallet_multisig,
+		Licensure: pallet_licensure,
+		Vesting: pallet_vesting,
+		VestingGovernance: pallet_vesting_governance,
+		Proxy: pallet_proxy,
+		Utility: pallet_utility,
+		Indices: pallet_indices,
+		ContractsRegistry: pallet_contracts_registry,
+		Elections: pallet_elections_phragmen,
+		CouncilElections: pallet_council_elections,
+		Polls: pallet_polls,
+		RosterSync: pallet_roster_sync,
+		Did: pallet_did,
+		Projects: pallet_projects,
+		PlanReview: pallet_plan_review,
+		Escrow: pallet_escrow,
 
 	}
 );
 
 /// The address format for describing accounts.
-pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
+pub type Address = sp_runtime::MultiAddress<AccountId, AccountIndex>;
 
 /// Block header type as expected by this runtime.
 pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
@@ -591,6 +1100,9 @@ pub type Extra = (
 /// TODO: convert to checked extrinsics prior to production.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, Extra>;
 
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, Extra>;
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -598,6 +1110,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	migrations::Migrations,
 >;
 
 impl_runtime_apis! {
@@ -760,8 +1273,12 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_collective,  Council);
 			list_benchmark!(list, extra, pallet_collective,  BoardVirginia);
 			list_benchmark!(list, extra, pallet_membership, BoardVirginiaMemberManager);
-			list_benchmark!(list, extra, pallet_membership, CouncilMemberManager);
+			list_benchmark!(list, extra, pallet_elections_phragmen, Elections);
 			list_benchmark!(list, extra, pallet_multisig, Multisig);
+			list_benchmark!(list, extra, pallet_vesting, Vesting);
+			list_benchmark!(list, extra, pallet_proxy, Proxy);
+			list_benchmark!(list, extra, pallet_utility, Utility);
+			list_benchmark!(list, extra, pallet_indices, Indices);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -803,8 +1320,12 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_collective, Council);
 			add_benchmark!(params, bathces, pallet_collective, BoardVirginia);
 			add_benchmark!(params, batches, pallet_membership, BoardVirginiaMemberManager);
-			add_benchmark!(params, batches, pallet_membership, CouncilMemberManager);
+			add_benchmark!(params, batches, pallet_elections_phragmen, Elections);
 			add_benchmark!(params, batches, pallet_multisig, Multisig);
+			add_benchmark!(params, batches, pallet_vesting, Vesting);
+			add_benchmark!(params, batches, pallet_proxy, Proxy);
+			add_benchmark!(params, batches, pallet_utility, Utility);
+			add_benchmark!(params, batches, pallet_indices, Indices);
 
 			Ok(batches)
 		}
@@ -855,4 +1376,126 @@ impl_runtime_apis! {
 			Contracts::get_storage(address, key)
 		}
 	}
+
+	// Licensure queries for the `licensure_*` RPC namespace.
+	impl pallet_licensure_rpc_runtime_api::LicensureApi<Block, AccountId, Board, BlockNumber, Hash>
+		for Runtime
+	{
+		fn license(number: Vec<u8>) -> Option<pallet_licensure::LicenseOf<Runtime>> {
+			Licensure::license_info(number)
+		}
+
+		fn licenses_of(account: AccountId) -> Vec<(Vec<u8>, pallet_licensure::LicenseOf<Runtime>)> {
+			Licensure::licenses_info(account)
+		}
+
+		fn board_members(board: Board) -> Vec<AccountId> {
+			boards::members(board)
+		}
+
+		fn board_motions(
+			board: Board,
+		) -> Vec<pallet_licensure_rpc_runtime_api::BoardMotion<AccountId, BlockNumber, Hash>> {
+			boards::motions(board)
+		}
+
+		fn verify_seal(document: Hash) -> Option<pallet_licensure::SealVerificationOf<Runtime>> {
+			Licensure::verify_seal(document)
+		}
+
+		fn disciplinary_history(
+			number: Vec<u8>,
+		) -> Vec<pallet_licensure::DisciplinaryActionOf<Runtime>> {
+			Licensure::disciplinary_history(number)
+		}
+
+		fn credential_key(board: Board) -> Option<[u8; 32]> {
+			Licensure::credential_key(board)
+		}
+	}
+
+	impl pallet_did_rpc_runtime_api::DidApi<Block, AccountId, BlockNumber> for Runtime {
+		fn document(account: AccountId) -> pallet_did::DidDocumentOf<Runtime> {
+			Did::document(account)
+		}
+	}
+
+	impl pallet_projects_rpc_runtime_api::ProjectsApi<Block, AccountId, Board, BlockNumber, Hash>
+		for Runtime
+	{
+		fn project(
+			id: pallet_projects::ProjectId,
+		) -> Option<pallet_projects::ProjectDetailsOf<Runtime>> {
+			Projects::project_details(id)
+		}
+
+		fn project_by_permit(
+			jurisdiction: Board,
+			permit_number: Vec<u8>,
+		) -> Option<pallet_projects::ProjectId> {
+			Projects::project_of_permit(jurisdiction, permit_number)
+		}
+
+		fn project_of_document(document: Hash) -> Option<pallet_projects::ProjectId> {
+			Projects::project_of_document(document)
+		}
+
+		fn history(
+			id: pallet_projects::ProjectId,
+		) -> Vec<pallet_projects::HistoryEntryOf<Runtime>> {
+			Projects::history(id)
+		}
+	}
+
+	impl pallet_plan_review_rpc_runtime_api::PlanReviewApi<Block, Hash> for Runtime {
+		fn permit_status(
+			id: pallet_projects::ProjectId,
+		) -> Option<pallet_plan_review::PermitStatus<Hash>> {
+			PlanReview::permit_status(id)
+		}
+	}
+
+	impl build3_reserves_rpc_runtime_api::ReservesApi<Block, AccountId, Balance> for Runtime {
+		fn breakdown(
+			account: AccountId,
+		) -> build3_reserves_rpc_runtime_api::ReserveBreakdown<Balance> {
+			reserves::breakdown(&account)
+		}
+	}
+
+	impl pallet_contracts_registry_runtime_api::ContractsRegistryApi<Block, AccountId, BlockNumber, Hash>
+		for Runtime
+	{
+		fn code_audit(code_hash: Hash) -> Option<pallet_contracts_registry::CodeAuditOf<Runtime>> {
+			ContractsRegistry::code_audit(code_hash)
+		}
+
+		fn contract_audit(
+			contract: AccountId,
+		) -> Option<pallet_contracts_registry::CodeAuditOf<Runtime>> {
+			ContractsRegistry::contract_audit(&contract)
+		}
+
+		fn is_uploader(who: AccountId) -> bool {
+			ContractsRegistry::is_uploader(&who)
+		}
+	}
+
+	impl build3_governance_rpc_runtime_api::GovernanceApi<Block, AccountId, BlockNumber, Hash, Collective>
+		for Runtime
+	{
+		fn collectives() -> Vec<Collective> {
+			Collective::all()
+		}
+
+		fn collective(
+			collective: Collective,
+		) -> build3_governance_rpc_runtime_api::CollectiveState<AccountId, BlockNumber, Hash> {
+			collectives::state(collective)
+		}
+
+		fn closed_proposals(collective: Collective) -> Vec<(Hash, bool)> {
+			collectives::closed_proposals(collective)
+		}
+	}
 }
@@ -0,0 +1,63 @@
+//! Storage migrations run by the executive on runtime upgrades.
+//!
+//! Each migration checks whether it still has work to do, so that running it
+//! again in a later upgrade is harmless.
+
+use super::*;
+use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade};
+use pallet_elections_phragmen::SeatHolder;
+use sp_core::hashing::twox_128;
+use sp_io::KillStorageResult;
+
+/// Migrations of the next runtime upgrade, in order.
+pub type Migrations = (SeedElectionsFromMembership, RemoveRandomnessCollectiveFlip);
+
+/// Seats the council kept by `CouncilMemberManager`, from before the council
+/// was elected, as the members of the elections pallet.
+///
+/// Without it the elections pallet knows no members until the end of its first
+/// term, while the council collective keeps the old ones: `Elections` would
+/// report no seats, members could not renounce, and nobody would be a
+/// candidate for reelection. The seeded members hold no stake or deposit; they
+/// keep their seats until the first election, like every other member.
+///
+/// The membership pallet's storage is cleared, it no longer manages the
+/// council.
+pub struct SeedElectionsFromMembership;
+impl OnRuntimeUpgrade for SeedElectionsFromMembership {
+	fn on_runtime_upgrade() -> Weight {
+		let members = pallet_membership::Members::<Runtime, CouncilMembership>::take();
+		pallet_membership::Prime::<Runtime, CouncilMembership>::kill();
+		if members.is_empty() || !Elections::members().is_empty() {
+			return RocksDbWeight::get().reads_writes(2, 2)
+		}
+
+		let mut seats: Vec<_> = members
+			.into_iter()
+			.map(|who| SeatHolder { who, stake: 0, deposit: 0 })
+			.collect();
+		// The elections pallet keeps its members sorted by account.
+		seats.sort_by(|a, b| a.who.cmp(&b.who));
+		pallet_elections_phragmen::Members::<Runtime>::put(seats);
+
+		RocksDbWeight::get().reads_writes(2, 3)
+	}
+}
+
+/// Removes the storage of `RandomnessCollectiveFlip`, which the randomness
+/// beacon replaced.
+///
+/// The pallet left its `RandomMaterial`, the parent hashes of the last 81
+/// blocks, and its storage version behind under its prefix, where nothing reads
+/// them anymore.
+pub struct RemoveRandomnessCollectiveFlip;
+impl OnRuntimeUpgrade for RemoveRandomnessCollectiveFlip {
+	fn on_runtime_upgrade() -> Weight {
+		let removed = match unhashed::kill_prefix(&twox_128(b"RandomnessCollectiveFlip"), None) {
+			KillStorageResult::AllRemoved(removed) | KillStorageResult::SomeRemaining(removed) =>
+				removed,
+		};
+
+		RocksDbWeight::get().reads_writes(removed.into(), removed.into())
+	}
+}
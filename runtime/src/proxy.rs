@@ -0,0 +1,202 @@
+//! Kinds of proxies accounts can appoint.
+//!
+//! Engineers and firms let staff act for them without sharing their keys, each
+//! proxy limited to the calls its kind allows. Every kind may batch its calls
+//! through the utility pallet, which applies the same filter to each call of
+//! the batch.
+
+use super::*;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::InstanceFilter;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// The type used to represent the kinds of proxying allowed.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call at all.
+	Any,
+	/// Anything but moving funds: no balance, vested or index transfers, no
+	/// contract calls, which may carry value, and no sudo. Nor may it fund
+	/// escrows, pay review fees, or place or take back project and DID
+	/// deposits. Election bonds and multisig deposits stay allowed, as
+	/// `Governance` proxies place them too.
+	NonTransfer,
+	/// Council and board motions and votes, council candidacies and election
+	/// votes, poll votes, and multisig approvals.
+	Governance,
+	/// Every licensure call, e.g. for a firm's licensing staff.
+	LicensureAdmin,
+	/// Only affixing seals under the principal's licenses.
+	SealDraftOnly,
+	/// Only uploading, instantiating and calling contracts.
+	ContractsOnly,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<Call> for ProxyType {
+	fn filter(&self, c: &Call) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				Call::Balances(..) |
+					Call::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+					Call::Indices(pallet_indices::Call::transfer { .. }) |
+					Call::Contracts(..) |
+					Call::ContractsRegistry(pallet_contracts_registry::Call::upload_code { .. }) |
+					Call::Sudo(..) | Call::Escrow(pallet_escrow::Call::fund { .. }) |
+					Call::Escrow(pallet_escrow::Call::approve { .. }) |
+					Call::Escrow(pallet_escrow::Call::refund { .. }) |
+					Call::PlanReview(pallet_plan_review::Call::submit { .. }) |
+					Call::PlanReview(pallet_plan_review::Call::withdraw { .. }) |
+					Call::PlanReview(pallet_plan_review::Call::reclaim_fee { .. }) |
+					Call::Projects(pallet_projects::Call::register_project { .. }) |
+					Call::Projects(pallet_projects::Call::update_permit { .. }) |
+					Call::Projects(pallet_projects::Call::close_project { .. }) |
+					Call::Did(pallet_did::Call::set_controller { .. }) |
+					Call::Did(pallet_did::Call::add_key { .. }) |
+					Call::Did(pallet_did::Call::revoke_key { .. }) |
+					Call::Did(pallet_did::Call::add_service { .. }) |
+					Call::Did(pallet_did::Call::remove_service { .. }) |
+					Call::Did(pallet_did::Call::add_delegate { .. }) |
+					Call::Did(pallet_did::Call::remove_delegate { .. }) |
+					Call::Did(pallet_did::Call::deactivate { .. })
+			),
+			ProxyType::Governance => matches!(
+				c,
+				Call::Council(..) |
+					Call::Elections(..) |
+					Call::CouncilElections(..) |
+					Call::BoardVirginia(..) |
+					Call::Polls(..) | Call::Multisig(..) |
+					Call::Utility(..)
+			),
+			ProxyType::LicensureAdmin => matches!(c, Call::Licensure(..) | Call::Utility(..)),
+			ProxyType::SealDraftOnly => matches!(
+				c,
+				Call::Licensure(pallet_licensure::Call::affix_seal { .. }) | Call::Utility(..)
+			),
+			ProxyType::ContractsOnly => matches!(
+				c,
+				Call::Contracts(..) |
+					Call::ContractsRegistry(pallet_contracts_registry::Call::upload_code { .. }) |
+					Call::Utility(..)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(
+				ProxyType::NonTransfer,
+				ProxyType::Governance | ProxyType::LicensureAdmin | ProxyType::SealDraftOnly,
+			) => true,
+			(ProxyType::LicensureAdmin, ProxyType::SealDraftOnly) => true,
+			_ => false,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn did() -> <Indices as sp_runtime::traits::StaticLookup>::Source {
+		AccountId::new([1; 32]).into()
+	}
+
+	#[test]
+	fn non_transfer_proxies_move_no_funds() {
+		let moving = [
+			Call::Escrow(pallet_escrow::Call::approve { milestone: 0 }),
+			Call::Escrow(pallet_escrow::Call::refund { milestone: 0 }),
+			Call::PlanReview(pallet_plan_review::Call::withdraw { submission: 0 }),
+			Call::PlanReview(pallet_plan_review::Call::reclaim_fee { submission: 0 }),
+			Call::Projects(pallet_projects::Call::close_project { project: 0 }),
+			Call::Projects(pallet_projects::Call::update_permit {
+				project: 0,
+				permit_number: b"P-1".to_vec(),
+			}),
+			Call::Did(pallet_did::Call::deactivate { did: did() }),
+			Call::ContractsRegistry(pallet_contracts_registry::Call::upload_code {
+				code: Vec::new(),
+				storage_deposit_limit: None,
+			}),
+		];
+		for call in &moving {
+			assert!(!ProxyType::NonTransfer.filter(call), "{:?}", call);
+			assert!(ProxyType::Any.filter(call));
+		}
+
+		let still = [
+			Call::System(frame_system::Call::remark { remark: Vec::new() }),
+			Call::Escrow(pallet_escrow::Call::dispute { milestone: 0 }),
+			Call::PlanReview(pallet_plan_review::Call::open_round { submission: 0 }),
+			Call::Licensure(pallet_licensure::Call::affix_seal {
+				number: b"PE-1".to_vec(),
+				document: Default::default(),
+			}),
+		];
+		for call in &still {
+			assert!(ProxyType::NonTransfer.filter(call), "{:?}", call);
+		}
+	}
+
+	#[test]
+	fn supersets_allow_every_call_of_their_subsets() {
+		let calls = [
+			Call::System(frame_system::Call::remark { remark: Vec::new() }),
+			Call::Licensure(pallet_licensure::Call::affix_seal {
+				number: b"PE-1".to_vec(),
+				document: Default::default(),
+			}),
+			Call::Licensure(pallet_licensure::Call::set_credential_key { key: None }),
+			Call::Polls(pallet_polls::Call::close_poll { poll: 0, call_weight_bound: 0 }),
+			Call::Projects(pallet_projects::Call::close_project { project: 0 }),
+			Call::Did(pallet_did::Call::deactivate { did: did() }),
+			Call::Utility(pallet_utility::Call::batch { calls: Vec::new() }),
+		];
+		let kinds = [
+			ProxyType::Any,
+			ProxyType::NonTransfer,
+			ProxyType::Governance,
+			ProxyType::LicensureAdmin,
+			ProxyType::SealDraftOnly,
+			ProxyType::ContractsOnly,
+		];
+		for (kind, other) in kinds.iter().flat_map(|k| kinds.iter().map(move |o| (k, o))) {
+			if kind.is_superset(other) {
+				for call in calls.iter().filter(|call| other.filter(call)) {
+					assert!(
+						kind.filter(call),
+						"{:?} allows {:?} but {:?} does not",
+						other,
+						call,
+						kind
+					);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,65 @@
+//! The runtime's named reserves, as broken down by the reserves RPC.
+//!
+//! Pallets of this runtime reserve funds under an identifier of their own, so
+//! that the reserved balance of an account can be told apart by reason.
+//! Substrate's own pallets, such as identity, multisig, proxy and contracts,
+//! still reserve their deposits without a name.
+
+use super::*;
+use build3_reserves_rpc_runtime_api::{
+	ReserveBreakdown, ReserveIdentifier, ReserveReason, ReservedBalance,
+};
+use frame_support::traits::ReservableCurrency;
+use sp_runtime::traits::Saturating;
+
+/// Milestone payments held in escrow.
+pub const ESCROW: ReserveIdentifier = *b"b3escrow";
+/// Fees of plan reviews.
+pub const PLAN_REVIEW_FEE: ReserveIdentifier = *b"b3review";
+/// Bonds of randomness beacon commitments, until they are revealed.
+pub const RANDOMNESS_BOND: ReserveIdentifier = *b"b3rndbnd";
+/// Deposits of registered projects, against the permit numbers they claim.
+pub const PROJECT_DEPOSIT: ReserveIdentifier = *b"b3projdp";
+/// Deposits of DID documents and their keys, services and delegates.
+pub const DID_DEPOSIT: ReserveIdentifier = *b"b3diddep";
+
+/// What funds reserved under `id` are reserved for.
+pub fn reason(id: &ReserveIdentifier) -> ReserveReason {
+	match *id {
+		ESCROW => ReserveReason::Escrow,
+		PLAN_REVIEW_FEE => ReserveReason::PlanReviewFee,
+		RANDOMNESS_BOND => ReserveReason::RandomnessBond,
+		PROJECT_DEPOSIT => ReserveReason::ProjectDeposit,
+		DID_DEPOSIT => ReserveReason::DidDeposit,
+		_ => ReserveReason::Other,
+	}
+}
+
+/// Reserved balance of `who`, broken down by reason. Whatever is not part of a
+/// named reserve is reserved without a name.
+pub fn breakdown(who: &AccountId) -> ReserveBreakdown<Balance> {
+	let total = Balances::reserved_balance(who);
+	let mut reserves: Vec<_> = pallet_balances::Reserves::<Runtime>::get(who)
+		.into_iter()
+		.filter(|reserve| reserve.amount > 0)
+		.map(|reserve| ReservedBalance {
+			reason: reason(&reserve.id),
+			id: Some(reserve.id),
+			amount: reserve.amount,
+		})
+		.collect();
+
+	let named = reserves
+		.iter()
+		.fold(0, |named: Balance, reserve| named.saturating_add(reserve.amount));
+	let unnamed = total.saturating_sub(named);
+	if unnamed > 0 {
+		reserves.push(ReservedBalance {
+			reason: ReserveReason::Unnamed,
+			id: None,
+			amount: unnamed,
+		});
+	}
+
+	ReserveBreakdown { total, reserves }
+}